@@ -9,7 +9,7 @@
 use std::{env, path::PathBuf};
 
 use noodles_bam::{self as bam, bai};
-use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
+use noodles_csi::BinningIndex;
 use noodles_sam as sam;
 use tokio::fs::File;
 
@@ -22,22 +22,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let index = bai::r#async::read(src.with_extension("bam.bai")).await?;
 
-    for ((reference_sequence_name, reference_sequence), index_reference_sequence) in header
-        .reference_sequences()
-        .iter()
-        .zip(index.reference_sequences())
+    for ((reference_sequence_name, reference_sequence), statistics) in
+        header.reference_sequences().iter().zip(index.statistics()?)
     {
-        let (mapped_record_count, unmapped_record_count) = index_reference_sequence
-            .metadata()
-            .map(|m| (m.mapped_record_count(), m.unmapped_record_count()))
-            .unwrap_or_default();
-
         println!(
             "{}\t{}\t{}\t{}",
             reference_sequence_name,
             reference_sequence.length(),
-            mapped_record_count,
-            unmapped_record_count
+            statistics.mapped_record_count(),
+            statistics.unmapped_record_count()
         );
     }
 
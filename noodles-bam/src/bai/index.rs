@@ -7,10 +7,12 @@ pub use self::{builder::Builder, reference_sequence::ReferenceSequence};
 
 use std::io;
 
-use noodles_core::{region::Interval, Position};
+use noodles_bgzf as bgzf;
+use noodles_core::{region::Interval, Position, Region};
 use noodles_csi::{
     binning_index::optimize_chunks, index::reference_sequence::bin::Chunk, BinningIndex,
 };
+use noodles_sam as sam;
 
 const MIN_SHIFT: u8 = 14;
 const DEPTH: u8 = 5;
@@ -54,6 +56,56 @@ impl Index {
             n_no_coor,
         }
     }
+
+    /// Returns the estimated number of compressed bytes to read to query the given region.
+    ///
+    /// The region reference sequence name is resolved using the reference sequences of the given
+    /// header. See [`BinningIndex::estimated_compressed_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::num::NonZeroUsize;
+    /// use noodles_bam::bai::{
+    ///     self,
+    ///     index::{reference_sequence::Bin, ReferenceSequence},
+    /// };
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_core::Region;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    /// use noodles_sam::{self as sam, header::record::value::{map, Map}};
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(
+    ///         "sq0".parse()?,
+    ///         Map::<map::ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+    ///     )
+    ///     .build();
+    ///
+    /// let chunks = vec![Chunk::new(
+    ///     bgzf::VirtualPosition::try_from((8, 0))?,
+    ///     bgzf::VirtualPosition::try_from((21, 0))?,
+    /// )];
+    /// let bins = vec![Bin::new(0, chunks)];
+    /// let index = bai::Index::new(vec![ReferenceSequence::new(bins, Vec::new(), None)], None);
+    ///
+    /// let region = "sq0:5-8".parse()?;
+    /// assert_eq!(index.estimated_compressed_size_of_region(&header, &region)?, 13);
+    ///
+    /// let region = Region::new("sq1", ..);
+    /// assert!(index.estimated_compressed_size_of_region(&header, &region).is_err());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn estimated_compressed_size_of_region(
+        &self,
+        header: &sam::Header,
+        region: &Region,
+    ) -> io::Result<u64> {
+        let reference_sequence_id =
+            crate::reader::resolve_region(header.reference_sequences(), region)?;
+
+        self.estimated_compressed_size(reference_sequence_id, region.interval())
+    }
 }
 
 impl BinningIndex for Index {
@@ -124,6 +176,21 @@ impl BinningIndex for Index {
 
         Ok(merged_chunks)
     }
+
+    fn linear_index(
+        &self,
+        reference_sequence_id: usize,
+    ) -> io::Result<Vec<(Position, bgzf::VirtualPosition)>> {
+        self.reference_sequences()
+            .get(reference_sequence_id)
+            .map(|reference_sequence| reference_sequence.linear_index())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid reference sequence ID: {reference_sequence_id}"),
+                )
+            })
+    }
 }
 
 fn resolve_interval<I>(interval: I) -> io::Result<(Position, Position)>
@@ -152,3 +219,59 @@ where
         Ok((start, end))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimated_compressed_size_of_region_resolves_region_name(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use sam::header::record::value::{map, Map};
+
+        use self::reference_sequence::Bin;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<map::ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .add_reference_sequence(
+                "sq1".parse()?,
+                Map::<map::ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+            )
+            .build();
+
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::try_from((55, 0))?,
+            bgzf::VirtualPosition::try_from((89, 0))?,
+        );
+
+        let index = Index::new(
+            vec![
+                ReferenceSequence::default(),
+                ReferenceSequence::new(vec![Bin::new(0, vec![chunk])], Vec::new(), None),
+            ],
+            None,
+        );
+
+        // Only the second reference sequence has records.
+        let region = "sq1:2-5".parse()?;
+        let actual = index.estimated_compressed_size_of_region(&header, &region)?;
+        assert_eq!(
+            actual,
+            index.estimated_compressed_size(1, region.interval())?
+        );
+        assert!(actual > 0);
+
+        let region = Region::new("sq2", ..);
+        assert!(matches!(
+            index.estimated_compressed_size_of_region(&header, &region),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -130,6 +130,15 @@ impl ReferenceSequence {
         let i = (usize::from(start) - 1) / WINDOW_SIZE;
         self.intervals.get(i).copied().unwrap_or_default()
     }
+
+    pub(crate) fn linear_index(&self) -> Vec<(Position, bgzf::VirtualPosition)> {
+        self.intervals
+            .iter()
+            .enumerate()
+            .filter(|(_, pos)| **pos != bgzf::VirtualPosition::default())
+            .filter_map(|(i, pos)| Position::new(i * WINDOW_SIZE + 1).map(|start| (start, *pos)))
+            .collect()
+    }
 }
 
 impl ReferenceSequenceExt for ReferenceSequence {
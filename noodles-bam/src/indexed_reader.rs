@@ -14,7 +14,7 @@ use crate::reader::UnmappedRecords;
 
 use super::{
     bai, lazy,
-    reader::{LazyRecords, Query, Records},
+    reader::{LazyRecords, Query, Records, RegionsQuery},
     Reader,
 };
 
@@ -100,6 +100,15 @@ where
         self.inner.query(header, &self.index, region)
     }
 
+    /// Returns an iterator over records that intersect any of the given regions.
+    pub fn query_regions<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+        regions: &[Region],
+    ) -> io::Result<RegionsQuery<'a, R>> {
+        self.inner.query_regions(header, &self.index, regions)
+    }
+
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     pub fn query_unmapped(&mut self) -> io::Result<UnmappedRecords<'_, R>> {
         self.inner.query_unmapped(&self.index)
@@ -4,10 +4,15 @@ mod lazy_records;
 pub(crate) mod query;
 pub mod record;
 mod records;
+mod regions_query;
 mod unmapped_records;
 
 pub use self::{
-    lazy_records::LazyRecords, query::Query, records::Records, unmapped_records::UnmappedRecords,
+    lazy_records::LazyRecords,
+    query::Query,
+    records::Records,
+    regions_query::{RegionsQuery, WithRegionIndices},
+    unmapped_records::UnmappedRecords,
 };
 
 use std::{
@@ -408,6 +413,56 @@ where
         ))
     }
 
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The index chunks of all regions are merged, so each compressed block is read at most once,
+    /// and each record is returned at most once, even if the regions overlap or are close
+    /// together. Use [`RegionsQuery::with_region_indices`] to also get the regions each record
+    /// intersects.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bam::{self as bam, bai};
+    /// use noodles_core::Region;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// let header = reader.read_header()?.parse()?;
+    ///
+    /// let index = bai::read("sample.bam.bai")?;
+    /// let regions: Vec<Region> = vec!["sq0:8-13".parse()?, "sq0:21-34".parse()?];
+    /// let query = reader.query_regions(&header, &index, &regions)?;
+    ///
+    /// for result in query.with_region_indices() {
+    ///     let (record, region_indices) = result?;
+    ///     println!("{:?} {:?}", record, region_indices);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'a, I>(
+        &'a mut self,
+        header: &'a sam::Header,
+        index: &I,
+        regions: &[Region],
+    ) -> io::Result<RegionsQuery<'a, R>>
+    where
+        I: BinningIndex,
+    {
+        let queries = regions
+            .iter()
+            .map(|region| {
+                resolve_region(header.reference_sequences(), region)
+                    .map(|reference_sequence_id| (reference_sequence_id, region.interval()))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let chunks = index.query_intervals(queries)?;
+
+        Ok(RegionsQuery::new(self, header, chunks, regions))
+    }
+
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     ///
     /// # Examples
@@ -685,4 +740,101 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use noodles_core::Position;
+        use noodles_csi::index::reference_sequence::bin::Chunk;
+
+        use crate::bai::{self, index::reference_sequence::Bin};
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(64)?),
+            )
+            .add_reference_sequence(
+                "sq1".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(64)?),
+            )
+            .build();
+
+        let build_record = |reference_sequence_id, alignment_start| {
+            Position::try_from(alignment_start).map(|position| {
+                Record::builder()
+                    .set_reference_sequence_id(reference_sequence_id)
+                    .set_alignment_start(position)
+                    .set_cigar("5M".parse().unwrap())
+                    .set_sequence("ACGTA".parse().unwrap())
+                    .build()
+            })
+        };
+
+        let records = [
+            build_record(0, 1)?,
+            build_record(0, 10)?,
+            build_record(0, 30)?,
+            build_record(1, 8)?,
+        ];
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        let mut chunk_starts = Vec::new();
+
+        for record in &records {
+            chunk_starts.push(writer.get_ref().virtual_position());
+            writer.write_record(&header, record)?;
+        }
+
+        let end = writer.get_ref().virtual_position();
+        writer.try_finish()?;
+
+        // Each reference sequence has a single bin (the root bin) with a chunk per record.
+        let build_reference_sequence = |chunks: Vec<Chunk>| {
+            bai::index::ReferenceSequence::new(vec![Bin::new(0, chunks)], Vec::new(), None)
+        };
+
+        let index = bai::Index::new(
+            vec![
+                build_reference_sequence(vec![
+                    Chunk::new(chunk_starts[0], chunk_starts[1]),
+                    Chunk::new(chunk_starts[1], chunk_starts[2]),
+                    Chunk::new(chunk_starts[2], chunk_starts[3]),
+                ]),
+                build_reference_sequence(vec![Chunk::new(chunk_starts[3], end)]),
+            ],
+            None,
+        );
+
+        let data = writer.get_ref().get_ref().clone();
+        let mut reader = Reader::new(Cursor::new(data));
+
+        let regions: Vec<Region> = vec![
+            "sq0:3-12".parse()?,
+            "sq0:11-20".parse()?,
+            "sq1:1-10".parse()?,
+        ];
+
+        let actual: Vec<_> = reader
+            .query_regions(&header, &index, &regions)?
+            .with_region_indices()
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (records[0].clone(), vec![0]),
+            (records[1].clone(), vec![0, 1]),
+            (records[3].clone(), vec![2]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let regions: Vec<Region> = vec!["sq2".parse()?];
+        assert!(reader.query_regions(&header, &index, &regions).is_err());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,162 @@
+use std::{
+    io::{self, Read, Seek},
+    vec,
+};
+
+use noodles_bgzf::{self as bgzf, VirtualPosition};
+use noodles_core::{interval_set::OverlapIndex, Feature, Region};
+use noodles_csi::index::reference_sequence::bin::Chunk;
+use noodles_sam::{self as sam, alignment::Record};
+
+use super::Reader;
+
+enum State {
+    Seek,
+    Read(VirtualPosition),
+    Done,
+}
+
+/// An iterator over records of a BAM reader that intersect any of the given regions.
+///
+/// Each record is read and returned at most once, even if it intersects more than one region.
+///
+/// This is created by calling [`Reader::query_regions`].
+pub struct RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: &'a mut Reader<bgzf::Reader<R>>,
+
+    header: &'a sam::Header,
+    chunks: vec::IntoIter<Chunk>,
+
+    regions: OverlapIndex<usize>,
+
+    state: State,
+    record: Record,
+}
+
+impl<'a, R> RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(super) fn new(
+        reader: &'a mut Reader<bgzf::Reader<R>>,
+        header: &'a sam::Header,
+        chunks: Vec<Chunk>,
+        regions: &[Region],
+    ) -> Self {
+        Self {
+            reader,
+
+            header,
+            chunks: chunks.into_iter(),
+
+            regions: regions.iter().cloned().zip(0..).collect(),
+
+            state: State::Seek,
+            record: Record::default(),
+        }
+    }
+
+    /// Returns an iterator that also yields the indices of the regions each record intersects.
+    ///
+    /// The indices are positions in the list of regions given to [`Reader::query_regions`], in
+    /// ascending order.
+    pub fn with_region_indices(self) -> WithRegionIndices<'a, R> {
+        WithRegionIndices { query: self }
+    }
+
+    fn next_record(&mut self) -> io::Result<Option<Record>> {
+        self.reader
+            .read_record(self.header, &mut self.record)
+            .map(|n| match n {
+                0 => None,
+                _ => Some(self.record.clone()),
+            })
+    }
+
+    fn next_with_region_indices(&mut self) -> Option<io::Result<(Record, Vec<usize>)>> {
+        loop {
+            match self.state {
+                State::Seek => {
+                    self.state = match self.chunks.next() {
+                        Some(chunk) => {
+                            if let Err(e) = self.reader.seek(chunk.start()) {
+                                return Some(Err(e));
+                            }
+
+                            State::Read(chunk.end())
+                        }
+                        None => State::Done,
+                    }
+                }
+                State::Read(chunk_end) => match self.next_record() {
+                    Ok(Some(record)) => {
+                        if self.reader.virtual_position() >= chunk_end {
+                            self.state = State::Seek;
+                        }
+
+                        let region_indices =
+                            intersecting_region_indices(&self.regions, self.header, &record);
+
+                        if !region_indices.is_empty() {
+                            return Some(Ok((record, region_indices)));
+                        }
+                    }
+                    Ok(None) => self.state = State::Seek,
+                    Err(e) => return Some(Err(e)),
+                },
+                State::Done => return None,
+            }
+        }
+    }
+}
+
+impl<'a, R> Iterator for RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_region_indices()
+            .map(|result| result.map(|(record, _)| record))
+    }
+}
+
+/// An iterator over records of a BAM reader and the indices of the regions they intersect.
+///
+/// This is created by calling [`RegionsQuery::with_region_indices`].
+pub struct WithRegionIndices<'a, R>
+where
+    R: Read + Seek,
+{
+    query: RegionsQuery<'a, R>,
+}
+
+impl<'a, R> Iterator for WithRegionIndices<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<(Record, Vec<usize>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.query.next_with_region_indices()
+    }
+}
+
+pub(crate) fn intersecting_region_indices(
+    regions: &OverlapIndex<usize>,
+    header: &sam::Header,
+    record: &Record,
+) -> Vec<usize> {
+    let region = match record.region(header) {
+        Ok(region) => region,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut indices: Vec<_> = regions.overlapping(&region).map(|(_, &i)| i).collect();
+    indices.sort_unstable();
+    indices
+}
@@ -2,17 +2,21 @@
 
 mod convert;
 mod filters;
-mod genotypes;
+pub mod genotypes;
 mod info;
-pub(crate) mod value;
+mod subset;
+pub mod value;
 
 pub(crate) use self::value::Value;
 pub use self::{filters::Filters, genotypes::Genotypes, info::Info};
 
 use std::io;
 
+use noodles_core::{self as core, region::Interval, Feature};
 use noodles_vcf as vcf;
 
+use crate::header::StringMaps;
+
 /// A chromosome ID.
 pub type ChromosomeId = usize;
 
@@ -259,3 +263,53 @@ impl Default for Record {
         }
     }
 }
+
+impl Feature<StringMaps> for Record {
+    fn reference_sequence_name<'a>(&'a self, string_maps: &'a StringMaps) -> core::Result<&'a str> {
+        string_maps
+            .contigs()
+            .get_index(self.chromosome_id())
+            .ok_or_else(|| core::Error::new(core::error::Kind::Other, "invalid chromosome ID"))
+    }
+
+    fn interval(&self) -> core::Result<Interval> {
+        fn try_into_position(position: vcf::record::Position) -> core::Result<core::Position> {
+            core::Position::try_from(usize::from(position))
+                .map_err(|e| core::Error::new(core::error::Kind::TryFrom, e))
+        }
+
+        let start = try_into_position(self.position())?;
+        let end = self
+            .end()
+            .map_err(core::Error::from)
+            .and_then(try_into_position)?;
+
+        Ok(Interval::from(start..=end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region() -> Result<(), Box<dyn std::error::Error>> {
+        use vcf::header::record::value::{map::Contig, Map};
+
+        let header = vcf::Header::builder()
+            .add_contig("sq0".parse()?, Map::<Contig>::new())
+            .build();
+        let string_maps = StringMaps::from(&header);
+
+        let mut record = Record::default();
+        *record.position_mut() = vcf::record::Position::from(8);
+        *record.rlen_mut() = 5;
+
+        assert_eq!(record.region(&string_maps)?, "sq0:8-12".parse()?);
+
+        *record.chromosome_id_mut() = 1;
+        assert!(record.reference_sequence_name(&string_maps).is_err());
+
+        Ok(())
+    }
+}
@@ -1,8 +1,19 @@
+//! BCF record genotypes.
+
+mod column;
+
+pub use self::column::Column;
+
 use std::io;
 
 use noodles_vcf as vcf;
 
-use crate::header::string_maps::StringStringMap;
+use crate::{
+    header::string_maps::StringStringMap,
+    reader::{string_map::read_string_map_index, value::read_type},
+};
+
+use super::value;
 
 /// BCF record genotypes.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -55,6 +66,112 @@ impl Genotypes {
         Ok(genotypes)
     }
 
+    /// Returns a typed view of the values of the field with the given key for all samples.
+    ///
+    /// Unlike [`Self::try_into_vcf_record_genotypes`], this does not convert the genotypes to VCF
+    /// record genotypes. Sample values are decoded directly from the record buffer, and missing
+    /// and end-of-vector sentinels are preserved. `GT` values are returned in their BCF encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::{header::StringMaps, record::{value::Array, Record}};
+    /// use noodles_vcf::{self as vcf, header::{format::key, record::value::{map, Map}}};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_format(key::GENOTYPE, Map::<map::Format>::from(&key::GENOTYPE))
+    ///     .build();
+    ///
+    /// let string_maps = StringMaps::from(&header);
+    ///
+    /// let record = Record::default();
+    /// assert!(record.genotypes().get_column(string_maps.strings(), "GT").is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get_column(
+        &self,
+        string_string_map: &StringStringMap,
+        key: &str,
+    ) -> Option<io::Result<Column<'_>>> {
+        let i = string_string_map.get_index_of(key)?;
+        let mut src = &self.buf[..];
+
+        for _ in 0..self.format_count() {
+            let result = read_string_map_index(&mut src).and_then(|j| {
+                let ty = read_type(&mut src)?;
+                let buf = value::split_off(&mut src, ty, self.len())?;
+                Ok((j, ty, buf))
+            });
+
+            match result {
+                Ok((j, ty, buf)) => {
+                    if j == i {
+                        return Some(Ok(Column::new(ty, buf, self.len())));
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+
+    /// Returns genotypes with only the samples at the given indices.
+    ///
+    /// Samples are output in the given order. The values of each field are sliced directly from
+    /// the record buffer, i.e., the genotypes are not converted to VCF record genotypes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::Genotypes;
+    /// let genotypes = Genotypes::default();
+    /// let selected_genotypes = genotypes.select(&[])?;
+    /// assert!(selected_genotypes.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn select(&self, sample_indices: &[usize]) -> io::Result<Self> {
+        if let Some(i) = sample_indices.iter().find(|&&i| i >= self.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid sample index: {i}"),
+            ));
+        }
+
+        let mut buf = Vec::with_capacity(self.buf.len());
+        let mut src = &self.buf[..];
+
+        for _ in 0..self.format_count() {
+            let field_start = src;
+
+            read_string_map_index(&mut src)?;
+            let ty = read_type(&mut src)?;
+
+            let descriptor_len = field_start.len() - src.len();
+            buf.extend_from_slice(&field_start[..descriptor_len]);
+
+            let values = value::split_off(&mut src, ty, self.len())?;
+
+            if values.is_empty() {
+                continue;
+            }
+
+            let n = values.len() / self.len();
+
+            for &i in sample_indices {
+                buf.extend_from_slice(&values[i * n..(i + 1) * n]);
+            }
+        }
+
+        Ok(Self {
+            buf,
+            format_count: self.format_count(),
+            sample_count: sample_indices.len(),
+        })
+    }
+
     /// Returns the number of samples.
     ///
     /// # Examples
@@ -132,3 +249,107 @@ impl AsMut<Vec<u8>> for Genotypes {
         &mut self.buf
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use noodles_vcf::header::{
+        format::key,
+        record::value::{map, Map},
+    };
+
+    use super::*;
+    use crate::{
+        header::StringMaps,
+        record::value::{Array, Int8},
+    };
+
+    #[test]
+    fn test_get_column() -> io::Result<()> {
+        let header = vcf::Header::builder()
+            .add_format(key::GENOTYPE, Map::<map::Format>::from(&key::GENOTYPE))
+            .add_format(
+                key::CONDITIONAL_GENOTYPE_QUALITY,
+                Map::<map::Format>::from(&key::CONDITIONAL_GENOTYPE_QUALITY),
+            )
+            .build();
+
+        let string_maps = StringMaps::from(&header);
+
+        let genotypes = Genotypes {
+            buf: vec![
+                0x11, 0x01, // GT
+                0x21, 0x02, 0x04, 0x02, 0x81, // 0/1, 0
+                0x11, 0x02, // GQ
+                0x11, 0x0d, 0x80, // 13, .
+            ],
+            format_count: 2,
+            sample_count: 2,
+        };
+
+        let column = genotypes
+            .get_column(string_maps.strings(), "GQ")
+            .transpose()?
+            .expect("missing GQ column");
+
+        let values: Vec<_> = column
+            .iter()
+            .map(|result| match result? {
+                Some(Array::Int8(values)) => Ok(values.get(0)),
+                _ => Err(io::Error::from(io::ErrorKind::InvalidData)),
+            })
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(values, [Some(Int8::Value(13)), Some(Int8::Missing)]);
+
+        let column = genotypes
+            .get_column(string_maps.strings(), "GT")
+            .transpose()?
+            .expect("missing GT column");
+
+        match column.get(1).transpose()?.flatten() {
+            Some(Array::Int8(values)) => assert_eq!(
+                values.iter().collect::<Vec<_>>(),
+                [Int8::Value(0x02), Int8::EndOfVector]
+            ),
+            array => panic!("unexpected array: {array:?}"),
+        }
+
+        assert!(genotypes.get_column(string_maps.strings(), "DP").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_select() -> io::Result<()> {
+        let genotypes = Genotypes {
+            buf: vec![
+                0x11, 0x01, // GT
+                0x21, 0x02, 0x04, 0x02, 0x81, 0x04, 0x04, // 0/1, 0, 1/1
+                0x11, 0x02, // GQ
+                0x12, 0x0d, 0x00, 0x00, 0x80, 0x15, 0x00, // 13, ., 21
+            ],
+            format_count: 2,
+            sample_count: 3,
+        };
+
+        let actual = genotypes.select(&[2, 0])?;
+        let expected = Genotypes {
+            buf: vec![
+                0x11, 0x01, // GT
+                0x21, 0x04, 0x04, 0x02, 0x04, // 1/1, 0/1
+                0x11, 0x02, // GQ
+                0x12, 0x15, 0x00, 0x0d, 0x00, // 21, 13
+            ],
+            format_count: 2,
+            sample_count: 2,
+        };
+        assert_eq!(actual, expected);
+
+        assert!(matches!(
+            genotypes.select(&[3]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
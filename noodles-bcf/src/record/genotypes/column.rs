@@ -0,0 +1,95 @@
+use std::io;
+
+use crate::record::value::{Array, Type};
+
+/// A typed view of a genotype field for all samples.
+///
+/// Each sample value is decoded from the record buffer on access.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Column<'a> {
+    ty: Option<Type>,
+    src: &'a [u8],
+    sample_count: usize,
+}
+
+impl<'a> Column<'a> {
+    pub(crate) fn new(ty: Option<Type>, src: &'a [u8], sample_count: usize) -> Self {
+        Self {
+            ty,
+            src,
+            sample_count,
+        }
+    }
+
+    /// Returns the number of samples.
+    pub fn len(&self) -> usize {
+        self.sample_count
+    }
+
+    /// Returns whether there are any samples.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value of the sample at the given index.
+    ///
+    /// The inner option is `None` if the field has the missing type.
+    pub fn get(&self, i: usize) -> Option<io::Result<Option<Array<'a>>>> {
+        if i >= self.len() {
+            return None;
+        }
+
+        let ty = match self.ty {
+            Some(ty) => ty,
+            None => return Some(Ok(None)),
+        };
+
+        let n = self.src.len() / self.sample_count;
+        let buf = &self.src[i * n..(i + 1) * n];
+
+        Some(Array::new(ty, buf).map(Some))
+    }
+
+    /// Returns an iterator over the values of all samples.
+    pub fn iter(&self) -> impl Iterator<Item = io::Result<Option<Array<'a>>>> + 'a {
+        let column = *self;
+        (0..self.len()).filter_map(move |i| column.get(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::value::Int16;
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let data = [0x05, 0x00, 0x00, 0x80, 0x08, 0x00, 0x01, 0x80];
+        let column = Column::new(Some(Type::Int16(2)), &data, 2);
+
+        assert_eq!(column.len(), 2);
+
+        let values: Vec<Vec<_>> = column
+            .iter()
+            .map(|result| match result? {
+                Some(Array::Int16(values)) => Ok(values.iter().collect()),
+                _ => Err(io::Error::from(io::ErrorKind::InvalidData)),
+            })
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(
+            values,
+            [
+                vec![Int16::Value(5), Int16::Missing],
+                vec![Int16::Value(8), Int16::EndOfVector],
+            ]
+        );
+
+        assert!(column.get(2).is_none());
+
+        let column = Column::new(None, &[], 2);
+        assert!(matches!(column.get(0), Some(Ok(None))));
+
+        Ok(())
+    }
+}
@@ -2,7 +2,12 @@ use std::io;
 
 use noodles_vcf as vcf;
 
-use crate::header::string_maps::StringStringMap;
+use crate::{
+    header::string_maps::StringStringMap,
+    reader::{string_map::read_string_map_index, value::read_type},
+};
+
+use super::value::{self, Array};
 
 /// BCF record info.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -155,6 +160,75 @@ impl Info {
         None
     }
 
+    /// Returns a typed view of the value with the given key.
+    ///
+    /// Unlike [`Self::get`], this does not convert the value to a VCF record info field value.
+    /// The value is decoded directly from the record buffer, and missing and end-of-vector
+    /// sentinels are preserved. The inner option is `None` if the value has the missing type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::{
+    ///     header::StringMaps,
+    ///     record::{value::{Array, Int32}, Info},
+    /// };
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{info::key, record::value::{map, Map}},
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_info(key::ALLELE_COUNT, Map::<map::Info>::from(&key::ALLELE_COUNT))
+    ///     .add_info(key::TOTAL_DEPTH, Map::<map::Info>::from(&key::TOTAL_DEPTH))
+    ///     .build();
+    ///
+    /// let string_maps = StringMaps::from(&header);
+    ///
+    /// let data = vec![
+    ///     0x11, 0x01, 0x11, 0x05, // AC=5
+    ///     0x11, 0x02, 0x13, 0x08, 0x00, 0x00, 0x00, // DP=8
+    /// ];
+    ///
+    /// let info = Info::new(data, 2);
+    ///
+    /// match info.get_array(string_maps.strings(), "DP").transpose()?.flatten() {
+    ///     Some(Array::Int32(values)) => assert_eq!(values.get(0), Some(Int32::Value(8))),
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// assert!(info.get_array(string_maps.strings(), "AA").is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get_array(
+        &self,
+        string_string_map: &StringStringMap,
+        key: &str,
+    ) -> Option<io::Result<Option<Array<'_>>>> {
+        let i = string_string_map.get_index_of(key)?;
+        let mut src = &self.buf[..];
+
+        for _ in 0..self.len() {
+            let result = read_string_map_index(&mut src).and_then(|j| {
+                let ty = read_type(&mut src)?;
+                let buf = value::split_off(&mut src, ty, 1)?;
+                Ok((j, ty, buf))
+            });
+
+            match result {
+                Ok((j, ty, buf)) => {
+                    if j == i {
+                        return Some(ty.map(|ty| Array::new(ty, buf)).transpose());
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+
     /// Returns an iterator over all info fields.
     ///
     /// # Examples
@@ -0,0 +1,218 @@
+use std::io;
+
+use noodles_vcf::{self as vcf, recompute::Counts, subset::Subset};
+
+use super::{
+    value::{Array, Int16, Int32, Int8},
+    Info, Record,
+};
+use crate::header::StringMaps;
+
+impl Record {
+    /// Drops the unselected samples from the record.
+    ///
+    /// The record is expected to have the samples of the subset's original header; otherwise, this
+    /// returns an `InvalidData` error. The genotypes are sliced directly from the record buffer.
+    ///
+    /// If the subset recomputes allele counts, the `AC` and `AN` info fields are set from the
+    /// selected `GT` values when they are defined in the header. Only the info fields are
+    /// reencoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::{self as bcf, header::StringMaps};
+    /// use noodles_vcf::{self as vcf, subset::Subset};
+    ///
+    /// let header = vcf::Header::default();
+    /// let string_maps = StringMaps::from(&header);
+    /// let subset = Subset::builder().build(&header)?;
+    ///
+    /// let mut record = bcf::Record::default();
+    /// record.apply_subset(&subset, &string_maps)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn apply_subset(&mut self, subset: &Subset, string_maps: &StringMaps) -> io::Result<()> {
+        if self.genotypes().is_empty() {
+            return Ok(());
+        }
+
+        let sample_count = self.genotypes().len();
+
+        if sample_count != subset.original_sample_count() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "sample count mismatch: expected {}, got {}",
+                    subset.original_sample_count(),
+                    sample_count
+                ),
+            ));
+        }
+
+        *self.genotypes_mut() = self.genotypes().select(subset.sample_indices())?;
+
+        if subset.recomputes_allele_counts() {
+            recompute_allele_counts(subset.header(), string_maps, self)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn recompute_allele_counts(
+    header: &vcf::Header,
+    string_maps: &StringMaps,
+    record: &mut Record,
+) -> io::Result<()> {
+    let column = match record
+        .genotypes()
+        .get_column(string_maps.strings(), "GT")
+        .transpose()?
+    {
+        Some(column) => column,
+        None => return Ok(()),
+    };
+
+    let mut counts = Counts::new(record.alternate_bases().len());
+
+    for result in column.iter() {
+        let positions = match result? {
+            Some(Array::Int8(values)) => allele_positions(values.iter().map(|n| match n {
+                Int8::Value(n) => Some(i32::from(n)),
+                Int8::Missing => Some(0),
+                _ => None,
+            })),
+            Some(Array::Int16(values)) => allele_positions(values.iter().map(|n| match n {
+                Int16::Value(n) => Some(i32::from(n)),
+                Int16::Missing => Some(0),
+                _ => None,
+            })),
+            Some(Array::Int32(values)) => allele_positions(values.iter().map(|n| match n {
+                Int32::Value(n) => Some(n),
+                Int32::Missing => Some(0),
+                _ => None,
+            })),
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid genotype value type",
+                ))
+            }
+            None => Vec::new(),
+        };
+
+        counts.add_sample(&positions)?;
+    }
+
+    let mut info = record
+        .info()
+        .try_into_vcf_record_info(header, string_maps.strings())?;
+
+    counts.update_allele_count_fields(header, &mut info)?;
+
+    let mut buf = Vec::new();
+    crate::writer::vcf_record::site::info::write_info(&mut buf, string_maps.strings(), &info)?;
+    *record.info_mut() = Info::new(buf, info.len());
+
+    Ok(())
+}
+
+// Decodes the allele positions of an encoded genotype.
+//
+// The encoding is `(i + 1) << 1 | phased`, where `i` is the allele position, and 0 is missing.
+// Decoding stops at the end of the vector.
+fn allele_positions<I>(values: I) -> Vec<Option<usize>>
+where
+    I: Iterator<Item = Option<i32>>,
+{
+    values
+        .map_while(|value| value)
+        .map(|n| usize::try_from((n >> 1) - 1).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reader::record::read_record, writer::vcf_record::write_vcf_record};
+
+    #[test]
+    fn test_apply_subset() -> Result<(), Box<dyn std::error::Error>> {
+        let raw_header = "##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##INFO=<ID=AN,Number=1,Type=Integer,Description=\"Total number of alleles in called genotypes\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=GQ,Number=1,Type=Integer,Description=\"Conditional genotype quality\">
+##contig=<ID=sq0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1\tsample2
+";
+
+        let header: vcf::Header = raw_header.parse()?;
+        let string_maps: StringMaps = raw_header.parse()?;
+
+        let vcf_record = vcf::Record::try_from_str(
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=2,2;AN=6\tGT:GQ\t./1:7\t2/2:13\t1|0:21",
+            &header,
+        )?;
+
+        let mut buf = Vec::new();
+        write_vcf_record(&mut buf, &header, &string_maps, &vcf_record)?;
+
+        let mut record = Record::default();
+        read_record(&mut &buf[..], &mut Vec::new(), &mut record)?;
+
+        let subset = Subset::builder()
+            .set_sample_names(["sample2", "sample0"])
+            .set_recompute_allele_counts(true)
+            .build(&header)?;
+
+        record.apply_subset(&subset, &string_maps)?;
+
+        let actual = record.try_into_vcf_record(subset.header(), &string_maps)?;
+        let expected = vcf::Record::try_from_str(
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=2,0;AN=3\tGT:GQ\t1|0:21\t./1:7",
+            subset.header(),
+        )?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_subset_with_sample_count_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        let raw_header = "##fileformat=VCFv4.3
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##contig=<ID=sq0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+";
+
+        let header: vcf::Header = raw_header.parse()?;
+        let string_maps: StringMaps = raw_header.parse()?;
+
+        let subset = Subset::builder()
+            .set_sample_names(["sample1"])
+            .build(&header)?;
+
+        let raw_header = raw_header.replace("\tsample1", "");
+        let header: vcf::Header = raw_header.parse()?;
+
+        let vcf_record =
+            vcf::Record::try_from_str("sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0/0", &header)?;
+
+        let mut buf = Vec::new();
+        write_vcf_record(&mut buf, &header, &string_maps, &vcf_record)?;
+
+        let mut record = Record::default();
+        read_record(&mut &buf[..], &mut Vec::new(), &mut record)?;
+
+        assert!(matches!(
+            record.apply_subset(&subset, &string_maps),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -1,13 +1,25 @@
+//! BCF record values.
+
+mod array;
 mod float;
 mod int16;
 mod int32;
 mod int8;
 mod ty;
 
-pub use self::{float::Float, int16::Int16, int32::Int32, int8::Int8, ty::Type};
+pub(crate) use self::ty::Type;
+pub use self::{
+    array::{Array, Decode, Values},
+    float::Float,
+    int16::Int16,
+    int32::Int32,
+    int8::Int8,
+};
+
+use std::io;
 
 #[derive(Clone, Debug, PartialEq)]
-pub enum Value {
+pub(crate) enum Value {
     Int8(Option<Int8>),
     Int8Array(Vec<i8>),
     Int16(Option<Int16>),
@@ -18,3 +30,28 @@ pub enum Value {
     FloatArray(Vec<f32>),
     String(Option<String>),
 }
+
+/// Splits the encoded data of `count` values of the given type off the front of the source.
+pub(crate) fn split_off<'a>(
+    src: &mut &'a [u8],
+    ty: Option<Type>,
+    count: usize,
+) -> io::Result<&'a [u8]> {
+    let (size, len) = match ty {
+        Some(Type::Int8(len)) | Some(Type::String(len)) => (1, len),
+        Some(Type::Int16(len)) => (2, len),
+        Some(Type::Int32(len)) | Some(Type::Float(len)) => (4, len),
+        None => return Ok(&[]),
+    };
+
+    let n = len
+        .checked_mul(size)
+        .and_then(|n| n.checked_mul(count))
+        .filter(|&n| n <= src.len())
+        .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+
+    let (buf, rest) = src.split_at(n);
+    *src = rest;
+
+    Ok(buf)
+}
@@ -0,0 +1,207 @@
+use std::{fmt, io, marker::PhantomData, str};
+
+use super::{Float, Int16, Int32, Int8, Type};
+
+const NUL: u8 = 0x00;
+
+/// A borrowed BCF typed value.
+///
+/// This is a view of an encoded value in a record buffer. Values are decoded on access, and
+/// missing and end-of-vector sentinels are preserved.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Array<'a> {
+    /// 8-bit integers.
+    Int8(Values<'a, Int8>),
+    /// 16-bit integers.
+    Int16(Values<'a, Int16>),
+    /// 32-bit integers.
+    Int32(Values<'a, Int32>),
+    /// Single-precision floating-points.
+    Float(Values<'a, Float>),
+    /// A string.
+    ///
+    /// Trailing NUL padding is removed.
+    String(&'a str),
+}
+
+impl<'a> Array<'a> {
+    /// Creates a typed value view from a type and its encoded data.
+    pub(crate) fn new(ty: Type, src: &'a [u8]) -> io::Result<Self> {
+        match ty {
+            Type::Int8(_) => Ok(Self::Int8(Values::new(src))),
+            Type::Int16(_) => Ok(Self::Int16(Values::new(src))),
+            Type::Int32(_) => Ok(Self::Int32(Values::new(src))),
+            Type::Float(_) => Ok(Self::Float(Values::new(src))),
+            Type::String(_) => {
+                let end = src.iter().rposition(|&b| b != NUL).map_or(0, |i| i + 1);
+
+                str::from_utf8(&src[..end])
+                    .map(Self::String)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+        }
+    }
+}
+
+/// A scalar that can be decoded from little-endian bytes.
+///
+/// This trait is sealed and cannot be implemented outside this crate.
+pub trait Decode: Sized + private::Sealed {
+    #[doc(hidden)]
+    const SIZE: usize;
+
+    #[doc(hidden)]
+    fn decode(buf: &[u8]) -> Self;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::Int8 {}
+    impl Sealed for super::Int16 {}
+    impl Sealed for super::Int32 {}
+    impl Sealed for super::Float {}
+}
+
+impl Decode for Int8 {
+    const SIZE: usize = 1;
+
+    fn decode(buf: &[u8]) -> Self {
+        Self::from(i8::from_le_bytes([buf[0]]))
+    }
+}
+
+impl Decode for Int16 {
+    const SIZE: usize = 2;
+
+    fn decode(buf: &[u8]) -> Self {
+        Self::from(i16::from_le_bytes([buf[0], buf[1]]))
+    }
+}
+
+impl Decode for Int32 {
+    const SIZE: usize = 4;
+
+    fn decode(buf: &[u8]) -> Self {
+        Self::from(i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]))
+    }
+}
+
+impl Decode for Float {
+    const SIZE: usize = 4;
+
+    fn decode(buf: &[u8]) -> Self {
+        Self::from(f32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]))
+    }
+}
+
+/// A borrowed list of encoded BCF values.
+pub struct Values<'a, N> {
+    src: &'a [u8],
+    _marker: PhantomData<N>,
+}
+
+impl<'a, N> Values<'a, N>
+where
+    N: Decode + 'a,
+{
+    fn new(src: &'a [u8]) -> Self {
+        Self {
+            src,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of values, including sentinels.
+    pub fn len(&self) -> usize {
+        self.src.len() / N::SIZE
+    }
+
+    /// Returns whether there are any values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at the given index.
+    pub fn get(&self, i: usize) -> Option<N> {
+        let start = i.checked_mul(N::SIZE)?;
+        let end = start.checked_add(N::SIZE)?;
+        self.src.get(start..end).map(N::decode)
+    }
+
+    /// Returns an iterator over the values.
+    pub fn iter(&self) -> impl Iterator<Item = N> + 'a {
+        self.src.chunks_exact(N::SIZE).map(N::decode)
+    }
+}
+
+impl<N> Clone for Values<'_, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N> Copy for Values<'_, N> {}
+
+impl<N> fmt::Debug for Values<'_, N>
+where
+    N: Decode + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<N> PartialEq for Values<'_, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.src == other.src
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() -> io::Result<()> {
+        let data = [0x05, 0x80, 0x81];
+        let array = Array::new(Type::Int8(3), &data)?;
+
+        match array {
+            Array::Int8(values) => {
+                assert_eq!(values.len(), 3);
+                assert_eq!(
+                    values.iter().collect::<Vec<_>>(),
+                    [Int8::Value(5), Int8::Missing, Int8::EndOfVector]
+                );
+                assert_eq!(values.get(1), Some(Int8::Missing));
+                assert!(values.get(3).is_none());
+            }
+            _ => panic!("unexpected array: {array:?}"),
+        }
+
+        let data = [0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x80, 0x7f];
+        let array = Array::new(Type::Float(2), &data)?;
+
+        match array {
+            Array::Float(values) => {
+                assert_eq!(
+                    values.iter().collect::<Vec<_>>(),
+                    [Float::Value(f32::from_bits(0x08)), Float::Missing]
+                );
+            }
+            _ => panic!("unexpected array: {array:?}"),
+        }
+
+        let data = *b"ndls\x00\x00";
+        assert_eq!(Array::new(Type::String(6), &data)?, Array::String("ndls"));
+
+        let data = [0xff];
+        assert!(matches!(
+            Array::new(Type::String(1), &data),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
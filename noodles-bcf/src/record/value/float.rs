@@ -1,9 +1,14 @@
-#[derive(Clone, Debug, PartialEq)]
+/// A BCF single-precision floating-point value.
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[non_exhaustive]
 pub enum Float {
+    /// A value.
     Value(f32),
+    /// A missing value.
     Missing,
+    /// An end-of-vector marker.
     EndOfVector,
+    /// A reserved value.
     Reserved(f32),
 }
 
@@ -1,9 +1,14 @@
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// A BCF 32-bit integer value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum Int32 {
+    /// A value.
     Value(i32),
+    /// A missing value.
     Missing,
+    /// An end-of-vector marker.
     EndOfVector,
+    /// A reserved value.
     Reserved(i32),
 }
 
@@ -1,7 +1,7 @@
 mod record;
 mod string_map;
 mod value;
-mod vcf_record;
+pub(crate) mod vcf_record;
 
 use std::{
     ffi::CString,
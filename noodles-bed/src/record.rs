@@ -16,7 +16,7 @@ use std::{
     str::FromStr,
 };
 
-use noodles_core::Position;
+use noodles_core::{self as core, region::Interval, Feature, Position};
 
 const DELIMITER: char = '\t';
 const MISSING_STRING: &str = ".";
@@ -465,6 +465,19 @@ where
     }
 }
 
+impl<const N: u8> Feature for Record<N>
+where
+    Self: BedN<3>,
+{
+    fn reference_sequence_name<'a>(&'a self, _: &'a ()) -> core::Result<&'a str> {
+        Ok(self.reference_sequence_name())
+    }
+
+    fn interval(&self) -> core::Result<Interval> {
+        Ok(Interval::from(self.start_position()..=self.end_position()))
+    }
+}
+
 impl fmt::Display for Record<3> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         format_bed_3_fields(f, self)?;
@@ -1084,6 +1097,19 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_region() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::<3>::builder()
+            .set_reference_sequence_name("sq0")
+            .set_start_position(Position::try_from(8)?)
+            .set_end_position(Position::try_from(13)?)
+            .build()?;
+
+        assert_eq!(record.region(&())?, "sq0:8-13".parse()?);
+
+        Ok(())
+    }
+
     #[test]
     fn test_fmt_for_record_3() -> Result<(), noodles_core::position::TryFromIntError> {
         let start = Position::try_from(8)?;
@@ -0,0 +1,389 @@
+//! Filter expressions.
+//!
+//! This is the format-agnostic part of record filter expressions: the syntax tree, the parser,
+//! the evaluator, and the dynamically-typed values that fields evaluate to. Formats supply a
+//! [`Resolver`] to resolve field and function names while parsing and an [`Evaluator`] to
+//! evaluate fields and functions against a record.
+//!
+//! This module is enabled by the `expression` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{convert::Infallible, io};
+//!
+//! use noodles_core::expression::{Evaluator, Expression, Resolver, Value};
+//!
+//! struct Length;
+//!
+//! impl Resolver for Length {
+//!     type Field = ();
+//!     type Function = Infallible;
+//!
+//!     fn is_namespace(&self, _: &str) -> bool {
+//!         false
+//!     }
+//!
+//!     fn resolve_field(&self, name: &str) -> Option<Self::Field> {
+//!         (name == "len").then_some(())
+//!     }
+//!
+//!     fn resolve_function(&self, _: &str) -> Option<Self::Function> {
+//!         None
+//!     }
+//! }
+//!
+//! struct Record<'a>(&'a str);
+//!
+//! impl<'a> Evaluator<'a> for Record<'a> {
+//!     type Field = ();
+//!     type Function = Infallible;
+//!
+//!     fn evaluate_field(&self, _: &Self::Field) -> io::Result<Value<'a>> {
+//!         Ok(Value::Number(self.0.len() as f64))
+//!     }
+//!
+//!     fn evaluate_call(
+//!         &self,
+//!         function: &Self::Function,
+//!         _: &Expression<Self::Field, Self::Function>,
+//!     ) -> io::Result<Value<'a>> {
+//!         match *function {}
+//!     }
+//! }
+//!
+//! let expression = Expression::parse("len > 4 && len < 8", &Length)?;
+//! assert!(expression.evaluate(&Record("noodles"))?.is_truthy());
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+mod parser;
+mod value;
+
+pub use self::{
+    parser::{ParseError, Resolver},
+    value::Value,
+};
+
+use std::io;
+
+/// A parsed expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression<F, C> {
+    /// A literal.
+    Literal(Literal),
+    /// A field with an optional element index.
+    Field(F, Option<usize>),
+    /// A function applied to an expression.
+    Call(C, Box<Self>),
+    /// A unary operation.
+    Unary(UnaryOperator, Box<Self>),
+    /// A binary operation.
+    Binary(BinaryOperator, Box<Self>, Box<Self>),
+    /// A test of whether a value is equal to any of the given literals.
+    In(Box<Self>, Vec<Literal>),
+}
+
+impl<F, C> Expression<F, C> {
+    /// Parses an expression.
+    ///
+    /// Field and function names are resolved using the given resolver. See the [module
+    /// documentation](self) for an example.
+    pub fn parse<R>(s: &str, resolver: &R) -> Result<Self, ParseError>
+    where
+        R: Resolver<Field = F, Function = C>,
+    {
+        parser::parse(s, resolver)
+    }
+
+    /// Evaluates the expression.
+    ///
+    /// Fields and functions are evaluated using the given evaluator. `&&` and `||` short-circuit.
+    /// See the [module documentation](self) for an example.
+    ///
+    /// Comparisons with a list are true if any element satisfies the comparison, and arithmetic
+    /// is applied element-wise. Comparisons with a missing value are false, and arithmetic with a
+    /// missing value is missing. An error is returned if values of incompatible types are
+    /// compared or used in arithmetic.
+    pub fn evaluate<'r, E>(&self, evaluator: &E) -> io::Result<Value<'r>>
+    where
+        E: Evaluator<'r, Field = F, Function = C>,
+    {
+        match self {
+            Self::Literal(literal) => Ok(Value::from(literal)),
+            Self::Field(field, index) => {
+                let value = evaluator.evaluate_field(field)?;
+
+                match index {
+                    Some(i) => Ok(value.index(*i)),
+                    None => Ok(value),
+                }
+            }
+            Self::Call(function, expr) => evaluator.evaluate_call(function, expr),
+            Self::Unary(operator, expr) => {
+                let value = expr.evaluate(evaluator)?;
+                value::apply_unary(*operator, value)
+            }
+            Self::Binary(BinaryOperator::And, lhs, rhs) => {
+                let value =
+                    lhs.evaluate(evaluator)?.is_truthy() && rhs.evaluate(evaluator)?.is_truthy();
+
+                Ok(Value::Bool(value))
+            }
+            Self::Binary(BinaryOperator::Or, lhs, rhs) => {
+                let value =
+                    lhs.evaluate(evaluator)?.is_truthy() || rhs.evaluate(evaluator)?.is_truthy();
+
+                Ok(Value::Bool(value))
+            }
+            Self::Binary(operator, lhs, rhs) => {
+                if let Some(result) = compare_field(evaluator, *operator, lhs, rhs) {
+                    return result.map(Value::Bool);
+                }
+
+                let lhs = lhs.evaluate(evaluator)?;
+                let rhs = rhs.evaluate(evaluator)?;
+                value::apply_binary(*operator, lhs, rhs)
+            }
+            Self::In(expr, literals) => {
+                let mut value = None;
+
+                for literal in literals {
+                    let result = match (&**expr, literal) {
+                        (Self::Field(field, index), Literal::String(s)) => {
+                            evaluator.compare_field(field, *index, BinaryOperator::Eq, s)
+                        }
+                        _ => None,
+                    };
+
+                    let is_match = match result {
+                        Some(result) => result?,
+                        None => {
+                            if value.is_none() {
+                                value = Some(expr.evaluate(evaluator)?);
+                            }
+
+                            let lhs = value.as_ref().expect("missing value");
+                            value::compare(BinaryOperator::Eq, lhs, &Value::from(literal))?
+                        }
+                    };
+
+                    if is_match {
+                        return Ok(Value::Bool(true));
+                    }
+                }
+
+                Ok(Value::Bool(false))
+            }
+        }
+    }
+}
+
+/// Evaluates format-specific fields and functions of a record.
+pub trait Evaluator<'r> {
+    /// The type of a resolved field.
+    type Field;
+    /// The type of a resolved function.
+    type Function;
+
+    /// Returns the value of a field.
+    fn evaluate_field(&self, field: &Self::Field) -> io::Result<Value<'r>>;
+
+    /// Applies a function to an expression.
+    ///
+    /// The argument is not evaluated, which allows the function to evaluate it with a different
+    /// context, e.g., per sample.
+    fn evaluate_call(
+        &self,
+        function: &Self::Function,
+        expr: &Expression<Self::Field, Self::Function>,
+    ) -> io::Result<Value<'r>>;
+
+    /// Compares a field with a string without evaluating the field.
+    ///
+    /// This is used for `==`, `!=`, and `in` with a field and a string literal, e.g., to compare
+    /// fields that are not stored as strings without building one. The operator is either
+    /// [`BinaryOperator::Eq`] or [`BinaryOperator::Ne`], and the result must be the same as
+    /// comparing the evaluated field.
+    ///
+    /// The default implementation returns `None`, i.e., the field is evaluated.
+    fn compare_field(
+        &self,
+        field: &Self::Field,
+        index: Option<usize>,
+        operator: BinaryOperator,
+        s: &str,
+    ) -> Option<io::Result<bool>> {
+        let _ = (field, index, operator, s);
+        None
+    }
+}
+
+fn compare_field<'r, E>(
+    evaluator: &E,
+    operator: BinaryOperator,
+    lhs: &Expression<E::Field, E::Function>,
+    rhs: &Expression<E::Field, E::Function>,
+) -> Option<io::Result<bool>>
+where
+    E: Evaluator<'r>,
+{
+    if !matches!(operator, BinaryOperator::Eq | BinaryOperator::Ne) {
+        return None;
+    }
+
+    match (lhs, rhs) {
+        (Expression::Field(field, index), Expression::Literal(Literal::String(s)))
+        | (Expression::Literal(Literal::String(s)), Expression::Field(field, index)) => {
+            evaluator.compare_field(field, *index, operator, s)
+        }
+        _ => None,
+    }
+}
+
+/// A literal.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+    /// A number.
+    Number(f64),
+    /// A string.
+    String(String),
+}
+
+/// A unary operator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnaryOperator {
+    /// Logical negation (`!`).
+    Not,
+    /// Arithmetic negation (`-`).
+    Negate,
+}
+
+/// A binary operator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinaryOperator {
+    /// Logical or (`||`).
+    Or,
+    /// Logical and (`&&`).
+    And,
+    /// Equal (`==` or `=`).
+    Eq,
+    /// Not equal (`!=`).
+    Ne,
+    /// Less than (`<`).
+    Lt,
+    /// Less than or equal (`<=`).
+    Le,
+    /// Greater than (`>`).
+    Gt,
+    /// Greater than or equal (`>=`).
+    Ge,
+    /// Addition (`+`).
+    Add,
+    /// Subtraction (`-`).
+    Sub,
+    /// Multiplication (`*`).
+    Mul,
+    /// Division (`/`).
+    Div,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, convert::Infallible};
+
+    use super::*;
+
+    struct TestResolver;
+
+    impl Resolver for TestResolver {
+        type Field = String;
+        type Function = Infallible;
+
+        fn is_namespace(&self, _: &str) -> bool {
+            false
+        }
+
+        fn resolve_field(&self, name: &str) -> Option<Self::Field> {
+            Some(name.into())
+        }
+
+        fn resolve_function(&self, _: &str) -> Option<Self::Function> {
+            None
+        }
+    }
+
+    struct TestEvaluator;
+
+    impl<'r> Evaluator<'r> for TestEvaluator {
+        type Field = String;
+        type Function = Infallible;
+
+        fn evaluate_field(&self, field: &Self::Field) -> io::Result<Value<'r>> {
+            match field.as_str() {
+                "n" => Ok(Value::Number(8.0)),
+                "s" => Ok(Value::String(Cow::Borrowed("ndls"))),
+                "l" => Ok(Value::List(vec![Value::Number(1.0), Value::Number(5.0)])),
+                "c" | "e" => Err(io::Error::new(io::ErrorKind::InvalidData, "invalid field")),
+                _ => Ok(Value::Missing),
+            }
+        }
+
+        fn evaluate_call(
+            &self,
+            function: &Self::Function,
+            _: &Expression<Self::Field, Self::Function>,
+        ) -> io::Result<Value<'r>> {
+            match *function {}
+        }
+
+        fn compare_field(
+            &self,
+            field: &Self::Field,
+            _: Option<usize>,
+            operator: BinaryOperator,
+            s: &str,
+        ) -> Option<io::Result<bool>> {
+            (field == "c").then(|| Ok((s == "ndls") == (operator == BinaryOperator::Eq)))
+        }
+    }
+
+    fn eval(s: &str) -> io::Result<bool> {
+        let expression = Expression::parse(s, &TestResolver)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        expression
+            .evaluate(&TestEvaluator)
+            .map(|value| value.is_truthy())
+    }
+
+    #[test]
+    fn test_evaluate() -> io::Result<()> {
+        assert!(eval("n == 8 && s == 'ndls'")?);
+        assert!(eval("n * 2 - 1 == 15")?);
+        assert!(eval("l > 4 && l[0] == 1 && !l[2]")?);
+        assert!(eval("s in ('a', 'ndls') && n in (8)")?);
+        assert!(!eval("m in (0) || m == 0 || m != 0")?);
+
+        assert!(eval("n || e")?);
+        assert!(!eval("m && e")?);
+        assert!(eval("e").is_err());
+
+        assert!(eval("s > 1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluate_with_compare_field() -> io::Result<()> {
+        assert!(eval("c == 'ndls' && 'ndls' == c && c != 'bgzf'")?);
+        assert!(!eval("c == 'bgzf' || c != 'ndls'")?);
+        assert!(eval("c in ('bgzf', 'ndls')")?);
+        assert!(!eval("c in ('bgzf')")?);
+
+        assert!(eval("c > 'ndls'").is_err());
+        assert!(eval("c == 8").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,740 @@
+use std::{error, fmt, iter::Peekable, str::CharIndices};
+
+use super::{BinaryOperator, Expression, Literal, UnaryOperator};
+
+// The maximum nesting depth of an expression.
+//
+// This bounds the recursion when parsing and evaluating an expression, e.g., for deeply nested
+// parentheses or long chains of operators.
+const MAX_DEPTH: usize = 128;
+
+/// Resolves format-specific names while parsing.
+pub trait Resolver {
+    /// The type of a resolved field.
+    type Field;
+    /// The type of a resolved function.
+    type Function;
+
+    /// Returns whether the name is a field namespace, e.g., `INFO` in `INFO/DP`.
+    ///
+    /// A namespace is joined with the following name, separated by a `/`.
+    fn is_namespace(&self, name: &str) -> bool;
+
+    /// Resolves a field name.
+    ///
+    /// A bracketed name, e.g., `[NM]`, is resolved with its brackets.
+    fn resolve_field(&self, name: &str) -> Option<Self::Field>;
+
+    /// Resolves a function name.
+    fn resolve_function(&self, name: &str) -> Option<Self::Function>;
+}
+
+/// An error returned when a filter expression fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input ended unexpectedly.
+    UnexpectedEof,
+    /// A character is invalid.
+    UnexpectedCharacter(char),
+    /// A token is unexpected.
+    UnexpectedToken(String),
+    /// A string literal is not terminated.
+    UnterminatedString,
+    /// A number is invalid.
+    InvalidNumber(String),
+    /// An index is invalid.
+    InvalidIndex(String),
+    /// A field name is invalid or undefined.
+    InvalidField(String),
+    /// A function name is invalid.
+    InvalidFunction(String),
+    /// The expression is nested too deeply.
+    MaxDepthExceeded,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::UnexpectedEof => f.write_str("unexpected end of input"),
+            Self::UnexpectedCharacter(c) => write!(f, "unexpected character: {c}"),
+            Self::UnexpectedToken(s) => write!(f, "unexpected token: {s}"),
+            Self::UnterminatedString => f.write_str("unterminated string"),
+            Self::InvalidNumber(s) => write!(f, "invalid number: {s}"),
+            Self::InvalidIndex(s) => write!(f, "invalid index: {s}"),
+            Self::InvalidField(s) => write!(f, "invalid field: {s}"),
+            Self::InvalidFunction(s) => write!(f, "invalid function: {s}"),
+            Self::MaxDepthExceeded => write!(f, "maximum depth ({MAX_DEPTH}) exceeded"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    String(String),
+    Identifier(String),
+    LeftParenthesis,
+    RightParenthesis,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    In,
+    Not,
+    Minus,
+    Operator(BinaryOperator),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::String(s) => write!(f, "{s:?}"),
+            Self::Identifier(s) => f.write_str(s),
+            Self::LeftParenthesis => f.write_str("("),
+            Self::RightParenthesis => f.write_str(")"),
+            Self::LeftBracket => f.write_str("["),
+            Self::RightBracket => f.write_str("]"),
+            Self::Comma => f.write_str(","),
+            Self::In => f.write_str("in"),
+            Self::Not => f.write_str("!"),
+            Self::Minus => f.write_str("-"),
+            Self::Operator(operator) => f.write_str(match operator {
+                BinaryOperator::Or => "||",
+                BinaryOperator::And => "&&",
+                BinaryOperator::Eq => "==",
+                BinaryOperator::Ne => "!=",
+                BinaryOperator::Lt => "<",
+                BinaryOperator::Le => "<=",
+                BinaryOperator::Gt => ">",
+                BinaryOperator::Ge => ">=",
+                BinaryOperator::Add => "+",
+                BinaryOperator::Sub => "-",
+                BinaryOperator::Mul => "*",
+                BinaryOperator::Div => "/",
+            }),
+        }
+    }
+}
+
+/// Parses a filter expression.
+///
+/// In order of increasing precedence, the operators are `||`; `&&`; `==` (or `=`), `!=`, `<`,
+/// `<=`, `>`, `>=`, `in`; `+`, `-`; `*`, `/`; and the unary `!` and `-`. Fields may be followed
+/// by an element index, e.g., `INFO/AC[1]`, and functions take a single argument, e.g., `all(x)`.
+/// `in` is followed by a parenthesized list of literals, e.g., `x in ("a", 1)`.
+///
+/// Parenthesized expressions, function calls, unary operators, and chained binary operators
+/// increase the depth of the expression, which is limited to 128.
+pub(super) fn parse<R>(
+    s: &str,
+    resolver: &R,
+) -> Result<Expression<R::Field, R::Function>, ParseError>
+where
+    R: Resolver,
+{
+    let tokens = tokenize(s, resolver)?;
+
+    if tokens.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        depth: 0,
+        resolver,
+    };
+
+    let expr = parser.parse_or()?;
+
+    match parser.next() {
+        Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+        None => Ok(expr),
+    }
+}
+
+fn tokenize<R>(s: &str, resolver: &R) -> Result<Vec<Token>, ParseError>
+where
+    R: Resolver,
+{
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            ',' => Token::Comma,
+            '+' => Token::Operator(BinaryOperator::Add),
+            '-' => Token::Minus,
+            '*' => Token::Operator(BinaryOperator::Mul),
+            '/' => Token::Operator(BinaryOperator::Div),
+            '=' => {
+                next_if_eq(&mut chars, '=');
+                Token::Operator(BinaryOperator::Eq)
+            }
+            '!' => {
+                if next_if_eq(&mut chars, '=') {
+                    Token::Operator(BinaryOperator::Ne)
+                } else {
+                    Token::Not
+                }
+            }
+            '<' => {
+                if next_if_eq(&mut chars, '=') {
+                    Token::Operator(BinaryOperator::Le)
+                } else {
+                    Token::Operator(BinaryOperator::Lt)
+                }
+            }
+            '>' => {
+                if next_if_eq(&mut chars, '=') {
+                    Token::Operator(BinaryOperator::Ge)
+                } else {
+                    Token::Operator(BinaryOperator::Gt)
+                }
+            }
+            '&' if next_if_eq(&mut chars, '&') => Token::Operator(BinaryOperator::And),
+            '|' if next_if_eq(&mut chars, '|') => Token::Operator(BinaryOperator::Or),
+            '"' | '\'' => {
+                let start = i + c.len_utf8();
+
+                loop {
+                    match chars.next() {
+                        Some((j, d)) if d == c => break Token::String(s[start..j].into()),
+                        Some(_) => {}
+                        None => return Err(ParseError::UnterminatedString),
+                    }
+                }
+            }
+            _ if c.is_ascii_digit() || c == '.' => {
+                let end = take_number(s, i, &mut chars);
+                let t = &s[i..end];
+
+                t.parse()
+                    .map(Token::Number)
+                    .map_err(|_| ParseError::InvalidNumber(t.into()))?
+            }
+            _ if is_identifier_start(c) => {
+                let mut end = take_while(&mut chars, is_identifier_char).unwrap_or(i + 1);
+
+                if resolver.is_namespace(&s[i..end]) {
+                    let mut lookahead = chars.clone();
+
+                    if let (Some((_, '/')), Some((_, d))) = (lookahead.next(), lookahead.next()) {
+                        if d.is_ascii_alphanumeric() || d == '_' {
+                            chars.next();
+                            chars.next();
+                            end = take_while(&mut chars, is_identifier_char).unwrap_or(end + 2);
+                        }
+                    }
+                }
+
+                match &s[i..end] {
+                    "in" => Token::In,
+                    name => Token::Identifier(name.into()),
+                }
+            }
+            _ => return Err(ParseError::UnexpectedCharacter(c)),
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn next_if_eq(chars: &mut Peekable<CharIndices<'_>>, c: char) -> bool {
+    chars.next_if(|&(_, d)| d == c).is_some()
+}
+
+// Consumes characters while the predicate holds and returns the end of the last consumed one.
+fn take_while<P>(chars: &mut Peekable<CharIndices<'_>>, predicate: P) -> Option<usize>
+where
+    P: Fn(char) -> bool,
+{
+    let mut end = None;
+
+    while let Some((i, c)) = chars.next_if(|&(_, c)| predicate(c)) {
+        end = Some(i + c.len_utf8());
+    }
+
+    end
+}
+
+fn take_number(s: &str, start: usize, chars: &mut Peekable<CharIndices<'_>>) -> usize {
+    let mut end = start + 1;
+    let mut prev = s.as_bytes()[start];
+
+    while let Some(&(i, c)) = chars.peek() {
+        let is_sign = (c == '+' || c == '-') && (prev == b'e' || prev == b'E');
+
+        if c.is_ascii_alphanumeric() || c == '.' || is_sign {
+            chars.next();
+            end = i + 1;
+            prev = c as u8;
+        } else {
+            break;
+        }
+    }
+
+    end
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+struct Parser<'r, R> {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+    resolver: &'r R,
+}
+
+type ParseResult<R> =
+    Result<Expression<<R as Resolver>::Field, <R as Resolver>::Function>, ParseError>;
+
+impl<'r, R> Parser<'r, R>
+where
+    R: Resolver,
+{
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn next_if_operator(&mut self, operators: &[BinaryOperator]) -> Option<BinaryOperator> {
+        let operator = match self.peek() {
+            Some(Token::Operator(operator)) if operators.contains(operator) => *operator,
+            Some(Token::Minus) if operators.contains(&BinaryOperator::Sub) => BinaryOperator::Sub,
+            _ => return None,
+        };
+
+        self.position += 1;
+
+        Some(operator)
+    }
+
+    fn descend(&mut self) -> Result<(), ParseError> {
+        self.depth += 1;
+
+        if self.depth > MAX_DEPTH {
+            Err(ParseError::MaxDepthExceeded)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ParseError> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+            None => Err(ParseError::UnexpectedEof),
+        }
+    }
+
+    fn parse_binary<F>(
+        &mut self,
+        operators: &[BinaryOperator],
+        mut parse_operand: F,
+    ) -> ParseResult<R>
+    where
+        F: FnMut(&mut Self) -> ParseResult<R>,
+    {
+        let mut lhs = parse_operand(self)?;
+        let depth = self.depth;
+
+        // Each operator nests the previous operations one level deeper.
+        while let Some(operator) = self.next_if_operator(operators) {
+            self.descend()?;
+            let rhs = parse_operand(self)?;
+            lhs = Expression::Binary(operator, Box::new(lhs), Box::new(rhs));
+        }
+
+        self.depth = depth;
+
+        Ok(lhs)
+    }
+
+    fn parse_or(&mut self) -> ParseResult<R> {
+        self.parse_binary(&[BinaryOperator::Or], Self::parse_and)
+    }
+
+    fn parse_and(&mut self) -> ParseResult<R> {
+        self.parse_binary(&[BinaryOperator::And], Self::parse_comparison)
+    }
+
+    fn parse_comparison(&mut self) -> ParseResult<R> {
+        let expr = self.parse_binary(
+            &[
+                BinaryOperator::Eq,
+                BinaryOperator::Ne,
+                BinaryOperator::Lt,
+                BinaryOperator::Le,
+                BinaryOperator::Gt,
+                BinaryOperator::Ge,
+            ],
+            Self::parse_additive,
+        )?;
+
+        if self.peek() == Some(&Token::In) {
+            self.position += 1;
+            let literals = self.parse_literal_list()?;
+            Ok(Expression::In(Box::new(expr), literals))
+        } else {
+            Ok(expr)
+        }
+    }
+
+    fn parse_literal_list(&mut self) -> Result<Vec<Literal>, ParseError> {
+        self.expect(Token::LeftParenthesis)?;
+
+        let mut literals = Vec::new();
+
+        loop {
+            match self.next() {
+                Some(Token::Number(n)) => literals.push(Literal::Number(n)),
+                Some(Token::String(s)) => literals.push(Literal::String(s)),
+                Some(token) => return Err(ParseError::UnexpectedToken(token.to_string())),
+                None => return Err(ParseError::UnexpectedEof),
+            }
+
+            match self.next() {
+                Some(Token::Comma) => {}
+                Some(Token::RightParenthesis) => break,
+                Some(token) => return Err(ParseError::UnexpectedToken(token.to_string())),
+                None => return Err(ParseError::UnexpectedEof),
+            }
+        }
+
+        Ok(literals)
+    }
+
+    fn parse_additive(&mut self) -> ParseResult<R> {
+        self.parse_binary(
+            &[BinaryOperator::Add, BinaryOperator::Sub],
+            Self::parse_multiplicative,
+        )
+    }
+
+    fn parse_multiplicative(&mut self) -> ParseResult<R> {
+        self.parse_binary(
+            &[BinaryOperator::Mul, BinaryOperator::Div],
+            Self::parse_unary,
+        )
+    }
+
+    fn parse_unary(&mut self) -> ParseResult<R> {
+        let operator = match self.peek() {
+            Some(Token::Not) => UnaryOperator::Not,
+            Some(Token::Minus) => UnaryOperator::Negate,
+            _ => return self.parse_primary(),
+        };
+
+        self.position += 1;
+
+        self.descend()?;
+        let expr = self.parse_unary()?;
+        self.depth -= 1;
+
+        Ok(Expression::Unary(operator, Box::new(expr)))
+    }
+
+    fn parse_primary(&mut self) -> ParseResult<R> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expression::Literal(Literal::Number(n))),
+            Some(Token::String(s)) => Ok(Expression::Literal(Literal::String(s))),
+            Some(Token::LeftParenthesis) => {
+                self.descend()?;
+                let expr = self.parse_or()?;
+                self.depth -= 1;
+
+                self.expect(Token::RightParenthesis)?;
+
+                Ok(expr)
+            }
+            Some(Token::Identifier(name)) => {
+                if self.peek() == Some(&Token::LeftParenthesis) {
+                    let function = self
+                        .resolver
+                        .resolve_function(&name)
+                        .ok_or(ParseError::InvalidFunction(name))?;
+
+                    self.position += 1;
+
+                    self.descend()?;
+                    let expr = self.parse_or()?;
+                    self.depth -= 1;
+
+                    self.expect(Token::RightParenthesis)?;
+
+                    return Ok(Expression::Call(function, Box::new(expr)));
+                }
+
+                self.parse_field(name)
+            }
+            Some(Token::LeftBracket) => {
+                let name = match self.next() {
+                    Some(Token::Identifier(name)) => name,
+                    Some(token) => return Err(ParseError::UnexpectedToken(token.to_string())),
+                    None => return Err(ParseError::UnexpectedEof),
+                };
+
+                self.expect(Token::RightBracket)?;
+
+                self.parse_field(format!("[{name}]"))
+            }
+            Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+            None => Err(ParseError::UnexpectedEof),
+        }
+    }
+
+    fn parse_field(&mut self, name: String) -> ParseResult<R> {
+        let field = self
+            .resolver
+            .resolve_field(&name)
+            .ok_or(ParseError::InvalidField(name))?;
+
+        let index = if self.peek() == Some(&Token::LeftBracket) {
+            self.position += 1;
+            let index = self.parse_index()?;
+            self.expect(Token::RightBracket)?;
+            Some(index)
+        } else {
+            None
+        };
+
+        Ok(Expression::Field(field, index))
+    }
+
+    fn parse_index(&mut self) -> Result<usize, ParseError> {
+        match self.next() {
+            Some(Token::Number(n)) if n >= 0.0 && n.fract() == 0.0 && n <= usize::MAX as f64 => {
+                Ok(n as usize)
+            }
+            Some(token) => Err(ParseError::InvalidIndex(token.to_string())),
+            None => Err(ParseError::UnexpectedEof),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestResolver;
+
+    impl Resolver for TestResolver {
+        type Field = String;
+        type Function = String;
+
+        fn is_namespace(&self, name: &str) -> bool {
+            name == "INFO"
+        }
+
+        fn resolve_field(&self, name: &str) -> Option<Self::Field> {
+            if name == "invalid" || name == "[invalid]" {
+                None
+            } else {
+                Some(name.into())
+            }
+        }
+
+        fn resolve_function(&self, name: &str) -> Option<Self::Function> {
+            if name == "all" {
+                Some(name.into())
+            } else {
+                None
+            }
+        }
+    }
+
+    fn field(name: &str, index: Option<usize>) -> Box<Expression<String, String>> {
+        Box::new(Expression::Field(name.into(), index))
+    }
+
+    fn number(n: f64) -> Box<Expression<String, String>> {
+        Box::new(Expression::Literal(Literal::Number(n)))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("INFO/DP > 10 && QUAL >= 1e1", &TestResolver),
+            Ok(Expression::Binary(
+                BinaryOperator::And,
+                Box::new(Expression::Binary(
+                    BinaryOperator::Gt,
+                    field("INFO/DP", None),
+                    number(10.0),
+                )),
+                Box::new(Expression::Binary(
+                    BinaryOperator::Ge,
+                    field("QUAL", None),
+                    number(10.0),
+                )),
+            ))
+        );
+
+        assert_eq!(
+            parse("1 + 2 * -AC[1]", &TestResolver),
+            Ok(Expression::Binary(
+                BinaryOperator::Add,
+                number(1.0),
+                Box::new(Expression::Binary(
+                    BinaryOperator::Mul,
+                    number(2.0),
+                    Box::new(Expression::Unary(
+                        UnaryOperator::Negate,
+                        field("AC", Some(1))
+                    )),
+                )),
+            ))
+        );
+
+        assert_eq!(
+            parse("AC/AN", &TestResolver),
+            Ok(Expression::Binary(
+                BinaryOperator::Div,
+                field("AC", None),
+                field("AN", None),
+            ))
+        );
+
+        assert_eq!(
+            parse("!all(TYPE = 'snp')", &TestResolver),
+            Ok(Expression::Unary(
+                UnaryOperator::Not,
+                Box::new(Expression::Call(
+                    String::from("all"),
+                    Box::new(Expression::Binary(
+                        BinaryOperator::Eq,
+                        field("TYPE", None),
+                        Box::new(Expression::Literal(Literal::String(String::from("snp")))),
+                    )),
+                )),
+            ))
+        );
+
+        assert_eq!(
+            parse("(1 || 2) && 3", &TestResolver),
+            Ok(Expression::Binary(
+                BinaryOperator::And,
+                Box::new(Expression::Binary(
+                    BinaryOperator::Or,
+                    number(1.0),
+                    number(2.0)
+                )),
+                number(3.0),
+            ))
+        );
+
+        assert_eq!(
+            parse("[NM][1] in ('a', 2)", &TestResolver),
+            Ok(Expression::In(
+                field("[NM]", Some(1)),
+                vec![Literal::String(String::from("a")), Literal::Number(2.0)],
+            ))
+        );
+
+        assert_eq!(
+            parse("INFO/1000G", &TestResolver),
+            Ok(Expression::Field(String::from("INFO/1000G"), None))
+        );
+
+        assert_eq!(parse("", &TestResolver), Err(ParseError::Empty));
+        assert_eq!(parse(" ", &TestResolver), Err(ParseError::Empty));
+        assert_eq!(parse("1 +", &TestResolver), Err(ParseError::UnexpectedEof));
+        assert_eq!(parse("(1", &TestResolver), Err(ParseError::UnexpectedEof));
+        assert_eq!(
+            parse("1 2", &TestResolver),
+            Err(ParseError::UnexpectedToken(String::from("2")))
+        );
+        assert_eq!(
+            parse("1 & 2", &TestResolver),
+            Err(ParseError::UnexpectedCharacter('&'))
+        );
+        assert_eq!(
+            parse("'snp", &TestResolver),
+            Err(ParseError::UnterminatedString)
+        );
+        assert_eq!(
+            parse("1.2.3", &TestResolver),
+            Err(ParseError::InvalidNumber(String::from("1.2.3")))
+        );
+        assert_eq!(
+            parse("AC[-1]", &TestResolver),
+            Err(ParseError::InvalidIndex(String::from("-")))
+        );
+        assert_eq!(
+            parse("AC[0.5]", &TestResolver),
+            Err(ParseError::InvalidIndex(String::from("0.5")))
+        );
+        assert_eq!(
+            parse("invalid > 1", &TestResolver),
+            Err(ParseError::InvalidField(String::from("invalid")))
+        );
+        assert_eq!(
+            parse("any(1)", &TestResolver),
+            Err(ParseError::InvalidFunction(String::from("any")))
+        );
+        assert_eq!(parse("[NM", &TestResolver), Err(ParseError::UnexpectedEof));
+        assert_eq!(
+            parse("[1]", &TestResolver),
+            Err(ParseError::UnexpectedToken(String::from("1")))
+        );
+        assert_eq!(
+            parse("[invalid]", &TestResolver),
+            Err(ParseError::InvalidField(String::from("[invalid]")))
+        );
+        assert_eq!(
+            parse("AC in (AN)", &TestResolver),
+            Err(ParseError::UnexpectedToken(String::from("AN")))
+        );
+        assert_eq!(
+            parse("AC in ('a' 'b')", &TestResolver),
+            Err(ParseError::UnexpectedToken(String::from("\"b\"")))
+        );
+    }
+
+    #[test]
+    fn test_parse_with_max_depth() {
+        fn nest(prefix: &str, s: &str, suffix: &str, n: usize) -> String {
+            format!("{}{s}{}", prefix.repeat(n), suffix.repeat(n))
+        }
+
+        assert!(parse(&nest("(", "1", ")", MAX_DEPTH), &TestResolver).is_ok());
+        assert!(parse(&nest("all(", "1", ")", MAX_DEPTH), &TestResolver).is_ok());
+        assert!(parse(&nest("!", "1", "", MAX_DEPTH), &TestResolver).is_ok());
+        assert!(parse(&nest("", "1", " + 1", MAX_DEPTH), &TestResolver).is_ok());
+
+        for s in [
+            nest("(", "1", ")", MAX_DEPTH + 1),
+            nest("all(", "1", ")", MAX_DEPTH + 1),
+            nest("!", "1", "", MAX_DEPTH + 1),
+            nest("", "1", " + 1", MAX_DEPTH + 1),
+            nest("", "1", " && 1", MAX_DEPTH + 1),
+            nest("(", "1 - -1", ")", MAX_DEPTH),
+        ] {
+            assert_eq!(parse(&s, &TestResolver), Err(ParseError::MaxDepthExceeded));
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use std::{borrow::Cow, cmp::Ordering, io};
+
+use super::{BinaryOperator, Literal, UnaryOperator};
+
+/// An evaluated value.
+///
+/// Strings borrow from the evaluated record when possible.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    /// A missing value.
+    Missing,
+    /// A boolean.
+    Bool(bool),
+    /// A number.
+    Number(f64),
+    /// A string.
+    String(Cow<'a, str>),
+    /// A list of values.
+    List(Vec<Value<'a>>),
+}
+
+impl<'a> Value<'a> {
+    /// Returns whether the value is considered true.
+    ///
+    /// Missing values, `false`, zero, `NaN`, and empty strings are false. A list is true if any
+    /// of its elements are true.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::expression::Value;
+    ///
+    /// assert!(Value::Number(8.0).is_truthy());
+    /// assert!(!Value::Missing.is_truthy());
+    /// assert!(Value::List(vec![Value::Missing, Value::Bool(true)]).is_truthy());
+    /// ```
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Self::Missing => false,
+            Self::Bool(b) => *b,
+            Self::Number(n) => *n != 0.0 && !n.is_nan(),
+            Self::String(s) => !s.is_empty(),
+            Self::List(values) => values.iter().any(|value| value.is_truthy()),
+        }
+    }
+
+    /// Returns the element at the given index.
+    ///
+    /// A scalar is treated as a list with a single element. An element that does not exist is
+    /// missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::expression::Value;
+    ///
+    /// let value = Value::List(vec![Value::Number(5.0), Value::Number(8.0)]);
+    /// assert_eq!(value.clone().index(1), Value::Number(8.0));
+    /// assert_eq!(value.index(2), Value::Missing);
+    ///
+    /// assert_eq!(Value::Number(13.0).index(0), Value::Number(13.0));
+    /// ```
+    pub fn index(self, i: usize) -> Self {
+        match self {
+            Self::List(mut values) => {
+                if i < values.len() {
+                    values.swap_remove(i)
+                } else {
+                    Self::Missing
+                }
+            }
+            Self::Missing => Self::Missing,
+            value if i == 0 => value,
+            _ => Self::Missing,
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Bool(b) => Some(f64::from(u8::from(*b))),
+            Self::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Literal> for Value<'_> {
+    fn from(literal: &Literal) -> Self {
+        match literal {
+            Literal::Number(n) => Self::Number(*n),
+            Literal::String(s) => Self::String(Cow::Owned(s.clone())),
+        }
+    }
+}
+
+/// Applies a unary operator to a value.
+pub(super) fn apply_unary<'a>(operator: UnaryOperator, value: Value<'a>) -> io::Result<Value<'a>> {
+    match operator {
+        UnaryOperator::Not => Ok(Value::Bool(!value.is_truthy())),
+        UnaryOperator::Negate => match value {
+            Value::Missing => Ok(Value::Missing),
+            Value::List(values) => values
+                .into_iter()
+                .map(|value| apply_unary(operator, value))
+                .collect::<io::Result<_>>()
+                .map(Value::List),
+            value => value
+                .as_number()
+                .map(|n| Value::Number(-n))
+                .ok_or_else(|| type_error("cannot negate", &value)),
+        },
+    }
+}
+
+/// Applies a binary operator to two values.
+///
+/// Comparisons with a list are true if any element satisfies the comparison, and arithmetic is
+/// applied element-wise. Comparisons with a missing value are false, and arithmetic with a
+/// missing value is missing.
+pub(super) fn apply_binary<'a>(
+    operator: BinaryOperator,
+    lhs: Value<'a>,
+    rhs: Value<'a>,
+) -> io::Result<Value<'a>> {
+    match operator {
+        BinaryOperator::Or => Ok(Value::Bool(lhs.is_truthy() || rhs.is_truthy())),
+        BinaryOperator::And => Ok(Value::Bool(lhs.is_truthy() && rhs.is_truthy())),
+        BinaryOperator::Eq
+        | BinaryOperator::Ne
+        | BinaryOperator::Lt
+        | BinaryOperator::Le
+        | BinaryOperator::Gt
+        | BinaryOperator::Ge => compare(operator, &lhs, &rhs).map(Value::Bool),
+        BinaryOperator::Add | BinaryOperator::Sub | BinaryOperator::Mul | BinaryOperator::Div => {
+            calculate(operator, lhs, rhs)
+        }
+    }
+}
+
+pub(super) fn compare(
+    operator: BinaryOperator,
+    lhs: &Value<'_>,
+    rhs: &Value<'_>,
+) -> io::Result<bool> {
+    match (lhs, rhs) {
+        (Value::List(values), _) => {
+            for value in values {
+                if compare(operator, value, rhs)? {
+                    return Ok(true);
+                }
+            }
+
+            Ok(false)
+        }
+        (_, Value::List(values)) => {
+            for value in values {
+                if compare(operator, lhs, value)? {
+                    return Ok(true);
+                }
+            }
+
+            Ok(false)
+        }
+        (Value::Missing, _) | (_, Value::Missing) => Ok(false),
+        (Value::String(a), Value::String(b)) => Ok(is_match(operator, Some(a.cmp(b)))),
+        _ => match (lhs.as_number(), rhs.as_number()) {
+            (Some(a), Some(b)) => Ok(is_match(operator, a.partial_cmp(&b))),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("cannot compare {lhs:?} and {rhs:?}"),
+            )),
+        },
+    }
+}
+
+fn is_match(operator: BinaryOperator, ordering: Option<Ordering>) -> bool {
+    match (operator, ordering) {
+        (BinaryOperator::Ne, None) => true,
+        (_, None) => false,
+        (BinaryOperator::Eq, Some(ordering)) => ordering.is_eq(),
+        (BinaryOperator::Ne, Some(ordering)) => ordering.is_ne(),
+        (BinaryOperator::Lt, Some(ordering)) => ordering.is_lt(),
+        (BinaryOperator::Le, Some(ordering)) => ordering.is_le(),
+        (BinaryOperator::Gt, Some(ordering)) => ordering.is_gt(),
+        (BinaryOperator::Ge, Some(ordering)) => ordering.is_ge(),
+        _ => false,
+    }
+}
+
+fn calculate<'a>(
+    operator: BinaryOperator,
+    lhs: Value<'a>,
+    rhs: Value<'a>,
+) -> io::Result<Value<'a>> {
+    match (lhs, rhs) {
+        (Value::Missing, _) | (_, Value::Missing) => Ok(Value::Missing),
+        (Value::List(values), rhs) => values
+            .into_iter()
+            .map(|value| calculate(operator, value, rhs.clone()))
+            .collect::<io::Result<_>>()
+            .map(Value::List),
+        (lhs, Value::List(values)) => values
+            .into_iter()
+            .map(|value| calculate(operator, lhs.clone(), value))
+            .collect::<io::Result<_>>()
+            .map(Value::List),
+        (lhs, rhs) => {
+            let a = lhs
+                .as_number()
+                .ok_or_else(|| type_error("cannot calculate with", &lhs))?;
+            let b = rhs
+                .as_number()
+                .ok_or_else(|| type_error("cannot calculate with", &rhs))?;
+
+            let n = match operator {
+                BinaryOperator::Add => a + b,
+                BinaryOperator::Sub => a - b,
+                BinaryOperator::Mul => a * b,
+                BinaryOperator::Div => a / b,
+                _ => unreachable!(),
+            };
+
+            Ok(Value::Number(n))
+        }
+    }
+}
+
+fn type_error(message: &str, value: &Value<'_>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{message} {value:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(s: &str) -> Value<'_> {
+        Value::String(Cow::Borrowed(s))
+    }
+
+    #[test]
+    fn test_is_truthy() {
+        assert!(!Value::Missing.is_truthy());
+        assert!(Value::Bool(true).is_truthy());
+        assert!(!Value::Number(0.0).is_truthy());
+        assert!(!Value::Number(f64::NAN).is_truthy());
+        assert!(Value::Number(-1.0).is_truthy());
+        assert!(!string("").is_truthy());
+        assert!(string("ndls").is_truthy());
+        assert!(Value::List(vec![Value::Missing, Value::Number(1.0)]).is_truthy());
+        assert!(!Value::List(Vec::new()).is_truthy());
+    }
+
+    #[test]
+    fn test_index() {
+        let list = Value::List(vec![Value::Number(1.0), Value::Number(2.0)]);
+        assert_eq!(list.clone().index(1), Value::Number(2.0));
+        assert_eq!(list.index(2), Value::Missing);
+
+        assert_eq!(Value::Number(5.0).index(0), Value::Number(5.0));
+        assert_eq!(Value::Number(5.0).index(1), Value::Missing);
+        assert_eq!(Value::Missing.index(0), Value::Missing);
+    }
+
+    #[test]
+    fn test_apply_unary() -> io::Result<()> {
+        assert_eq!(
+            apply_unary(UnaryOperator::Not, Value::Missing)?,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            apply_unary(UnaryOperator::Negate, Value::Number(2.0))?,
+            Value::Number(-2.0)
+        );
+        assert_eq!(
+            apply_unary(UnaryOperator::Negate, Value::Missing)?,
+            Value::Missing
+        );
+        assert!(apply_unary(UnaryOperator::Negate, string("ndls")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_binary() -> io::Result<()> {
+        assert_eq!(
+            apply_binary(BinaryOperator::Lt, Value::Number(1.0), Value::Number(2.0))?,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            apply_binary(BinaryOperator::Eq, string("a"), string("a"))?,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            apply_binary(BinaryOperator::Eq, Value::Bool(true), Value::Number(1.0))?,
+            Value::Bool(true)
+        );
+
+        let list = Value::List(vec![Value::Number(1.0), Value::Missing, Value::Number(8.0)]);
+        assert_eq!(
+            apply_binary(BinaryOperator::Gt, list.clone(), Value::Number(5.0))?,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            apply_binary(BinaryOperator::Gt, list.clone(), Value::Number(10.0))?,
+            Value::Bool(false)
+        );
+        assert_eq!(
+            apply_binary(BinaryOperator::Mul, list, Value::Number(2.0))?,
+            Value::List(vec![
+                Value::Number(2.0),
+                Value::Missing,
+                Value::Number(16.0)
+            ])
+        );
+
+        assert_eq!(
+            apply_binary(BinaryOperator::Ne, Value::Missing, Value::Number(1.0))?,
+            Value::Bool(false)
+        );
+        assert_eq!(
+            apply_binary(BinaryOperator::Add, Value::Missing, Value::Number(1.0))?,
+            Value::Missing
+        );
+        assert_eq!(
+            apply_binary(BinaryOperator::Div, Value::Number(1.0), Value::Number(4.0))?,
+            Value::Number(0.25)
+        );
+
+        assert!(apply_binary(BinaryOperator::Lt, string("a"), Value::Number(1.0)).is_err());
+        assert!(apply_binary(BinaryOperator::Add, string("a"), Value::Number(1.0)).is_err());
+
+        Ok(())
+    }
+}
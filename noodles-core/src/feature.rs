@@ -0,0 +1,67 @@
+//! Genomic features.
+
+use crate::{region::Interval, Region};
+
+/// A genomic feature.
+///
+/// A feature is located by a reference sequence name and a 1-based, closed interval, regardless
+/// of how the underlying format represents its coordinates.
+///
+/// Some formats store reference sequences by ID rather than name, e.g., SAM/BAM/CRAM alignment
+/// records and BCF records. The header type `H` is the context used to resolve the reference
+/// sequence name. Formats that do not need a context use the unit type.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{self as core, region::Interval, Feature, Position, Region};
+///
+/// struct Record {
+///     name: String,
+///     start: Position,
+///     end: Position,
+/// }
+///
+/// impl Feature for Record {
+///     fn reference_sequence_name<'a>(&'a self, _: &'a ()) -> core::Result<&'a str> {
+///         Ok(&self.name)
+///     }
+///
+///     fn interval(&self) -> core::Result<Interval> {
+///         Ok(Interval::from(self.start..=self.end))
+///     }
+/// }
+///
+/// let record = Record {
+///     name: String::from("sq0"),
+///     start: Position::try_from(8)?,
+///     end: Position::try_from(13)?,
+/// };
+///
+/// assert_eq!(record.region(&())?, "sq0:8-13".parse()?);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub trait Feature<H: ?Sized = ()> {
+    /// Returns the reference sequence name.
+    fn reference_sequence_name<'a>(&'a self, header: &'a H) -> crate::Result<&'a str>;
+
+    /// Returns the 1-based, closed interval of the feature.
+    fn interval(&self) -> crate::Result<Interval>;
+
+    /// Returns the region of the feature.
+    fn region(&self, header: &H) -> crate::Result<Region> {
+        let name = self.reference_sequence_name(header)?;
+        let interval = self.interval()?;
+        Ok(Region::new(name, interval))
+    }
+}
+
+impl Feature for Region {
+    fn reference_sequence_name<'a>(&'a self, _: &'a ()) -> crate::Result<&'a str> {
+        Ok(self.name())
+    }
+
+    fn interval(&self) -> crate::Result<Interval> {
+        Ok(self.interval())
+    }
+}
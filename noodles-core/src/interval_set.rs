@@ -0,0 +1,558 @@
+//! Genomic interval sets.
+
+mod overlap_index;
+
+pub use self::overlap_index::OverlapIndex;
+
+use std::{cmp, collections::BTreeMap};
+
+use crate::{region::Interval, Position, Region};
+
+/// A set of genomic intervals.
+///
+/// Intervals are grouped by reference sequence name. Within each reference sequence, intervals
+/// are kept sorted, and overlapping or adjacent intervals are merged.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet {
+    intervals: BTreeMap<String, Vec<(Position, Position)>>,
+}
+
+impl IntervalSet {
+    /// Creates an empty interval set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::interval_set::IntervalSet;
+    /// let set = IntervalSet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of intervals in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, Position};
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+    /// set.insert("sq0", Position::try_from(7)?..=Position::try_from(13)?);
+    /// set.insert("sq1", Position::try_from(1)?..=Position::try_from(2)?);
+    ///
+    /// assert_eq!(set.len(), 2);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.intervals
+            .values()
+            .map(|intervals| intervals.len())
+            .sum()
+    }
+
+    /// Returns whether the set has no intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::interval_set::IntervalSet;
+    /// let set = IntervalSet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Adds an interval to the set.
+    ///
+    /// The interval is merged with any overlapping or adjacent intervals. An unbounded start or
+    /// end is resolved to [`Position::MIN`] or [`Position::MAX`], respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, region::Interval, Position};
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+    /// set.insert("sq0", Position::try_from(9)?..=Position::try_from(13)?);
+    ///
+    /// let intervals: Vec<_> = set.intervals("sq0").collect();
+    /// assert_eq!(
+    ///     intervals,
+    ///     [Interval::from(Position::try_from(5)?..=Position::try_from(13)?)]
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn insert<N, I>(&mut self, name: N, interval: I)
+    where
+        N: Into<String>,
+        I: Into<Interval>,
+    {
+        let (start, end) = resolve(interval.into());
+
+        if start > end {
+            return;
+        }
+
+        let intervals = self.intervals.entry(name.into()).or_default();
+
+        // The first interval that ends at or after the position before the start.
+        let i = intervals.partition_point(|&(_, e)| next(e).map(|n| n < start).unwrap_or(false));
+
+        let mut j = i;
+        let (mut merged_start, mut merged_end) = (start, end);
+
+        while j < intervals.len() {
+            let (s, e) = intervals[j];
+
+            if next(merged_end).map(|n| s > n).unwrap_or(false) {
+                break;
+            }
+
+            merged_start = cmp::min(merged_start, s);
+            merged_end = cmp::max(merged_end, e);
+            j += 1;
+        }
+
+        intervals.splice(i..j, [(merged_start, merged_end)]);
+    }
+
+    /// Returns whether a position is in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, Position};
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+    ///
+    /// assert!(set.contains("sq0", Position::try_from(8)?));
+    /// assert!(!set.contains("sq0", Position::try_from(13)?));
+    /// assert!(!set.contains("sq1", Position::try_from(8)?));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn contains(&self, name: &str, position: Position) -> bool {
+        let intervals = match self.intervals.get(name) {
+            Some(intervals) => intervals,
+            None => return false,
+        };
+
+        let i = intervals.partition_point(|&(_, e)| e < position);
+
+        intervals
+            .get(i)
+            .map(|&(s, _)| s <= position)
+            .unwrap_or(false)
+    }
+
+    /// Returns an iterator over the reference sequence names in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, Position};
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert("sq1", Position::try_from(1)?..=Position::try_from(2)?);
+    /// set.insert("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+    ///
+    /// assert!(set.names().eq(["sq0", "sq1"]));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.intervals.keys().map(|name| name.as_str())
+    }
+
+    /// Returns an iterator over the sorted intervals of a reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, region::Interval, Position};
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert("sq0", Position::try_from(13)?..=Position::try_from(21)?);
+    /// set.insert("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+    ///
+    /// let intervals: Vec<_> = set.intervals("sq0").collect();
+    ///
+    /// assert_eq!(intervals, [
+    ///     Interval::from(Position::try_from(5)?..=Position::try_from(8)?),
+    ///     Interval::from(Position::try_from(13)?..=Position::try_from(21)?),
+    /// ]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn intervals(&self, name: &str) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|&(start, end)| Interval::from(start..=end))
+    }
+
+    /// Returns an iterator over all intervals in the set as regions.
+    ///
+    /// Regions are ordered by reference sequence name and then by start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, Position, Region};
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+    ///
+    /// let regions: Vec<_> = set.regions().collect();
+    /// assert_eq!(
+    ///     regions,
+    ///     [Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?)]
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn regions(&self) -> impl Iterator<Item = Region> + '_ {
+        self.intervals.iter().flat_map(|(name, intervals)| {
+            intervals
+                .iter()
+                .map(move |&(start, end)| Region::new(name.clone(), start..=end))
+        })
+    }
+
+    /// Returns the union of this set and another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, region::Interval, Position};
+    ///
+    /// let mut a = IntervalSet::new();
+    /// a.insert("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+    ///
+    /// let mut b = IntervalSet::new();
+    /// b.insert("sq0", Position::try_from(8)?..=Position::try_from(13)?);
+    ///
+    /// let set = a.union(&b);
+    /// let intervals: Vec<_> = set.intervals("sq0").collect();
+    /// assert_eq!(
+    ///     intervals,
+    ///     [Interval::from(Position::try_from(5)?..=Position::try_from(13)?)]
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+
+        for (name, intervals) in &other.intervals {
+            for &(start, end) in intervals {
+                set.insert(name.clone(), start..=end);
+            }
+        }
+
+        set
+    }
+
+    /// Returns the intersection of this set and another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, region::Interval, Position};
+    ///
+    /// let mut a = IntervalSet::new();
+    /// a.insert("sq0", Position::try_from(5)?..=Position::try_from(13)?);
+    ///
+    /// let mut b = IntervalSet::new();
+    /// b.insert("sq0", Position::try_from(8)?..=Position::try_from(21)?);
+    ///
+    /// let set = a.intersection(&b);
+    /// let intervals: Vec<_> = set.intervals("sq0").collect();
+    /// assert_eq!(
+    ///     intervals,
+    ///     [Interval::from(Position::try_from(8)?..=Position::try_from(13)?)]
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut set = Self::new();
+
+        for (name, a) in &self.intervals {
+            let b = match other.intervals.get(name) {
+                Some(intervals) => intervals,
+                None => continue,
+            };
+
+            let (mut i, mut j) = (0, 0);
+            let mut intervals = Vec::new();
+
+            while i < a.len() && j < b.len() {
+                let start = cmp::max(a[i].0, b[j].0);
+                let end = cmp::min(a[i].1, b[j].1);
+
+                if start <= end {
+                    intervals.push((start, end));
+                }
+
+                if a[i].1 < b[j].1 {
+                    i += 1;
+                } else {
+                    j += 1;
+                }
+            }
+
+            if !intervals.is_empty() {
+                set.intervals.insert(name.clone(), intervals);
+            }
+        }
+
+        set
+    }
+
+    /// Returns the intervals of this set that are not in another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, region::Interval, Position};
+    ///
+    /// let mut a = IntervalSet::new();
+    /// a.insert("sq0", Position::try_from(5)?..=Position::try_from(13)?);
+    ///
+    /// let mut b = IntervalSet::new();
+    /// b.insert("sq0", Position::try_from(8)?..=Position::try_from(10)?);
+    ///
+    /// let set = a.difference(&b);
+    /// let intervals: Vec<_> = set.intervals("sq0").collect();
+    /// assert_eq!(intervals, [
+    ///     Interval::from(Position::try_from(5)?..=Position::try_from(7)?),
+    ///     Interval::from(Position::try_from(11)?..=Position::try_from(13)?),
+    /// ]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = Self::new();
+
+        for (name, a) in &self.intervals {
+            let b = other
+                .intervals
+                .get(name)
+                .map(|intervals| &intervals[..])
+                .unwrap_or_default();
+
+            let intervals = subtract(a, b);
+
+            if !intervals.is_empty() {
+                set.intervals.insert(name.clone(), intervals);
+            }
+        }
+
+        set
+    }
+
+    /// Returns the intervals that are not in this set, bounded by the given reference sequence
+    /// lengths.
+    ///
+    /// Each reference sequence spans [1, length]. Intervals on reference sequences that are not
+    /// listed are excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::IntervalSet, region::Interval, Position};
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+    ///
+    /// let complement = set.complement([
+    ///     ("sq0", Position::try_from(13)?),
+    ///     ("sq1", Position::try_from(21)?),
+    /// ]);
+    ///
+    /// let intervals: Vec<_> = complement.intervals("sq0").collect();
+    /// assert_eq!(intervals, [
+    ///     Interval::from(Position::try_from(1)?..=Position::try_from(4)?),
+    ///     Interval::from(Position::try_from(9)?..=Position::try_from(13)?),
+    /// ]);
+    ///
+    /// let intervals: Vec<_> = complement.intervals("sq1").collect();
+    /// assert_eq!(
+    ///     intervals,
+    ///     [Interval::from(Position::try_from(1)?..=Position::try_from(21)?)]
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn complement<I, N>(&self, reference_sequence_lengths: I) -> Self
+    where
+        I: IntoIterator<Item = (N, Position)>,
+        N: Into<String>,
+    {
+        let mut set = Self::new();
+
+        for (name, length) in reference_sequence_lengths {
+            let name = name.into();
+
+            let b = self
+                .intervals
+                .get(&name)
+                .map(|intervals| &intervals[..])
+                .unwrap_or_default();
+
+            let intervals = subtract(&[(Position::MIN, length)], b);
+
+            if !intervals.is_empty() {
+                set.intervals.insert(name, intervals);
+            }
+        }
+
+        set
+    }
+}
+
+impl Extend<Region> for IntervalSet {
+    fn extend<T: IntoIterator<Item = Region>>(&mut self, iter: T) {
+        for region in iter {
+            self.insert(region.name(), region.interval());
+        }
+    }
+}
+
+impl FromIterator<Region> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Region>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+pub(crate) fn resolve(interval: Interval) -> (Position, Position) {
+    (
+        interval.start().unwrap_or(Position::MIN),
+        interval.end().unwrap_or(Position::MAX),
+    )
+}
+
+fn next(position: Position) -> Option<Position> {
+    position.checked_add(1)
+}
+
+fn prev(position: Position) -> Option<Position> {
+    Position::new(usize::from(position) - 1)
+}
+
+// Both inputs must be sorted and merged.
+fn subtract(a: &[(Position, Position)], b: &[(Position, Position)]) -> Vec<(Position, Position)> {
+    let mut intervals = Vec::new();
+    let mut j = 0;
+
+    for &(start, end) in a {
+        let mut current_start = Some(start);
+
+        while j < b.len() && b[j].1 < start {
+            j += 1;
+        }
+
+        let mut k = j;
+
+        while let Some(s) = current_start {
+            match b.get(k) {
+                Some(&(b_start, b_end)) if b_start <= end => {
+                    if b_start > s {
+                        if let Some(e) = prev(b_start) {
+                            intervals.push((s, e));
+                        }
+                    }
+
+                    current_start = if b_end < end { next(b_end) } else { None };
+                    k += 1;
+                }
+                _ => {
+                    intervals.push((s, end));
+                    current_start = None;
+                }
+            }
+        }
+    }
+
+    intervals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_set(
+        name: &str,
+        intervals: &[(usize, usize)],
+    ) -> Result<IntervalSet, crate::position::TryFromIntError> {
+        let mut set = IntervalSet::new();
+
+        for &(start, end) in intervals {
+            set.insert(name, Position::try_from(start)?..=Position::try_from(end)?);
+        }
+
+        Ok(set)
+    }
+
+    fn to_pairs(set: &IntervalSet, name: &str) -> Vec<(usize, usize)> {
+        set.intervals
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|&(s, e)| (usize::from(s), usize::from(e)))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert() -> Result<(), crate::position::TryFromIntError> {
+        let set = build_set(
+            "sq0",
+            &[(8, 13), (1, 2), (21, 34), (4, 6), (14, 15), (3, 3)],
+        )?;
+        assert_eq!(to_pairs(&set, "sq0"), [(1, 6), (8, 15), (21, 34)]);
+
+        let set = build_set("sq0", &[(5, 8), (13, 21), (2, 34)])?;
+        assert_eq!(to_pairs(&set, "sq0"), [(2, 34)]);
+
+        let mut set = IntervalSet::new();
+        set.insert("sq0", Position::try_from(8)?..);
+        set.insert("sq0", ..=Position::try_from(5)?);
+        assert_eq!(to_pairs(&set, "sq0"), [(1, 5), (8, usize::MAX)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_operations() -> Result<(), crate::position::TryFromIntError> {
+        let a = build_set("sq0", &[(1, 5), (8, 13), (21, 34)])?;
+        let b = build_set("sq0", &[(3, 9), (13, 13), (30, 40)])?;
+
+        assert_eq!(to_pairs(&a.union(&b), "sq0"), [(1, 13), (21, 40)]);
+
+        assert_eq!(
+            to_pairs(&a.intersection(&b), "sq0"),
+            [(3, 5), (8, 9), (13, 13), (30, 34)]
+        );
+
+        assert_eq!(
+            to_pairs(&a.difference(&b), "sq0"),
+            [(1, 2), (10, 12), (21, 29)]
+        );
+
+        assert_eq!(to_pairs(&b.difference(&a), "sq0"), [(6, 7), (35, 40)]);
+
+        let complement = a.complement([("sq0", Position::try_from(30)?)]);
+        assert_eq!(to_pairs(&complement, "sq0"), [(6, 7), (14, 20)]);
+
+        let complement = a.complement([("sq0", Position::try_from(34)?)]);
+        assert_eq!(to_pairs(&complement, "sq0"), [(6, 7), (14, 20)]);
+
+        let complement = a.complement([("sq1", Position::try_from(8)?)]);
+        assert!(to_pairs(&complement, "sq0").is_empty());
+        assert_eq!(to_pairs(&complement, "sq1"), [(1, 8)]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+
+use super::resolve;
+use crate::{region::Interval, Position, Region};
+
+const LINEAR_SCAN_MAX_LEVEL: usize = 3;
+
+/// An immutable index of values by genomic interval.
+///
+/// Each reference sequence is indexed as an implicit augmented interval tree, i.e., intervals
+/// are sorted by start position, and each node in the tree stores the maximum end position of its
+/// subtree.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{interval_set::OverlapIndex, Position, Region};
+///
+/// let index: OverlapIndex<&str> = [
+///     (Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?), "a"),
+///     (Region::new("sq0", Position::try_from(13)?..=Position::try_from(21)?), "b"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let region = Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?);
+/// let values: Vec<_> = index.overlapping(&region).map(|(_, value)| *value).collect();
+/// assert_eq!(values, ["a", "b"]);
+/// # Ok::<_, noodles_core::position::TryFromIntError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct OverlapIndex<T> {
+    trees: HashMap<String, Tree<T>>,
+}
+
+impl<T> OverlapIndex<T> {
+    /// Returns the number of indexed intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::OverlapIndex, Position, Region};
+    ///
+    /// let index: OverlapIndex<()> = [
+    ///     (Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?), ()),
+    ///     (Region::new("sq1", Position::try_from(13)?..=Position::try_from(21)?), ()),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(index.len(), 2);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.trees.values().map(|tree| tree.entries.len()).sum()
+    }
+
+    /// Returns whether there are no indexed intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::interval_set::OverlapIndex;
+    /// let index: OverlapIndex<()> = std::iter::empty().collect();
+    /// assert!(index.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    /// Returns an iterator over the indexed intervals and values that intersect the given region.
+    ///
+    /// Results are ordered by start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::OverlapIndex, region::Interval, Position, Region};
+    ///
+    /// let index: OverlapIndex<u32> = [
+    ///     (Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?), 0),
+    ///     (Region::new("sq0", Position::try_from(13)?..=Position::try_from(21)?), 1),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let region = Region::new("sq0", Position::try_from(1)?..=Position::try_from(5)?);
+    /// let results: Vec<_> = index.overlapping(&region).collect();
+    /// assert_eq!(
+    ///     results,
+    ///     [(Interval::from(Position::try_from(5)?..=Position::try_from(8)?), &0)]
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn overlapping(&self, region: &Region) -> impl Iterator<Item = (Interval, &T)> {
+        let (start, end) = resolve(region.interval());
+
+        let (tree, indices) = match self.trees.get(region.name()) {
+            Some(tree) => (Some(tree), tree.overlapping(start, end)),
+            None => (None, Vec::new()),
+        };
+
+        indices
+            .into_iter()
+            .filter_map(move |i| tree.map(|t| t.get(i)))
+    }
+
+    /// Returns the indexed intervals and values nearest to the given region.
+    ///
+    /// If any intervals intersect the region, these are returned. Otherwise, the closest
+    /// preceding or following intervals are returned. When the preceding and following intervals
+    /// are equidistant, both are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_set::OverlapIndex, Position, Region};
+    ///
+    /// let index: OverlapIndex<&str> = [
+    ///     (Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?), "a"),
+    ///     (Region::new("sq0", Position::try_from(21)?..=Position::try_from(34)?), "b"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let region = Region::new("sq0", Position::try_from(13)?..=Position::try_from(13)?);
+    /// let values: Vec<_> = index.nearest(&region).into_iter().map(|(_, value)| *value).collect();
+    /// assert_eq!(values, ["a"]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn nearest(&self, region: &Region) -> Vec<(Interval, &T)> {
+        let (start, end) = resolve(region.interval());
+
+        match self.trees.get(region.name()) {
+            Some(tree) => tree
+                .nearest(start, end)
+                .into_iter()
+                .map(|i| tree.get(i))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Region, T)> for OverlapIndex<T> {
+    fn from_iter<I: IntoIterator<Item = (Region, T)>>(iter: I) -> Self {
+        let mut entries: HashMap<String, Vec<Entry<T>>> = HashMap::new();
+
+        for (region, value) in iter {
+            let (start, end) = resolve(region.interval());
+
+            entries
+                .entry(region.name().into())
+                .or_default()
+                .push(Entry {
+                    start,
+                    end,
+                    max_end: end,
+                    value,
+                });
+        }
+
+        let trees = entries
+            .into_iter()
+            .map(|(name, entries)| (name, Tree::new(entries)))
+            .collect();
+
+        Self { trees }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    start: Position,
+    end: Position,
+    max_end: Position,
+    value: T,
+}
+
+#[derive(Clone, Debug)]
+struct Tree<T> {
+    entries: Vec<Entry<T>>,
+    root_level: usize,
+    // The maximum end position of entries[..=i].
+    prefix_max_ends: Vec<Position>,
+}
+
+impl<T> Tree<T> {
+    fn new(mut entries: Vec<Entry<T>>) -> Self {
+        entries.sort_by_key(|entry| (entry.start, entry.end));
+
+        let root_level = build_max_ends(&mut entries);
+
+        let mut prefix_max_ends = Vec::with_capacity(entries.len());
+        let mut max_end = Position::MIN;
+
+        for entry in &entries {
+            max_end = max_end.max(entry.end);
+            prefix_max_ends.push(max_end);
+        }
+
+        Self {
+            entries,
+            root_level,
+            prefix_max_ends,
+        }
+    }
+
+    fn get(&self, i: usize) -> (Interval, &T) {
+        let entry = &self.entries[i];
+        (Interval::from(entry.start..=entry.end), &entry.value)
+    }
+
+    fn overlapping(&self, start: Position, end: Position) -> Vec<usize> {
+        let entries = &self.entries;
+        let n = entries.len();
+
+        let mut indices = Vec::new();
+
+        if n == 0 {
+            return indices;
+        }
+
+        // (level, node index, whether the left child was visited)
+        let mut stack = vec![(self.root_level, (1 << self.root_level) - 1, false)];
+
+        while let Some((level, i, is_left_visited)) = stack.pop() {
+            if level <= LINEAR_SCAN_MAX_LEVEL {
+                let i0 = i >> level << level;
+                let i1 = (i0 + (1 << (level + 1)) - 1).min(n);
+
+                for (j, entry) in entries.iter().enumerate().take(i1).skip(i0) {
+                    if entry.start > end {
+                        break;
+                    }
+
+                    if entry.end >= start {
+                        indices.push(j);
+                    }
+                }
+            } else if !is_left_visited {
+                stack.push((level, i, true));
+
+                // The left child may be out of range when the tree is not full.
+                let left = i - (1 << (level - 1));
+
+                if left >= n || entries[left].max_end >= start {
+                    stack.push((level - 1, left, false));
+                }
+            } else if i < n && entries[i].start <= end {
+                if entries[i].end >= start {
+                    indices.push(i);
+                }
+
+                stack.push((level - 1, i + (1 << (level - 1)), false));
+            }
+        }
+
+        indices.sort_unstable();
+
+        indices
+    }
+
+    fn nearest(&self, start: Position, end: Position) -> Vec<usize> {
+        let indices = self.overlapping(start, end);
+
+        if !indices.is_empty() {
+            return indices;
+        }
+
+        let entries = &self.entries;
+
+        // Since no entries overlap, all entries that start before the query end before it, too.
+        let p = entries.partition_point(|entry| entry.start <= end);
+
+        let preceding = p.checked_sub(1).map(|i| {
+            let max_end = self.prefix_max_ends[i];
+            let first = self.prefix_max_ends.partition_point(|&e| e < max_end);
+
+            let indices: Vec<_> = (first..p).filter(|&j| entries[j].end == max_end).collect();
+
+            (usize::from(start) - usize::from(max_end), indices)
+        });
+
+        let following = entries.get(p).map(|entry| {
+            let indices: Vec<_> = (p..entries.len())
+                .take_while(|&j| entries[j].start == entry.start)
+                .collect();
+
+            (usize::from(entry.start) - usize::from(end), indices)
+        });
+
+        match (preceding, following) {
+            (Some((d, mut a)), Some((e, b))) => {
+                if d < e {
+                    a
+                } else if e < d {
+                    b
+                } else {
+                    a.extend(b);
+                    a
+                }
+            }
+            (Some((_, a)), None) => a,
+            (None, Some((_, b))) => b,
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+// Sets the maximum end position of each node's subtree and returns the level of the root.
+fn build_max_ends<T>(entries: &mut [Entry<T>]) -> usize {
+    let n = entries.len();
+
+    if n == 0 {
+        return 0;
+    }
+
+    let mut last_i = 0;
+    let mut last = Position::MIN;
+
+    for i in (0..n).step_by(2) {
+        entries[i].max_end = entries[i].end;
+        last_i = i;
+        last = entries[i].end;
+    }
+
+    let mut level = 1;
+
+    while 1 << level <= n {
+        let x = 1 << (level - 1);
+        let i0 = (x << 1) - 1;
+        let step = x << 2;
+
+        for i in (i0..n).step_by(step) {
+            let left_max_end = entries[i - x].max_end;
+
+            let right_max_end = if i + x < n {
+                entries[i + x].max_end
+            } else {
+                last
+            };
+
+            entries[i].max_end = entries[i].end.max(left_max_end).max(right_max_end);
+        }
+
+        last_i = if (last_i >> level) & 1 == 1 {
+            last_i - x
+        } else {
+            last_i + x
+        };
+
+        if last_i < n {
+            last = last.max(entries[last_i].max_end);
+        }
+
+        level += 1;
+    }
+
+    level - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(n: usize) -> Position {
+        Position::new(n).unwrap()
+    }
+
+    fn build_index(intervals: &[(usize, usize)]) -> OverlapIndex<usize> {
+        intervals
+            .iter()
+            .enumerate()
+            .map(|(i, &(start, end))| (Region::new("sq0", position(start)..=position(end)), i))
+            .collect()
+    }
+
+    #[test]
+    fn test_overlapping() {
+        // A simple linear congruential generator for reproducible pseudorandom intervals.
+        let mut state: u64 = 0x5eed;
+
+        let mut next = |n: usize| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) as usize) % n
+        };
+
+        for len in [0, 1, 2, 3, 7, 8, 15, 16, 17, 100, 257] {
+            let intervals: Vec<_> = (0..len)
+                .map(|_| {
+                    let start = next(1000) + 1;
+                    let span = if next(10) == 0 { next(500) } else { next(20) };
+                    (start, start + span)
+                })
+                .collect();
+
+            let index = build_index(&intervals);
+
+            for _ in 0..100 {
+                let start = next(1100) + 1;
+                let end = start + next(50);
+
+                let region = Region::new("sq0", position(start)..=position(end));
+
+                let mut actual: Vec<_> = index.overlapping(&region).map(|(_, &i)| i).collect();
+                actual.sort_unstable();
+
+                let expected: Vec<_> = intervals
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &(s, e))| s <= end && e >= start)
+                    .map(|(i, _)| i)
+                    .collect();
+
+                assert_eq!(actual, expected, "len = {len}, query = [{start}, {end}]");
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlapping_with_missing_reference_sequence() {
+        let index = build_index(&[(5, 8)]);
+        let region = Region::new("sq1", ..);
+        assert_eq!(index.overlapping(&region).count(), 0);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let index = build_index(&[(5, 8), (2, 10), (21, 34), (21, 22), (40, 50)]);
+
+        let t = |start, end, expected: &[usize]| {
+            let region = Region::new("sq0", position(start)..=position(end));
+            let mut actual: Vec<_> = index
+                .nearest(&region)
+                .into_iter()
+                .map(|(_, &i)| i)
+                .collect();
+            actual.sort_unstable();
+            assert_eq!(actual, expected, "[{start}, {end}]");
+        };
+
+        t(8, 9, &[0, 1]);
+        t(13, 13, &[1]);
+        t(16, 16, &[2, 3]);
+        t(14, 17, &[1, 2, 3]);
+        t(36, 36, &[2]);
+        t(38, 38, &[4]);
+        t(60, 60, &[4]);
+        t(1, 1, &[1]);
+    }
+}
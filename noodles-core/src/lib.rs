@@ -3,10 +3,15 @@
 //! **noodles-core** contains shared structures and behavior among noodles libraries.
 
 pub mod error;
+#[cfg(feature = "expression")]
+pub mod expression;
+pub mod feature;
+pub mod interval_set;
 pub mod position;
 pub mod region;
+pub mod region_list;
 
-pub use self::{error::Error, position::Position, region::Region};
+pub use self::{error::Error, feature::Feature, position::Position, region::Region};
 
 /// A specialized [`std::result::Result`] type for results in noodles.
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -0,0 +1,321 @@
+//! Validated lists of genomic regions.
+
+mod dictionary;
+
+pub use self::dictionary::Dictionary;
+
+use std::{num::NonZeroUsize, slice};
+
+use crate::{error::Kind, Error, Feature, Position, Region};
+
+/// A list of genomic regions.
+///
+/// Regions are kept in the order they are added. The constructors validate each region against a
+/// reference sequence [`Dictionary`].
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::region_list::{Dictionary, RegionList};
+///
+/// let dictionary: Dictionary = [("sq0", 8), ("sq1", 13)].into_iter().collect();
+/// let regions = RegionList::parse(&dictionary, ["sq0:5-21", "sq1"])?;
+///
+/// let actual: Vec<_> = regions.iter().map(|region| region.to_string()).collect();
+/// assert_eq!(actual, ["sq0:5-8", "sq1:1-13"]);
+/// # Ok::<_, noodles_core::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RegionList {
+    regions: Vec<Region>,
+}
+
+impl RegionList {
+    /// Creates an empty region list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::RegionList;
+    /// let regions = RegionList::new();
+    /// assert!(regions.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses and validates a list of region strings.
+    ///
+    /// Each input is either a reference sequence name, i.e., the whole reference sequence, or a
+    /// region string (`<name>[:<start>[-<end>]]`). See [`Dictionary::parse_region`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::{Dictionary, RegionList};
+    ///
+    /// let dictionary: Dictionary = [("sq0", 8)].into_iter().collect();
+    ///
+    /// let regions = RegionList::parse(&dictionary, ["sq0:2-5"])?;
+    /// assert_eq!(regions.len(), 1);
+    ///
+    /// assert!(RegionList::parse(&dictionary, ["sq1"]).is_err());
+    /// # Ok::<_, noodles_core::Error>(())
+    /// ```
+    pub fn parse<I, S>(dictionary: &Dictionary, regions: I) -> crate::Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        regions
+            .into_iter()
+            .map(|s| dictionary.parse_region(s.as_ref()))
+            .collect::<crate::Result<_>>()
+            .map(|regions| Self { regions })
+    }
+
+    /// Creates a region list from features, e.g., BED records.
+    ///
+    /// Each feature is converted to a region (see [`Feature::region`]) and validated against the
+    /// dictionary. Since features are normalized to 1-based, closed intervals, this handles
+    /// coordinate conversions, e.g., the 0-based, half-open intervals of BED records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{
+    ///     region_list::{Dictionary, RegionList},
+    ///     Position, Region,
+    /// };
+    ///
+    /// let dictionary: Dictionary = [("sq0", 8)].into_iter().collect();
+    /// let features = [Region::new("sq0", Position::try_from(5)?..=Position::try_from(13)?)];
+    ///
+    /// let regions = RegionList::from_features(&dictionary, &(), features)?;
+    /// assert_eq!(regions.iter().next(), Some(&"sq0:5-8".parse()?));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_features<I, F, H>(
+        dictionary: &Dictionary,
+        header: &H,
+        features: I,
+    ) -> crate::Result<Self>
+    where
+        I: IntoIterator<Item = F>,
+        F: Feature<H>,
+        H: ?Sized,
+    {
+        features
+            .into_iter()
+            .map(|feature| {
+                feature
+                    .region(header)
+                    .and_then(|region| dictionary.validate(&region))
+            })
+            .collect::<crate::Result<_>>()
+            .map(|regions| Self { regions })
+    }
+
+    /// Creates a region list of the whole reference sequences in the dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::{Dictionary, RegionList};
+    ///
+    /// let dictionary: Dictionary = [("sq0", 8), ("sq1", 13)].into_iter().collect();
+    /// let regions = RegionList::whole_genome(&dictionary)?;
+    ///
+    /// let actual: Vec<_> = regions.iter().map(|region| region.to_string()).collect();
+    /// assert_eq!(actual, ["sq0:1-8", "sq1:1-13"]);
+    /// # Ok::<_, noodles_core::Error>(())
+    /// ```
+    pub fn whole_genome(dictionary: &Dictionary) -> crate::Result<Self> {
+        dictionary
+            .iter()
+            .map(|(name, _)| dictionary.validate(&Region::new(name, ..)))
+            .collect::<crate::Result<_>>()
+            .map(|regions| Self { regions })
+    }
+
+    /// Creates a region list that tiles the whole genome with windows of the given size.
+    ///
+    /// This is useful to split work into parallel jobs. The last window of each reference
+    /// sequence may be shorter than the given size. All reference sequence lengths must be
+    /// known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_core::region_list::{Dictionary, RegionList};
+    ///
+    /// let dictionary: Dictionary = [("sq0", 8), ("sq1", 3)].into_iter().collect();
+    /// let regions = RegionList::windows(&dictionary, NonZeroUsize::try_from(5)?)?;
+    ///
+    /// let actual: Vec<_> = regions.iter().map(|region| region.to_string()).collect();
+    /// assert_eq!(actual, ["sq0:1-5", "sq0:6-8", "sq1:1-3"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn windows(dictionary: &Dictionary, size: NonZeroUsize) -> crate::Result<Self> {
+        Self::whole_genome(dictionary)?.into_windows(size)
+    }
+
+    /// Splits each region into windows of the given size.
+    ///
+    /// The last window of each region may be shorter than the given size. Each region must have
+    /// a bounded end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_core::region_list::{Dictionary, RegionList};
+    ///
+    /// let dictionary: Dictionary = [("sq0", 21)].into_iter().collect();
+    /// let regions = RegionList::parse(&dictionary, ["sq0:3-13"])?
+    ///     .into_windows(NonZeroUsize::try_from(5)?)?;
+    ///
+    /// let actual: Vec<_> = regions.iter().map(|region| region.to_string()).collect();
+    /// assert_eq!(actual, ["sq0:3-7", "sq0:8-12", "sq0:13-13"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn into_windows(self, size: NonZeroUsize) -> crate::Result<Self> {
+        let mut regions = Vec::new();
+
+        for region in self.regions {
+            let interval = region.interval();
+            let start = usize::from(interval.start().unwrap_or(Position::MIN));
+
+            let end = interval.end().map(usize::from).ok_or_else(|| {
+                Error::new(Kind::Other, format!("region end is unbounded: {region}"))
+            })?;
+
+            let mut window_start = start;
+
+            while window_start <= end {
+                let window_end = window_start.saturating_add(size.get() - 1).min(end);
+
+                let interval = Position::new(window_start)
+                    .zip(Position::new(window_end))
+                    .map(|(s, e)| s..=e)
+                    .ok_or_else(|| Error::new(Kind::Other, "invalid window position"))?;
+
+                regions.push(Region::new(region.name(), interval));
+
+                window_start = match window_end.checked_add(1) {
+                    Some(n) => n,
+                    None => break,
+                };
+            }
+        }
+
+        Ok(Self { regions })
+    }
+
+    /// Returns the number of regions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::RegionList;
+    /// let regions = RegionList::new();
+    /// assert_eq!(regions.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns whether the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::RegionList;
+    /// let regions = RegionList::new();
+    /// assert!(regions.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Returns an iterator over the regions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::RegionList;
+    /// let regions = RegionList::new();
+    /// assert!(regions.iter().next().is_none());
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'_, Region> {
+        self.regions.iter()
+    }
+}
+
+impl AsRef<[Region]> for RegionList {
+    fn as_ref(&self) -> &[Region] {
+        &self.regions
+    }
+}
+
+impl From<RegionList> for Vec<Region> {
+    fn from(regions: RegionList) -> Self {
+        regions.regions
+    }
+}
+
+impl Extend<Region> for RegionList {
+    fn extend<T: IntoIterator<Item = Region>>(&mut self, iter: T) {
+        self.regions.extend(iter);
+    }
+}
+
+impl FromIterator<Region> for RegionList {
+    fn from_iter<T: IntoIterator<Item = Region>>(iter: T) -> Self {
+        Self {
+            regions: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for RegionList {
+    type Item = Region;
+    type IntoIter = std::vec::IntoIter<Region>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.regions.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a RegionList {
+    type Item = &'a Region;
+    type IntoIter = slice::Iter<'a, Region>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.regions.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows() -> Result<(), Box<dyn std::error::Error>> {
+        let mut dictionary: Dictionary = [("sq0", 10), ("sq1", 1)].into_iter().collect();
+
+        let regions = RegionList::windows(&dictionary, NonZeroUsize::try_from(5)?)?;
+        let actual: Vec<_> = regions.iter().map(|region| region.to_string()).collect();
+        assert_eq!(actual, ["sq0:1-5", "sq0:6-10", "sq1:1-1"]);
+
+        let regions = RegionList::windows(&dictionary, NonZeroUsize::try_from(100)?)?;
+        let actual: Vec<_> = regions.iter().map(|region| region.to_string()).collect();
+        assert_eq!(actual, ["sq0:1-10", "sq1:1-1"]);
+
+        dictionary.insert("sq2", None);
+        assert!(RegionList::windows(&dictionary, NonZeroUsize::try_from(5)?).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::Kind,
+    region::{Interval, ParseError},
+    Error, Position, Region,
+};
+
+/// A reference sequence dictionary.
+///
+/// A dictionary is an ordered list of reference sequence names and their lengths, e.g., the
+/// reference sequences in a SAM header, the contigs in a VCF header, or the records in a FASTA
+/// index. Lengths are optional, as they are not always known.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Dictionary {
+    entries: Vec<(String, Option<usize>)>,
+    indices: HashMap<String, usize>,
+}
+
+impl Dictionary {
+    /// Creates an empty reference sequence dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// let dictionary = Dictionary::new();
+    /// assert!(dictionary.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of reference sequences in the dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// let dictionary: Dictionary = [("sq0", 8), ("sq1", 13)].into_iter().collect();
+    /// assert_eq!(dictionary.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the dictionary is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// let dictionary = Dictionary::new();
+    /// assert!(dictionary.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds a reference sequence with an optional length.
+    ///
+    /// If the name already exists, its length is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    ///
+    /// let mut dictionary = Dictionary::new();
+    /// dictionary.insert("sq0", Some(8));
+    /// dictionary.insert("sq1", None);
+    ///
+    /// assert_eq!(dictionary.get("sq0"), Some(Some(8)));
+    /// assert_eq!(dictionary.get("sq1"), Some(None));
+    /// ```
+    pub fn insert<N>(&mut self, name: N, length: Option<usize>)
+    where
+        N: Into<String>,
+    {
+        let name = name.into();
+
+        if let Some(&i) = self.indices.get(&name) {
+            self.entries[i].1 = length;
+        } else {
+            self.indices.insert(name.clone(), self.entries.len());
+            self.entries.push((name, length));
+        }
+    }
+
+    /// Returns the length of the reference sequence with the given name.
+    ///
+    /// This returns `None` if the name is not in the dictionary and `Some(None)` if the length is
+    /// unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// let dictionary: Dictionary = [("sq0", 8)].into_iter().collect();
+    /// assert_eq!(dictionary.get("sq0"), Some(Some(8)));
+    /// assert!(dictionary.get("sq1").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<Option<usize>> {
+        self.indices.get(name).map(|&i| self.entries[i].1)
+    }
+
+    /// Returns whether the dictionary has a reference sequence with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// let dictionary: Dictionary = [("sq0", 8)].into_iter().collect();
+    /// assert!(dictionary.contains("sq0"));
+    /// assert!(!dictionary.contains("sq1"));
+    /// ```
+    pub fn contains(&self, name: &str) -> bool {
+        self.indices.contains_key(name)
+    }
+
+    /// Returns an iterator over the reference sequence names and lengths, in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// let dictionary: Dictionary = [("sq0", 8), ("sq1", 13)].into_iter().collect();
+    /// let entries: Vec<_> = dictionary.iter().collect();
+    /// assert_eq!(entries, [("sq0", Some(8)), ("sq1", Some(13))]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<usize>)> {
+        self.entries
+            .iter()
+            .map(|(name, length)| (name.as_str(), *length))
+    }
+
+    /// Validates a region against the dictionary.
+    ///
+    /// The reference sequence name must be in the dictionary. If the length of the reference
+    /// sequence is known, the region is bounded by it, i.e., an unbounded or overhanging end is
+    /// clamped to the length, and the start must not be past the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region_list::Dictionary, Position, Region};
+    ///
+    /// let dictionary: Dictionary = [("sq0", 8)].into_iter().collect();
+    ///
+    /// let region = Region::new("sq0", Position::try_from(5)?..=Position::try_from(13)?);
+    /// assert_eq!(dictionary.validate(&region)?, "sq0:5-8".parse()?);
+    ///
+    /// assert!(dictionary.validate(&"sq1".parse()?).is_err());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate(&self, region: &Region) -> crate::Result<Region> {
+        let length = self.get(region.name()).ok_or_else(|| {
+            Error::new(
+                Kind::Other,
+                format!("invalid reference sequence name: {}", region.name()),
+            )
+        })?;
+
+        let length = match length {
+            Some(length) => length,
+            None => return Ok(region.clone()),
+        };
+
+        let max_end = Position::new(length)
+            .ok_or_else(|| Error::new(Kind::Other, "invalid reference sequence length"))?;
+
+        let interval = region.interval();
+        let start = interval.start().unwrap_or(Position::MIN);
+
+        if start > max_end {
+            return Err(Error::new(
+                Kind::Other,
+                format!("region start is past the end of the reference sequence: {region}"),
+            ));
+        }
+
+        let end = interval
+            .end()
+            .map(|end| end.min(max_end))
+            .unwrap_or(max_end);
+
+        if end < start {
+            return Err(Error::new(
+                Kind::Other,
+                format!("invalid region interval: {region}"),
+            ));
+        }
+
+        Ok(Region::new(region.name(), start..=end))
+    }
+
+    /// Parses and validates a region.
+    ///
+    /// The input is either a reference sequence name, i.e., the whole reference sequence, or a
+    /// region string (`<name>[:<start>[-<end>]]`). Since names may contain colons, the input is
+    /// first checked against the dictionary. It is an error if the input resolves to both a
+    /// reference sequence name and a region string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region_list::Dictionary, Position, Region};
+    ///
+    /// let dictionary: Dictionary = [("sq0", 8), ("HLA-A*01:01", 13)].into_iter().collect();
+    ///
+    /// assert_eq!(dictionary.parse_region("sq0:5")?, "sq0:5-8".parse()?);
+    /// assert_eq!(dictionary.parse_region("HLA-A*01:01")?, "HLA-A*01:01:1-13".parse()?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parse_region(&self, s: &str) -> crate::Result<Region> {
+        if s.is_empty() {
+            return Err(Error::new(Kind::Parse, ParseError::Empty));
+        }
+
+        let whole = if self.contains(s) {
+            Some(Region::new(s, ..))
+        } else {
+            None
+        };
+
+        let partial = s.rsplit_once(':').and_then(|(name, suffix)| {
+            if self.contains(name) {
+                suffix
+                    .parse::<Interval>()
+                    .ok()
+                    .map(|interval| Region::new(name, interval))
+            } else {
+                None
+            }
+        });
+
+        let region = match (whole, partial) {
+            (Some(_), Some(_)) => return Err(Error::new(Kind::Parse, ParseError::Ambiguous)),
+            (Some(region), None) | (None, Some(region)) => region,
+            (None, None) => s
+                .parse()
+                .map_err(|e: ParseError| Error::new(Kind::Parse, e))?,
+        };
+
+        self.validate(&region)
+    }
+}
+
+impl<N> Extend<(N, usize)> for Dictionary
+where
+    N: Into<String>,
+{
+    fn extend<T: IntoIterator<Item = (N, usize)>>(&mut self, iter: T) {
+        for (name, length) in iter {
+            self.insert(name, Some(length));
+        }
+    }
+}
+
+impl<N> FromIterator<(N, usize)> for Dictionary
+where
+    N: Into<String>,
+{
+    fn from_iter<T: IntoIterator<Item = (N, usize)>>(iter: T) -> Self {
+        let mut dictionary = Self::new();
+        dictionary.extend(iter);
+        dictionary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut dictionary: Dictionary = [("sq0", 8)].into_iter().collect();
+        dictionary.insert("sq1", None);
+
+        assert_eq!(dictionary.validate(&"sq0".parse()?)?, "sq0:1-8".parse()?);
+        assert_eq!(
+            dictionary.validate(&"sq0:3-5".parse()?)?,
+            "sq0:3-5".parse()?
+        );
+        assert_eq!(
+            dictionary.validate(&"sq0:5-13".parse()?)?,
+            "sq0:5-8".parse()?
+        );
+        assert!(dictionary.validate(&"sq0:13".parse()?).is_err());
+        assert!(dictionary.validate(&"sq0:5-3".parse()?).is_err());
+
+        assert_eq!(dictionary.validate(&"sq1:5".parse()?)?, "sq1:5".parse()?);
+
+        assert!(dictionary.validate(&"sq2".parse()?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_region() -> Result<(), Box<dyn std::error::Error>> {
+        let dictionary: Dictionary = [("sq0", 8), ("sq0:1", 13), ("sq1:2", 21)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(dictionary.parse_region("sq0")?, "sq0:1-8".parse()?);
+        assert_eq!(dictionary.parse_region("sq0:3-5")?, "sq0:3-5".parse()?);
+        assert_eq!(
+            dictionary.parse_region("sq1:2")?,
+            Region::new("sq1:2", Position::MIN..=Position::try_from(21)?)
+        );
+
+        assert_eq!(
+            dictionary.parse_region("sq0:1").map_err(|e| e.kind()),
+            Err(Kind::Parse)
+        );
+
+        assert!(dictionary.parse_region("").is_err());
+        assert!(dictionary.parse_region("sq2").is_err());
+
+        Ok(())
+    }
+}
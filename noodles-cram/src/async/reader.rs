@@ -1,3 +1,6 @@
+//! Async CRAM reader.
+
+mod builder;
 mod crc_reader;
 mod data_container;
 mod header_container;
@@ -5,7 +8,11 @@ mod num;
 mod query;
 mod records;
 
-pub use self::crc_reader::CrcReader;
+pub use self::builder::Builder;
+
+pub(crate) use self::crc_reader::CrcReader;
+
+use std::collections::HashSet;
 
 use bytes::BytesMut;
 use futures::Stream;
@@ -20,6 +27,8 @@ use crate::{crai, file_definition::Version, DataContainer, FileDefinition, Recor
 pub struct Reader<R> {
     inner: R,
     buf: BytesMut,
+    strict_reference_sequence_checks: bool,
+    verified_reference_sequence_ids: HashSet<usize>,
 }
 
 impl<R> Reader<R>
@@ -36,9 +45,32 @@ where
     /// let reader = cram::AsyncReader::new(&data[..]);
     /// ```
     pub fn new(inner: R) -> Self {
-        Self {
-            inner,
-            buf: BytesMut::new(),
+        Builder::default().build_from_reader(inner)
+    }
+
+    /// Returns whether reference sequences are strictly checked.
+    ///
+    /// See [`Builder::strict_reference_sequence_checks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let reader = cram::AsyncReader::new(&data[..]);
+    /// assert!(!reader.strict_reference_sequence_checks());
+    /// ```
+    pub fn strict_reference_sequence_checks(&self) -> bool {
+        self.strict_reference_sequence_checks
+    }
+
+    // Returns the IDs of the reference sequences whose `M5` checksums were verified, if reference
+    // sequences are strictly checked.
+    pub(crate) fn verified_reference_sequence_ids(&mut self) -> Option<&mut HashSet<usize>> {
+        if self.strict_reference_sequence_checks {
+            Some(&mut self.verified_reference_sequence_ids)
+        } else {
+            None
         }
     }
 
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use bytes::BytesMut;
+use tokio::io::AsyncRead;
+
+use super::Reader;
+
+/// An async CRAM reader builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    strict_reference_sequence_checks: bool,
+}
+
+impl Builder {
+    /// Sets whether to strictly check reference sequences.
+    ///
+    /// See [`crate::reader::Builder::strict_reference_sequence_checks`].
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::r#async::reader::Builder::default()
+    ///     .strict_reference_sequence_checks(true);
+    /// ```
+    pub fn strict_reference_sequence_checks(mut self, value: bool) -> Self {
+        self.strict_reference_sequence_checks = value;
+        self
+    }
+
+    /// Builds an async CRAM reader from a reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let reader = cram::r#async::reader::Builder::default().build_from_reader(&data[..]);
+    /// ```
+    pub fn build_from_reader<R>(self, reader: R) -> Reader<R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        Reader {
+            inner: reader,
+            buf: BytesMut::new(),
+            strict_reference_sequence_checks: self.strict_reference_sequence_checks,
+            verified_reference_sequence_ids: HashSet::new(),
+        }
+    }
+}
@@ -101,11 +101,12 @@ where
             let compression_header = container.compression_header();
 
             slice.records(compression_header).and_then(|mut records| {
-                slice.resolve_records(
+                slice.resolve_records_inner(
                     ctx.reference_sequence_repository,
                     ctx.header,
                     compression_header,
                     &mut records,
+                    ctx.reader.verified_reference_sequence_ids(),
                 )?;
 
                 Ok(records)
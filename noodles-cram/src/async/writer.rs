@@ -13,7 +13,9 @@ use tokio::io::{self, AsyncWrite, AsyncWriteExt};
 
 pub use self::builder::Builder;
 use crate::{
-    file_definition::Version, writer::Options, DataContainer, FileDefinition, Record, MAGIC_NUMBER,
+    file_definition::Version,
+    writer::{Options, Selector},
+    DataContainer, FileDefinition, Record, MAGIC_NUMBER,
 };
 
 /// An async CRAM writer.
@@ -23,6 +25,7 @@ pub struct Writer<W> {
     inner: W,
     reference_sequence_repository: fasta::Repository,
     options: Options,
+    block_content_encoder_selector: Option<Selector>,
     data_container_builder: crate::data_container::Builder,
     record_counter: u64,
 }
@@ -140,16 +143,23 @@ where
     /// ```
     pub async fn write_file_header(&mut self, header: &sam::Header) -> io::Result<()> {
         use self::header_container::write_header_container;
-        use crate::writer::add_missing_reference_sequence_checksums;
+        use crate::writer::{add_missing_reference_sequence_checksums, ReferenceSequenceMode};
 
         let mut header = header.clone();
 
-        add_missing_reference_sequence_checksums(
-            &self.reference_sequence_repository,
-            header.reference_sequences_mut(),
-        )?;
+        if self.options.reference_sequence_mode != ReferenceSequenceMode::None {
+            add_missing_reference_sequence_checksums(
+                &self.reference_sequence_repository,
+                header.reference_sequences_mut(),
+            )?;
+        }
 
-        write_header_container(&mut self.inner, &header).await
+        write_header_container(
+            &mut self.inner,
+            &header,
+            self.options.reference_sequence_mode,
+        )
+        .await
     }
 
     /// Writes a CRAM record.
@@ -185,7 +195,10 @@ where
         use crate::data_container::builder::AddRecordError;
 
         loop {
-            match self.data_container_builder.add_record(record) {
+            match self
+                .data_container_builder
+                .add_record(&self.options, record)
+            {
                 Ok(_) => {
                     self.record_counter += 1;
                     return Ok(());
@@ -222,6 +235,7 @@ where
             &self.options,
             &self.reference_sequence_repository,
             header,
+            self.block_content_encoder_selector.as_mut(),
         )?;
 
         write_data_container(&mut self.inner, &data_container, base_count).await
@@ -1,9 +1,13 @@
+use std::num::NonZeroUsize;
+
 use noodles_fasta as fasta;
 use tokio::io::AsyncWrite;
 
 use super::Writer;
 use crate::{
-    data_container::BlockContentEncoderMap, file_definition::Version, writer::Options,
+    data_container::BlockContentEncoderMap,
+    file_definition::Version,
+    writer::{Options, ReferenceSequenceMode, Selector, TrialCompression},
     DataContainer,
 };
 
@@ -44,12 +48,62 @@ impl Builder {
         self
     }
 
+    /// Sets the reference sequence mode.
+    ///
+    /// This controls whether slices reference an external reference sequence, embed the
+    /// reference sequence span, or are encoded without a reference. See
+    /// [`ReferenceSequenceMode`].
+    ///
+    /// The default is [`ReferenceSequenceMode::External`].
+    pub fn set_reference_sequence_mode(mut self, mode: ReferenceSequenceMode) -> Self {
+        self.options.reference_sequence_mode = mode;
+        self
+    }
+
+    /// Sets the maximum number of records per slice.
+    ///
+    /// The default is 10240.
+    pub fn set_records_per_slice(mut self, records_per_slice: NonZeroUsize) -> Self {
+        self.options.records_per_slice = records_per_slice;
+        self
+    }
+
+    /// Sets the maximum number of slices per container.
+    ///
+    /// The default is 1.
+    pub fn set_slices_per_container(mut self, slices_per_container: NonZeroUsize) -> Self {
+        self.options.slices_per_container = slices_per_container;
+        self
+    }
+
+    /// Sets whether to pack records of differing reference sequences into multi-reference
+    /// slices.
+    ///
+    /// This is typically useful for unsorted, query name-sorted, or unmapped data. If `false`, a
+    /// change in reference sequence ends both the current slice and container, i.e., each slice
+    /// and container refers to a single reference sequence (or none).
+    ///
+    /// The default is `true`.
+    pub fn use_multi_reference_slices(mut self, value: bool) -> Self {
+        self.options.multi_reference_slices = value;
+        self
+    }
+
     /// Sets the block content-encoder map.
     pub fn set_block_content_encoder_map(mut self, map: BlockContentEncoderMap) -> Self {
         self.options.block_content_encoder_map = map;
         self
     }
 
+    /// Sets the trial compression configuration.
+    ///
+    /// If set, the encoders of external blocks are selected by trial compression rather than the
+    /// block content-encoder map.
+    pub fn set_trial_compression(mut self, trial_compression: TrialCompression) -> Self {
+        self.options.trial_compression = Some(trial_compression);
+        self
+    }
+
     /// Builds an async CRAM writer from a writer.
     ///
     /// # Examples
@@ -63,16 +117,27 @@ impl Builder {
     where
         W: AsyncWrite + Unpin,
     {
-        use crate::writer::builder::uses_cram_3_1_codecs;
+        use crate::writer::builder::{is_cram_3_1_codec, uses_cram_3_1_codecs};
 
-        if uses_cram_3_1_codecs(&self.options.block_content_encoder_map) {
+        if uses_cram_3_1_codecs(&self.options.block_content_encoder_map)
+            || self
+                .options
+                .trial_compression
+                .as_ref()
+                .map(|t| t.candidates().iter().any(is_cram_3_1_codec))
+                .unwrap_or(false)
+        {
             self.options.version = Version::new(3, 1);
         }
 
+        let block_content_encoder_selector =
+            self.options.trial_compression.clone().map(Selector::new);
+
         Writer {
             inner: writer,
             reference_sequence_repository: self.reference_sequence_repository,
             options: self.options,
+            block_content_encoder_selector,
             data_container_builder: DataContainer::builder(0),
             record_counter: 0,
         }
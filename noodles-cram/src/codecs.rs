@@ -2,10 +2,10 @@
 
 pub mod aac;
 pub(crate) mod bzip2;
-pub(crate) mod fqzcomp;
+pub mod fqzcomp;
 pub(crate) mod gzip;
 pub(crate) mod lzma;
-pub(crate) mod name_tokenizer;
+pub mod name_tokenizer;
 pub mod rans_4x8;
 pub mod rans_nx16;
 
@@ -25,7 +25,7 @@ pub enum Encoder {
     /// adaptive arithmetic coder
     AdaptiveArithmeticCoding(aac::Flags),
     /// name tokenizer
-    NameTokenizer,
+    NameTokenizer(name_tokenizer::Options),
     /// fqzcomp
-    Fqzcomp,
+    Fqzcomp(fqzcomp::Options),
 }
@@ -233,7 +233,9 @@ fn encode_order_1(src: &[u8], dst: &mut Vec<u8>) -> io::Result<()> {
 
     let mut range_coder = RangeCoder::default();
 
-    models[0].encode(dst, &mut range_coder, src[0])?;
+    if let Some(&sym) = src.first() {
+        models[0].encode(dst, &mut range_coder, sym)?;
+    }
 
     for window in src.windows(2) {
         let sym_0 = usize::from(window[0]);
@@ -308,6 +310,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encode_order_1_with_pack_and_a_single_symbol() -> io::Result<()> {
+        let src = b"nnnn";
+        let actual = encode(Flags::ORDER | Flags::PACK, src)?;
+
+        let mut reader = &actual[..];
+        assert_eq!(super::super::decode(&mut reader, 0)?, src);
+
+        Ok(())
+    }
+
     #[test]
     fn test_encode_cat() -> io::Result<()> {
         let actual = encode(Flags::CAT, b"noodles")?;
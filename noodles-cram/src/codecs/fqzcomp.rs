@@ -1,9 +1,15 @@
+//! fqzcomp quality score codec.
+
 mod decode;
 mod encode;
+mod options;
 mod parameter;
 mod parameters;
+mod selector;
+mod strategy;
 
-pub use self::{decode::decode, encode::encode};
+pub(crate) use self::{decode::decode, encode::encode};
+pub use self::{options::Options, selector::Selector, strategy::Strategy};
 
 use super::aac::Model;
 
@@ -39,7 +45,7 @@ mod tests {
             let lens: Vec<_> = data.iter().map(|scores| scores.len()).collect();
             let src: Vec<_> = data.iter().flatten().copied().collect();
 
-            let compressed_data = encode(&lens, &src)?;
+            let compressed_data = encode(&Options::default(), &lens, &[], &src)?;
 
             let mut reader = &compressed_data[..];
             let uncompressed_data = decode(&mut reader)?;
@@ -63,4 +69,56 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_self_with_options() -> io::Result<()> {
+        let data = [
+            vec![30, 30, 37, 37, 37, 11, 37, 37, 30, 2],
+            vec![37, 37, 37, 37, 30, 30, 30, 11, 11, 2],
+            vec![2, 11, 30, 37, 37, 37, 37, 37, 37, 37],
+            vec![37, 30, 30],
+        ];
+
+        let lens: Vec<_> = data.iter().map(|scores| scores.len()).collect();
+        let src: Vec<_> = data.iter().flatten().copied().collect();
+
+        let quality_table: Vec<_> = (0..=u8::MAX).map(|q| q / 10).collect();
+        let offset_quality_table: Vec<_> = (0..=u8::MAX).map(|q| q.max(20) / 2).collect();
+
+        let options = [
+            Options::default().use_symbol_map(true),
+            Options::default().set_quality_table(quality_table.clone()),
+            Options::default()
+                .use_symbol_map(true)
+                .set_quality_table(quality_table),
+            Options::default().set_quality_table(offset_quality_table),
+            Options::default().set_strategy(Strategy::Basic),
+            Options::default().set_strategy(Strategy::HiSeq2000),
+            Options::default().set_strategy(Strategy::MiSeq),
+            Options::default().set_strategy(Strategy::IonTorrent),
+            Options::from_level(9),
+        ];
+
+        for sels in [&[][..], &[0, 1, 0, 1]] {
+            for opts in &options {
+                let compressed_data = encode(opts, &lens, sels, &src)?;
+
+                let mut reader = &compressed_data[..];
+                let uncompressed_data = decode(&mut reader)?;
+
+                assert_eq!(uncompressed_data, src, "{opts:?} {sels:?}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_invalid_quality_table() {
+        let options = Options::default().set_quality_table(vec![1, 0]);
+        assert!(matches!(
+            encode(&options, &[2], &[], &[0, 1]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
 }
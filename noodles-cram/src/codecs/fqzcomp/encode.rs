@@ -1,43 +1,69 @@
-use std::io::{self, Write};
+use std::{
+    cmp,
+    io::{self, Write},
+};
 
 use byteorder::{LittleEndian, WriteBytesExt};
 
-use super::{parameter, parameters, Models};
+use super::{parameter, parameters, Models, Options, Strategy};
 use crate::{codecs::aac::RangeCoder, writer::num::write_uint7};
 
-pub fn encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
+/// Encodes quality scores.
+///
+/// `lens` are the read lengths of the records. `sels` are the record selectors and are either
+/// empty or have one value per record.
+pub fn encode(options: &Options, lens: &[usize], sels: &[u8], src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dst: Option<Vec<u8>> = None;
+
+    for &strategy in options.strategies() {
+        let buf = encode_with_strategy(options, strategy, lens, sels, src)?;
+
+        if dst.as_ref().map(|d| buf.len() < d.len()).unwrap_or(true) {
+            dst = Some(buf);
+        }
+    }
+
+    dst.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing fqzcomp strategy"))
+}
+
+fn encode_with_strategy(
+    options: &Options,
+    strategy: Strategy,
+    lens: &[usize],
+    sels: &[u8],
+    src: &[u8],
+) -> io::Result<Vec<u8>> {
     let mut dst = Vec::new();
 
     let len =
         u32::try_from(src.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
     write_uint7(&mut dst, len)?;
 
-    let mut q_hist = [[0; 256]];
-
-    for (i, f) in q_hist[0].iter_mut().enumerate() {
-        *f = i as u8;
-    }
-
-    let parameters = build_parameters(lens, src);
+    let parameters = build_parameters(options, strategy, lens, sels, src)?;
     fqz_encode_params(&mut dst, &parameters)?;
 
     let mut range_coder = RangeCoder::default();
-    let mut models = Models::new(parameters.max_sym, 1); // FIXME: max_sel
+    let mut models = Models::new(parameters.max_sym, parameters.max_sel);
 
     let mut p = 0;
     let mut rec_num = 0;
 
     let mut x = 0;
+    let mut sel = 0;
     let mut last = 0;
     let mut qlast: u32 = 0;
+    let mut delta: u32 = 0;
+    let mut prevq = 0;
 
     for &q in src {
         if p == 0 {
-            if parameters.gflags.contains(parameters::Flags::HAVE_S_TAB) {
-                todo!("have_s_tab");
+            sel = sels.get(rec_num).copied().unwrap_or(0);
+
+            if parameters.max_sel > 0 {
+                models.sel.encode(&mut dst, &mut range_coder, sel)?;
             }
 
-            x = parameters.s_tab[0];
+            x = parameters.s_tab[usize::from(sel)];
 
             let is_fixed_len = parameters.params[usize::from(x)]
                 .flags
@@ -58,11 +84,13 @@ pub fn encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
             p = len;
             last = u32::from(param.context);
             qlast = 0;
+            delta = 0;
+            prevq = 0;
 
             rec_num += 1;
         }
 
-        let qq = q_hist[usize::from(x)][usize::from(q)];
+        let qq = parameters.symbols[usize::from(q)];
         models.qual[usize::from(last as u16)].encode(&mut dst, &mut range_coder, qq)?;
 
         let param = &parameters.params[usize::from(x)];
@@ -74,15 +102,22 @@ pub fn encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
         last += (qlast & ((1 << param.q_bits) - 1)) << param.q_loc;
 
         if param.flags.contains(parameter::Flags::HAVE_PTAB) {
-            last += u32::from(param.p_tab[p.min(1023)] << param.p_loc);
+            last += u32::from(param.p_tab[p.min(1023)]) << param.p_loc;
         }
 
         if param.flags.contains(parameter::Flags::HAVE_DTAB) {
-            todo!("have_dtab");
+            let d = cmp::min(delta, 255) as usize;
+            last += u32::from(param.d_tab[d]) << param.d_loc;
+
+            if prevq != qq {
+                delta += 1;
+            }
+
+            prevq = qq;
         }
 
         if param.flags.contains(parameter::Flags::DO_SEL) {
-            todo!("do_sel");
+            last += u32::from(sel) << param.s_loc;
         }
 
         last &= 0xffff;
@@ -100,6 +135,8 @@ struct Parameters {
     pub s_tab: Vec<u8>,
     pub params: Vec<Parameter>,
     pub max_sym: u8,
+    // quality score to encoded symbol
+    pub symbols: Vec<u8>,
 }
 
 struct Parameter {
@@ -118,11 +155,19 @@ struct Parameter {
 
     pub d_loc: u8,
 
+    pub q_map: Vec<u8>,
     pub q_tab: Vec<u8>,
     pub p_tab: Vec<u8>,
+    pub d_tab: Vec<u8>,
 }
 
-fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
+fn build_parameters(
+    options: &Options,
+    strategy: Strategy,
+    lens: &[usize],
+    sels: &[u8],
+    src: &[u8],
+) -> io::Result<Parameters> {
     let mut max_symbol = u8::MIN;
     let mut symbol_counts = [0; 256];
 
@@ -132,37 +177,109 @@ fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
         max_symbol = max_symbol.max(b)
     }
 
-    let q_shift = 5;
-    let q_bits = if q_shift > 4 { 9 } else { 8 };
-    let p_bits = 7;
-    let p_shift = i32::from(lens[0] > 128);
+    let max_len = lens.iter().copied().max().unwrap_or(0);
+    let model = strategy.context_model(max_len);
+
+    let mut flags = parameter::Flags::empty();
 
-    let q_tab: Vec<_> = (0..=u8::MAX).collect();
+    let mut symbols: Vec<_> = (0..=u8::MAX).collect();
+    let mut q_map = Vec::new();
+    let mut max_sym = max_symbol;
 
-    let mut p_tab = vec![0; 1024];
+    if options.uses_symbol_map() {
+        let used_symbols: Vec<_> = (0..=u8::MAX)
+            .filter(|&q| symbol_counts[usize::from(q)] > 0)
+            .collect();
 
-    for (i, p) in p_tab.iter_mut().enumerate() {
-        *p = ((1 << p_bits) - 1).min(i >> p_shift) as u8;
+        let is_identity = used_symbols
+            .iter()
+            .enumerate()
+            .all(|(i, &q)| usize::from(q) == i);
+
+        if !is_identity && used_symbols.len() <= usize::from(u8::MAX) {
+            for (i, &q) in used_symbols.iter().enumerate() {
+                symbols[usize::from(q)] = i as u8;
+            }
+
+            max_sym = used_symbols.len() as u8;
+            q_map = used_symbols;
+            flags |= parameter::Flags::HAVE_QMAP;
+        }
     }
 
-    let mut flags = parameter::Flags::HAVE_PTAB;
+    let q_tab = match options.quality_table() {
+        Some(table) => {
+            if table.len() != 256 || table.windows(2).any(|w| w[0] > w[1]) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid fqzcomp quality table",
+                ));
+            }
+
+            flags |= parameter::Flags::HAVE_QTAB;
+
+            let mut q_tab = if q_map.is_empty() {
+                table.to_vec()
+            } else {
+                // The table is indexed by the encoded symbol.
+                let mut q_tab: Vec<_> = q_map.iter().map(|&q| table[usize::from(q)]).collect();
+                let last = q_tab.last().copied().unwrap_or(0);
+                q_tab.resize(256, last);
+                q_tab
+            };
+
+            // Encoded arrays must start at 0.
+            let offset = q_tab[0];
+
+            for n in &mut q_tab {
+                *n -= offset;
+            }
+
+            q_tab
+        }
+        None => (0..=u8::MAX).collect(),
+    };
+
+    let p_tab = if model.p_bits > 0 {
+        flags |= parameter::Flags::HAVE_PTAB;
+        build_table(1024, model.p_bits, model.p_shift)
+    } else {
+        Vec::new()
+    };
+
+    let d_tab = if model.d_bits > 0 {
+        flags |= parameter::Flags::HAVE_DTAB;
+        build_table(256, model.d_bits, model.d_shift)
+    } else {
+        Vec::new()
+    };
 
     if lens.windows(2).all(|w| w[0] == w[1]) {
         flags |= parameter::Flags::DO_LEN;
     }
 
+    let mut gflags = parameters::Flags::empty();
+    let max_sel = sels.iter().copied().max().unwrap_or(0);
+
+    if max_sel > 0 {
+        gflags |= parameters::Flags::HAVE_S_TAB;
+        flags |= parameter::Flags::DO_SEL;
+    }
+
     let params = vec![Parameter {
         context: 0,
         flags,
-        max_sym: max_symbol,
-        q_bits,
-        q_shift,
-        q_loc: 7,
-        s_loc: 15,
-        p_loc: 0,
-        d_loc: 15,
+        max_sym,
+        q_bits: model.q_bits,
+        q_shift: model.q_shift,
+        q_loc: model.q_loc,
+        s_loc: model.s_loc,
+        p_loc: model.p_loc,
+        d_loc: model.d_loc,
+        q_map,
         q_tab,
         p_tab,
+        d_tab,
     }];
 
     let last_i = (params.len() - 1) as u8;
@@ -172,13 +289,19 @@ fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
         *s = i as u8;
     }
 
-    Parameters {
-        gflags: parameters::Flags::empty(),
-        max_sel: 0,
+    Ok(Parameters {
+        gflags,
+        max_sel,
         s_tab,
         params,
-        max_sym: max_symbol,
-    }
+        max_sym,
+        symbols,
+    })
+}
+
+fn build_table(len: usize, bits: u8, shift: u8) -> Vec<u8> {
+    let max = (1 << bits) - 1;
+    (0..len).map(|i| max.min(i >> shift) as u8).collect()
 }
 
 fn fqz_encode_params<W>(writer: &mut W, parameters: &Parameters) -> io::Result<()>
@@ -228,11 +351,11 @@ where
     writer.write_u8((parameter.p_loc << 4) | parameter.d_loc)?;
 
     if parameter.flags.contains(parameter::Flags::HAVE_QMAP) {
-        todo!("have_qmap");
+        writer.write_all(&parameter.q_map)?;
     }
 
     if parameter.flags.contains(parameter::Flags::HAVE_QTAB) {
-        todo!("have_qtab");
+        write_array(writer, &parameter.q_tab)?;
     }
 
     if parameter.flags.contains(parameter::Flags::HAVE_PTAB) {
@@ -240,7 +363,7 @@ where
     }
 
     if parameter.flags.contains(parameter::Flags::HAVE_DTAB) {
-        todo!("have_dtab");
+        write_array(writer, &parameter.d_tab)?;
     }
 
     Ok(())
@@ -342,7 +465,7 @@ mod tests {
         let lens: Vec<_> = data.iter().map(|scores| scores.len()).collect();
         let src: Vec<_> = data.into_iter().flatten().collect();
 
-        let actual = encode(&lens, &src)?;
+        let actual = encode(&Options::default(), &lens, &[], &src)?;
 
         let expected = [
             0x19, 0x05, 0x00, 0x00, 0x00, 0x20, 0x03, 0x95, 0x7f, 0x0f, 0x01, 0x01, 0x7d, 0xff,
@@ -366,7 +489,7 @@ mod tests {
         let lens: Vec<_> = data.iter().map(|scores| scores.len()).collect();
         let src: Vec<_> = data.into_iter().flatten().collect();
 
-        let actual = encode(&lens, &src)?;
+        let actual = encode(&Options::default(), &lens, &[], &src)?;
 
         let expected = [
             0x1e, 0x05, 0x00, 0x00, 0x00, 0x24, 0x03, 0x95, 0x7f, 0x0f, 0x01, 0x01, 0x7d, 0xff,
@@ -0,0 +1,173 @@
+use super::{Selector, Strategy};
+
+/// An fqzcomp encoder configuration.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Options {
+    strategies: Vec<Strategy>,
+    uses_symbol_map: bool,
+    quality_table: Option<Vec<u8>>,
+    selector: Selector,
+}
+
+impl Options {
+    /// Creates an fqzcomp encoder configuration from a compression level.
+    ///
+    /// Levels 0 to 3 use the default configuration. Levels 4 to 6 use the basic strategy with a
+    /// symbol map. Levels 7 and above try every strategy and keep the smallest output, use a
+    /// symbol map, and select records by read number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::{Options, Strategy};
+    /// let options = Options::from_level(5);
+    /// assert_eq!(options.strategies(), [Strategy::Basic]);
+    /// assert!(options.uses_symbol_map());
+    /// ```
+    pub fn from_level(level: u8) -> Self {
+        match level {
+            0..=3 => Self::default(),
+            4..=6 => Self::default()
+                .set_strategy(Strategy::Basic)
+                .use_symbol_map(true),
+            _ => Self {
+                strategies: Strategy::ALL.to_vec(),
+                uses_symbol_map: true,
+                quality_table: None,
+                selector: Selector::ReadNumber,
+            },
+        }
+    }
+
+    /// Returns the strategies that are tried.
+    ///
+    /// When there is more than one strategy, the smallest output is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::{Options, Strategy};
+    /// let options = Options::default();
+    /// assert_eq!(options.strategies(), [Strategy::General]);
+    /// ```
+    pub fn strategies(&self) -> &[Strategy] {
+        &self.strategies
+    }
+
+    /// Sets the strategy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::{Options, Strategy};
+    /// let options = Options::default().set_strategy(Strategy::MiSeq);
+    /// assert_eq!(options.strategies(), [Strategy::MiSeq]);
+    /// ```
+    pub fn set_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategies = vec![strategy];
+        self
+    }
+
+    /// Returns whether quality scores are mapped to a dense symbol alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Options;
+    /// let options = Options::default();
+    /// assert!(!options.uses_symbol_map());
+    /// ```
+    pub fn uses_symbol_map(&self) -> bool {
+        self.uses_symbol_map
+    }
+
+    /// Sets whether quality scores are mapped to a dense symbol alphabet.
+    ///
+    /// This is lossless and typically reduces the size of binned quality scores, e.g., those
+    /// with only a few distinct values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Options;
+    /// let options = Options::default().use_symbol_map(true);
+    /// assert!(options.uses_symbol_map());
+    /// ```
+    pub fn use_symbol_map(mut self, value: bool) -> Self {
+        self.uses_symbol_map = value;
+        self
+    }
+
+    /// Returns the quality score context table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Options;
+    /// let options = Options::default();
+    /// assert!(options.quality_table().is_none());
+    /// ```
+    pub fn quality_table(&self) -> Option<&[u8]> {
+        self.quality_table.as_deref()
+    }
+
+    /// Sets the quality score context table.
+    ///
+    /// This bins quality scores when they are used as context for following quality scores. The
+    /// quality scores themselves are stored losslessly. The table is indexed by quality score and
+    /// must have 256 non-decreasing values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Options;
+    ///
+    /// // Bins quality scores in groups of 4.
+    /// let table: Vec<_> = (0..=255).map(|q: u8| q / 4).collect();
+    /// let options = Options::default().set_quality_table(table.clone());
+    ///
+    /// assert_eq!(options.quality_table(), Some(&table[..]));
+    /// ```
+    pub fn set_quality_table(mut self, quality_table: Vec<u8>) -> Self {
+        self.quality_table = Some(quality_table);
+        self
+    }
+
+    /// Returns the record selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::{Options, Selector};
+    /// let options = Options::default();
+    /// assert_eq!(options.selector(), Selector::None);
+    /// ```
+    pub fn selector(&self) -> Selector {
+        self.selector
+    }
+
+    /// Sets the record selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::{Options, Selector};
+    /// let options = Options::default().set_selector(Selector::ReadGroup);
+    /// assert_eq!(options.selector(), Selector::ReadGroup);
+    /// ```
+    pub fn set_selector(mut self, selector: Selector) -> Self {
+        self.selector = selector;
+        self
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            strategies: vec![Strategy::General],
+            uses_symbol_map: false,
+            quality_table: None,
+            selector: Selector::None,
+        }
+    }
+}
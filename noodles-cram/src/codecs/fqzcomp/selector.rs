@@ -0,0 +1,13 @@
+/// An fqzcomp record selector.
+///
+/// A selector splits records into groups that are modeled with separate quality score contexts.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Selector {
+    /// All records share the same contexts.
+    #[default]
+    None,
+    /// Records are grouped by whether they are the last segment of a template (e.g., read 2).
+    ReadNumber,
+    /// Records are grouped by read group.
+    ReadGroup,
+}
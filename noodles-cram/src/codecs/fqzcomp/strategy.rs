@@ -0,0 +1,131 @@
+/// An fqzcomp context model strategy.
+///
+/// Each strategy is a preset of how quality score contexts are built from the previous quality
+/// scores, the position in the read, and the number of quality score changes in the read.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Strategy {
+    /// Previous quality scores and position.
+    #[default]
+    General,
+    /// Previous quality scores, position, and quality score changes.
+    Basic,
+    /// Tuned for older Illumina (e.g., HiSeq 2000) quality scores.
+    HiSeq2000,
+    /// Tuned for Illumina MiSeq quality scores.
+    MiSeq,
+    /// Previous quality scores only, e.g., for Ion Torrent quality scores.
+    IonTorrent,
+}
+
+impl Strategy {
+    pub(crate) const ALL: [Self; 5] = [
+        Self::General,
+        Self::Basic,
+        Self::HiSeq2000,
+        Self::MiSeq,
+        Self::IonTorrent,
+    ];
+
+    pub(crate) fn context_model(self, max_len: usize) -> ContextModel {
+        match self {
+            Self::General => ContextModel {
+                q_bits: 9,
+                q_shift: 5,
+                p_bits: 7,
+                p_shift: u8::from(max_len > 128),
+                d_bits: 0,
+                d_shift: 0,
+                q_loc: 7,
+                s_loc: 15,
+                p_loc: 0,
+                d_loc: 15,
+            },
+            Self::Basic => ContextModel {
+                q_bits: 10,
+                q_shift: 5,
+                p_bits: 4,
+                p_shift: auto_p_shift(max_len, 4),
+                d_bits: 2,
+                d_shift: 1,
+                q_loc: 0,
+                s_loc: 14,
+                p_loc: 10,
+                d_loc: 14,
+            },
+            Self::HiSeq2000 => ContextModel {
+                q_bits: 8,
+                q_shift: 5,
+                p_bits: 7,
+                p_shift: 0,
+                d_bits: 0,
+                d_shift: 0,
+                q_loc: 0,
+                s_loc: 15,
+                p_loc: 8,
+                d_loc: 15,
+            },
+            Self::MiSeq => ContextModel {
+                q_bits: 12,
+                q_shift: 6,
+                p_bits: 2,
+                p_shift: 0,
+                d_bits: 2,
+                d_shift: 3,
+                q_loc: 0,
+                s_loc: 9,
+                p_loc: 12,
+                d_loc: 14,
+            },
+            Self::IonTorrent => ContextModel {
+                q_bits: 12,
+                q_shift: 6,
+                p_bits: 0,
+                p_shift: 0,
+                d_bits: 0,
+                d_shift: 0,
+                q_loc: 0,
+                s_loc: 12,
+                p_loc: 0,
+                d_loc: 0,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct ContextModel {
+    pub q_bits: u8,
+    pub q_shift: u8,
+    pub p_bits: u8,
+    pub p_shift: u8,
+    pub d_bits: u8,
+    pub d_shift: u8,
+    pub q_loc: u8,
+    pub s_loc: u8,
+    pub p_loc: u8,
+    pub d_loc: u8,
+}
+
+// Scales positions so that the longest read spans the position table.
+fn auto_p_shift(max_len: usize, p_bits: u8) -> u8 {
+    let n = max_len >> p_bits;
+
+    if n == 0 {
+        0
+    } else {
+        (usize::BITS - n.leading_zeros()) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_p_shift() {
+        assert_eq!(auto_p_shift(10, 4), 0);
+        assert_eq!(auto_p_shift(16, 4), 1);
+        assert_eq!(auto_p_shift(151, 4), 4);
+        assert_eq!(auto_p_shift(256, 4), 5);
+    }
+}
@@ -1,7 +1,11 @@
+//! Name tokenizer codec.
+
 mod decode;
 mod encode;
+mod options;
 
-pub use self::{decode::decode, encode::encode};
+pub use self::options::Options;
+pub(crate) use self::{decode::decode, encode::encode};
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -84,12 +88,19 @@ I17_08765:2:123:1636:08611#9\0\
 I17_08765:2:124:45613:16161#9\0\
 ";
 
-        let input = encode(data)?;
+        for options in [
+            Options::default(),
+            Options::new(5),
+            Options::new(9),
+            Options::new(9).use_arithmetic_coder(true),
+        ] {
+            let input = encode(options, data)?;
 
-        let mut reader = &input[..];
-        let output = decode(&mut reader)?;
+            let mut reader = &input[..];
+            let output = decode(&mut reader)?;
 
-        assert_eq!(output, data);
+            assert_eq!(output, data);
+        }
 
         Ok(())
     }
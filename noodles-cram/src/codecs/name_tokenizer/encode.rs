@@ -6,12 +6,12 @@ use std::{
 
 use byteorder::{LittleEndian, WriteBytesExt};
 
-use super::Type;
+use super::{Options, Type};
 use crate::writer::num::write_uint7;
 
 const NUL: u8 = 0x00;
 
-pub fn encode(mut src: &[u8]) -> io::Result<Vec<u8>> {
+pub fn encode(options: Options, mut src: &[u8]) -> io::Result<Vec<u8>> {
     let mut dst = Vec::new();
 
     if let Some(buf) = src.strip_suffix(&[NUL]) {
@@ -24,7 +24,12 @@ pub fn encode(mut src: &[u8]) -> io::Result<Vec<u8>> {
         .collect::<Result<_, _>>()
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
-    write_header(&mut dst, src.len(), names.len())?;
+    write_header(
+        &mut dst,
+        src.len(),
+        names.len(),
+        options.uses_arithmetic_coder(),
+    )?;
 
     let mut names_indices = HashMap::new();
     let mut diffs = Vec::with_capacity(names.len());
@@ -54,7 +59,7 @@ pub fn encode(mut src: &[u8]) -> io::Result<Vec<u8>> {
         token_writer.write_token(&token)?;
     }
 
-    encode_token_byte_streams(&mut dst, &token_writer)?;
+    encode_token_byte_streams(&mut dst, options, &token_writer)?;
 
     for i in 0..max_token_count {
         let mut token_writer = TokenWriter::default();
@@ -69,13 +74,18 @@ pub fn encode(mut src: &[u8]) -> io::Result<Vec<u8>> {
             }
         }
 
-        encode_token_byte_streams(&mut dst, &token_writer)?;
+        encode_token_byte_streams(&mut dst, options, &token_writer)?;
     }
 
     Ok(dst)
 }
 
-fn write_header<W>(writer: &mut W, src_len: usize, names_count: usize) -> io::Result<()>
+fn write_header<W>(
+    writer: &mut W,
+    src_len: usize,
+    names_count: usize,
+    use_arith: bool,
+) -> io::Result<()>
 where
     W: Write,
 {
@@ -87,8 +97,7 @@ where
         u32::try_from(names_count).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
     writer.write_u32::<LittleEndian>(n_names)?;
 
-    // TODO: use_arith
-    writer.write_u8(0)?;
+    writer.write_u8(u8::from(use_arith))?;
 
     Ok(())
 }
@@ -398,30 +407,37 @@ where
     writer.write_u8(n)
 }
 
-fn encode_token_byte_streams<W>(writer: &mut W, token_writer: &TokenWriter) -> io::Result<()>
+fn encode_token_byte_streams<W>(
+    writer: &mut W,
+    options: Options,
+    token_writer: &TokenWriter,
+) -> io::Result<()>
 where
     W: Write,
 {
-    encode_token_byte_stream(writer, Type::Type, &token_writer.type_writer)?;
-    encode_token_byte_stream(writer, Type::String, &token_writer.string_writer)?;
-    encode_token_byte_stream(writer, Type::Char, &token_writer.char_writer)?;
-    encode_token_byte_stream(writer, Type::Digits0, &token_writer.digits0_writer)?;
-    encode_token_byte_stream(writer, Type::DZLen, &token_writer.dz_len_writer)?;
-    encode_token_byte_stream(writer, Type::Dup, &token_writer.dup_writer)?;
-    encode_token_byte_stream(writer, Type::Diff, &token_writer.diff_writer)?;
-    encode_token_byte_stream(writer, Type::Digits, &token_writer.digits_writer)?;
-    encode_token_byte_stream(writer, Type::Delta, &token_writer.delta_writer)?;
-    encode_token_byte_stream(writer, Type::Delta0, &token_writer.delta0_writer)?;
+    encode_token_byte_stream(writer, options, Type::Type, &token_writer.type_writer)?;
+    encode_token_byte_stream(writer, options, Type::String, &token_writer.string_writer)?;
+    encode_token_byte_stream(writer, options, Type::Char, &token_writer.char_writer)?;
+    encode_token_byte_stream(writer, options, Type::Digits0, &token_writer.digits0_writer)?;
+    encode_token_byte_stream(writer, options, Type::DZLen, &token_writer.dz_len_writer)?;
+    encode_token_byte_stream(writer, options, Type::Dup, &token_writer.dup_writer)?;
+    encode_token_byte_stream(writer, options, Type::Diff, &token_writer.diff_writer)?;
+    encode_token_byte_stream(writer, options, Type::Digits, &token_writer.digits_writer)?;
+    encode_token_byte_stream(writer, options, Type::Delta, &token_writer.delta_writer)?;
+    encode_token_byte_stream(writer, options, Type::Delta0, &token_writer.delta0_writer)?;
 
     Ok(())
 }
 
-fn encode_token_byte_stream<W>(writer: &mut W, ty: Type, buf: &[u8]) -> io::Result<()>
+fn encode_token_byte_stream<W>(
+    writer: &mut W,
+    options: Options,
+    ty: Type,
+    buf: &[u8],
+) -> io::Result<()>
 where
     W: Write,
 {
-    use crate::codecs::rans_nx16::{self, Flags};
-
     if buf.is_empty() {
         return Ok(());
     }
@@ -432,7 +448,7 @@ where
         write_type(writer, ty)?;
     }
 
-    let cdata = rans_nx16::encode(Flags::empty(), buf)?;
+    let cdata = compress(options, buf)?;
 
     let clen =
         u32::try_from(cdata.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
@@ -442,3 +458,61 @@ where
 
     Ok(())
 }
+
+fn compress(options: Options, buf: &[u8]) -> io::Result<Vec<u8>> {
+    use crate::codecs::{aac, rans_nx16};
+
+    if options.uses_arithmetic_coder() {
+        use aac::Flags;
+
+        let candidates = match options.level() {
+            0..=2 => vec![Flags::empty()],
+            3..=5 => vec![Flags::empty(), Flags::ORDER],
+            _ => vec![
+                Flags::empty(),
+                Flags::ORDER,
+                Flags::RLE,
+                Flags::ORDER | Flags::RLE,
+                Flags::PACK,
+                Flags::ORDER | Flags::PACK,
+            ],
+        };
+
+        compress_smallest(&candidates, |flags| aac::encode(flags, buf))
+    } else {
+        use rans_nx16::Flags;
+
+        let candidates = match options.level() {
+            0..=2 => vec![Flags::empty()],
+            3..=5 => vec![Flags::empty(), Flags::ORDER],
+            _ => vec![
+                Flags::empty(),
+                Flags::ORDER,
+                Flags::RLE,
+                Flags::ORDER | Flags::RLE,
+                Flags::PACK,
+                Flags::ORDER | Flags::PACK,
+            ],
+        };
+
+        compress_smallest(&candidates, |flags| rans_nx16::encode(flags, buf))
+    }
+}
+
+fn compress_smallest<T, F>(candidates: &[T], mut f: F) -> io::Result<Vec<u8>>
+where
+    T: Copy,
+    F: FnMut(T) -> io::Result<Vec<u8>>,
+{
+    let mut dst: Option<Vec<u8>> = None;
+
+    for &flags in candidates {
+        let cdata = f(flags)?;
+
+        if dst.as_ref().map(|d| cdata.len() < d.len()).unwrap_or(true) {
+            dst = Some(cdata);
+        }
+    }
+
+    Ok(dst.unwrap_or_default())
+}
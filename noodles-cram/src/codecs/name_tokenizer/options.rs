@@ -0,0 +1,79 @@
+const MIN_LEVEL: u8 = 1;
+const MAX_LEVEL: u8 = 9;
+
+/// A name tokenizer encoder configuration.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Options {
+    level: u8,
+    uses_arithmetic_coder: bool,
+}
+
+impl Options {
+    /// Creates a name tokenizer encoder configuration with the given compression level.
+    ///
+    /// The level controls how many entropy coder configurations are tried for each token stream.
+    /// Levels 1 and 2 only use order-0 coding; levels 3 to 5 also try order-1 coding; and levels 6
+    /// and above also try run-length encoding and bit packing. The level is clamped to [1, 9].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::name_tokenizer::Options;
+    /// let options = Options::new(9);
+    /// assert_eq!(options.level(), 9);
+    /// ```
+    pub fn new(level: u8) -> Self {
+        Self {
+            level: level.clamp(MIN_LEVEL, MAX_LEVEL),
+            uses_arithmetic_coder: false,
+        }
+    }
+
+    /// Returns the compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::name_tokenizer::Options;
+    /// let options = Options::default();
+    /// assert_eq!(options.level(), 1);
+    /// ```
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Returns whether token streams are compressed using the adaptive arithmetic coder rather
+    /// than rANS Nx16.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::name_tokenizer::Options;
+    /// let options = Options::default();
+    /// assert!(!options.uses_arithmetic_coder());
+    /// ```
+    pub fn uses_arithmetic_coder(&self) -> bool {
+        self.uses_arithmetic_coder
+    }
+
+    /// Sets whether token streams are compressed using the adaptive arithmetic coder rather than
+    /// rANS Nx16.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::name_tokenizer::Options;
+    /// let options = Options::new(9).use_arithmetic_coder(true);
+    /// assert!(options.uses_arithmetic_coder());
+    /// ```
+    pub fn use_arithmetic_coder(mut self, value: bool) -> Self {
+        self.uses_arithmetic_coder = value;
+        self
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::new(MIN_LEVEL)
+    }
+}
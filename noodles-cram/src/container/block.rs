@@ -8,6 +8,8 @@ pub use self::{
     content_type::ContentType,
 };
 
+pub(crate) use self::builder::encode;
+
 use std::{io, mem};
 
 use bytes::Bytes;
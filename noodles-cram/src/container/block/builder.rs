@@ -45,40 +45,9 @@ impl Builder {
     /// This sets the compression method, the uncompressed size to the length of the given data,
     /// and the data to the compressed output of the given data.
     pub fn compress_and_set_data(mut self, data: Vec<u8>, encoder: Encoder) -> io::Result<Self> {
-        use crate::codecs::{aac, bzip2, gzip, lzma, name_tokenizer, rans_4x8, rans_nx16};
-
         self.uncompressed_len = data.len();
 
-        let (compression_method, data) = match encoder {
-            Encoder::Gzip(compression_level) => (
-                CompressionMethod::Gzip,
-                gzip::encode(compression_level, &data)?,
-            ),
-            Encoder::Bzip2(compression_level) => (
-                CompressionMethod::Bzip2,
-                bzip2::encode(compression_level, &data)?,
-            ),
-            Encoder::Lzma(compression_level) => (
-                CompressionMethod::Lzma,
-                lzma::encode(compression_level, &data)?,
-            ),
-            Encoder::Rans4x8(order) => {
-                (CompressionMethod::Rans4x8, rans_4x8::encode(order, &data)?)
-            }
-            Encoder::RansNx16(flags) => (
-                CompressionMethod::RansNx16,
-                rans_nx16::encode(flags, &data)?,
-            ),
-            Encoder::AdaptiveArithmeticCoding(flags) => (
-                CompressionMethod::AdaptiveArithmeticCoding,
-                aac::encode(flags, &data)?,
-            ),
-            Encoder::NameTokenizer => (
-                CompressionMethod::NameTokenizer,
-                name_tokenizer::encode(&data)?,
-            ),
-            Encoder::Fqzcomp => unimplemented!(),
-        };
+        let (compression_method, data) = encode(&encoder, &data)?;
 
         self.compression_method = compression_method;
         self.data = Bytes::from(data);
@@ -96,3 +65,38 @@ impl Builder {
         }
     }
 }
+
+pub(crate) fn encode(encoder: &Encoder, src: &[u8]) -> io::Result<(CompressionMethod, Vec<u8>)> {
+    use crate::codecs::{aac, bzip2, gzip, lzma, name_tokenizer, rans_4x8, rans_nx16};
+
+    match *encoder {
+        Encoder::Gzip(compression_level) => Ok((
+            CompressionMethod::Gzip,
+            gzip::encode(compression_level, src)?,
+        )),
+        Encoder::Bzip2(compression_level) => Ok((
+            CompressionMethod::Bzip2,
+            bzip2::encode(compression_level, src)?,
+        )),
+        Encoder::Lzma(compression_level) => Ok((
+            CompressionMethod::Lzma,
+            lzma::encode(compression_level, src)?,
+        )),
+        Encoder::Rans4x8(order) => Ok((CompressionMethod::Rans4x8, rans_4x8::encode(order, src)?)),
+        Encoder::RansNx16(flags) => {
+            Ok((CompressionMethod::RansNx16, rans_nx16::encode(flags, src)?))
+        }
+        Encoder::AdaptiveArithmeticCoding(flags) => Ok((
+            CompressionMethod::AdaptiveArithmeticCoding,
+            aac::encode(flags, src)?,
+        )),
+        Encoder::NameTokenizer(options) => Ok((
+            CompressionMethod::NameTokenizer,
+            name_tokenizer::encode(options, src)?,
+        )),
+        Encoder::Fqzcomp(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "fqzcomp requires read lengths",
+        )),
+    }
+}
@@ -3,10 +3,11 @@ use std::{io, mem, num};
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
-use super::{slice, CompressionHeader, DataContainer, Slice};
-use crate::{writer::Options, Record};
-
-const MAX_SLICE_COUNT: usize = 1;
+use super::{slice, CompressionHeader, DataContainer, ReferenceSequenceContext, Slice};
+use crate::{
+    writer::{Options, Selector},
+    Record,
+};
 
 #[derive(Debug)]
 pub struct Builder {
@@ -42,12 +43,23 @@ impl Builder {
     }
 
     #[allow(clippy::result_large_err)]
-    pub fn add_record(&mut self, record: Record) -> Result<(), AddRecordError> {
-        if self.slice_builders.len() >= MAX_SLICE_COUNT {
+    pub fn add_record(&mut self, options: &Options, record: Record) -> Result<(), AddRecordError> {
+        if self.slice_builders.len() >= options.slices_per_container.get() {
+            return Err(AddRecordError::ContainerFull(record));
+        }
+
+        if !options.multi_reference_slices
+            && self.slice_builder.is_empty()
+            && self
+                .slice_builders
+                .last()
+                .map(|b| is_reference_sequence_changed(b.reference_sequence_context(), &record))
+                .unwrap_or(false)
+        {
             return Err(AddRecordError::ContainerFull(record));
         }
 
-        match self.slice_builder.add_record(record) {
+        match self.slice_builder.add_record(options, record) {
             Ok(r) => {
                 self.base_count += u64::try_from(r.read_length())
                     .map_err(AddRecordError::InvalidRecordReadLength)?;
@@ -59,6 +71,13 @@ impl Builder {
                     self.slice_builders.push(slice_builder);
                     Err(AddRecordError::SliceFull(r))
                 }
+                // Without multi-reference slices, a change in reference sequence also ends the
+                // container so that its slices share a single reference sequence context.
+                slice::builder::AddRecordError::ReferenceSequenceChanged(r) => {
+                    let slice_builder = mem::take(&mut self.slice_builder);
+                    self.slice_builders.push(slice_builder);
+                    Err(AddRecordError::ContainerFull(r))
+                }
             },
         }
     }
@@ -68,6 +87,7 @@ impl Builder {
         options: &Options,
         reference_sequence_repository: &fasta::Repository,
         header: &sam::Header,
+        mut block_content_encoder_selector: Option<&mut Selector>,
     ) -> io::Result<DataContainer> {
         if !self.slice_builder.is_empty() {
             self.slice_builders.push(self.slice_builder);
@@ -86,19 +106,20 @@ impl Builder {
         let compression_header = build_compression_header(&options, &self.slice_builders);
 
         let record_counter = self.record_counter;
-        let slices = self
-            .slice_builders
-            .into_iter()
-            .map(|builder| {
-                builder.build(
-                    &options.block_content_encoder_map,
-                    reference_sequence_repository,
-                    header,
-                    &compression_header,
-                    record_counter,
-                )
-            })
-            .collect::<Result<_, _>>()?;
+        let mut slices = Vec::with_capacity(self.slice_builders.len());
+
+        for builder in self.slice_builders {
+            let slice = builder.build(
+                &options,
+                reference_sequence_repository,
+                header,
+                &compression_header,
+                record_counter,
+                block_content_encoder_selector.as_deref_mut(),
+            )?;
+
+            slices.push(slice);
+        }
 
         Ok(DataContainer {
             compression_header,
@@ -107,6 +128,19 @@ impl Builder {
     }
 }
 
+pub(crate) fn is_reference_sequence_changed(
+    mut reference_sequence_context: ReferenceSequenceContext,
+    record: &Record,
+) -> bool {
+    reference_sequence_context.update(
+        record.reference_sequence_id(),
+        record.alignment_start(),
+        record.alignment_end(),
+    );
+
+    reference_sequence_context.is_many()
+}
+
 fn build_compression_header(
     options: &Options,
     slice_builders: &[slice::Builder],
@@ -122,3 +156,80 @@ fn build_compression_header(
 
     compression_header_builder.build()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_core::Position;
+
+    use super::*;
+
+    fn build_record(reference_sequence_id: usize) -> Result<Record, Box<dyn std::error::Error>> {
+        Ok(Record::builder()
+            .set_reference_sequence_id(reference_sequence_id)
+            .set_read_length(4)
+            .set_alignment_start(Position::try_from(8)?)
+            .build())
+    }
+
+    #[test]
+    fn test_add_record() -> Result<(), Box<dyn std::error::Error>> {
+        let options = Options {
+            records_per_slice: NonZeroUsize::try_from(2)?,
+            slices_per_container: NonZeroUsize::try_from(2)?,
+            ..Default::default()
+        };
+
+        let mut builder = Builder::new(0);
+        assert!(builder.add_record(&options, build_record(0)?).is_ok());
+        assert!(builder.add_record(&options, build_record(1)?).is_ok());
+        assert!(matches!(
+            builder.add_record(&options, build_record(0)?),
+            Err(AddRecordError::SliceFull(_))
+        ));
+        assert!(builder.add_record(&options, build_record(0)?).is_ok());
+        assert!(builder.add_record(&options, build_record(0)?).is_ok());
+        assert!(matches!(
+            builder.add_record(&options, build_record(0)?),
+            Err(AddRecordError::SliceFull(_))
+        ));
+        assert!(matches!(
+            builder.add_record(&options, build_record(0)?),
+            Err(AddRecordError::ContainerFull(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_record_without_multi_reference_slices() -> Result<(), Box<dyn std::error::Error>> {
+        let options = Options {
+            records_per_slice: NonZeroUsize::try_from(2)?,
+            slices_per_container: NonZeroUsize::try_from(2)?,
+            multi_reference_slices: false,
+            ..Default::default()
+        };
+
+        let mut builder = Builder::new(0);
+        assert!(builder.add_record(&options, build_record(0)?).is_ok());
+        assert!(matches!(
+            builder.add_record(&options, build_record(1)?),
+            Err(AddRecordError::ContainerFull(_))
+        ));
+
+        let mut builder = Builder::new(0);
+        assert!(builder.add_record(&options, build_record(0)?).is_ok());
+        assert!(builder.add_record(&options, build_record(0)?).is_ok());
+        assert!(matches!(
+            builder.add_record(&options, build_record(1)?),
+            Err(AddRecordError::SliceFull(_))
+        ));
+        assert!(matches!(
+            builder.add_record(&options, build_record(1)?),
+            Err(AddRecordError::ContainerFull(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ pub(crate) mod header;
 
 pub use self::{builder::Builder, header::Header};
 
-use std::io;
+use std::{collections::HashSet, io};
 
 use noodles_core::Position;
 use noodles_fasta as fasta;
@@ -12,6 +12,7 @@ use noodles_sam as sam;
 use super::{CompressionHeader, ReferenceSequenceContext};
 use crate::{
     container::Block,
+    reader::ReferenceSequenceError,
     record::resolve::{resolve_bases, resolve_quality_scores},
     BitReader, Record,
 };
@@ -119,6 +120,25 @@ impl Slice {
         header: &sam::Header,
         compression_header: &CompressionHeader,
         records: &mut [Record],
+    ) -> io::Result<()> {
+        self.resolve_records_inner(
+            reference_sequence_repository,
+            header,
+            compression_header,
+            records,
+            None,
+        )
+    }
+
+    // In strict mode, `verified_reference_sequence_ids` holds the IDs of the reference sequences
+    // whose `M5` checksums were already verified.
+    pub(crate) fn resolve_records_inner(
+        &self,
+        reference_sequence_repository: &fasta::Repository,
+        header: &sam::Header,
+        compression_header: &CompressionHeader,
+        records: &mut [Record],
+        verified_reference_sequence_ids: Option<&mut HashSet<usize>>,
     ) -> io::Result<()> {
         resolve_mates(records)?;
 
@@ -127,6 +147,7 @@ impl Slice {
             header,
             compression_header,
             records,
+            verified_reference_sequence_ids,
         )?;
 
         self.resolve_quality_scores(records);
@@ -140,6 +161,7 @@ impl Slice {
         header: &sam::Header,
         compression_header: &CompressionHeader,
         records: &mut [Record],
+        mut verified_reference_sequence_ids: Option<&mut HashSet<usize>>,
     ) -> io::Result<()> {
         let embedded_reference_sequence = if let Some(block_content_id) =
             self.header().embedded_reference_bases_block_content_id()
@@ -168,31 +190,32 @@ impl Slice {
             if let ReferenceSequenceContext::Some(context) =
                 self.header().reference_sequence_context()
             {
-                let reference_sequence_name = header
+                let (reference_sequence_name, reference_sequence) = header
                     .reference_sequences()
                     .get_index(context.reference_sequence_id())
-                    .map(|(name, _)| name)
                     .expect("invalid slice reference sequence ID");
 
-                let sequence = reference_sequence_repository
-                    .get(reference_sequence_name)
-                    .transpose()?
-                    .expect("invalid slice reference sequence name");
+                let sequence =
+                    get_reference_sequence(reference_sequence_repository, reference_sequence_name)?;
 
                 let start = context.alignment_start();
                 let end = context.alignment_end();
 
-                let actual_md5 =
-                    builder::calculate_normalized_sequence_digest(&sequence[start..=end]);
                 let expected_md5 = self.header().reference_md5();
 
-                if actual_md5 != expected_md5 {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!(
-                            "reference sequence checksum mismatch: expected {expected_md5:?}, got {actual_md5:?}"
-                        ),
-                    ));
+                if expected_md5 != MISSING_REFERENCE_MD5 {
+                    let actual_md5 =
+                        builder::calculate_normalized_sequence_digest(&sequence[start..=end]);
+
+                    check_reference_md5(reference_sequence_name, expected_md5, actual_md5)?;
+                } else if let Some(verified_ids) = verified_reference_sequence_ids.as_deref_mut() {
+                    verify_reference_sequence_md5(
+                        verified_ids,
+                        context.reference_sequence_id(),
+                        reference_sequence_name,
+                        reference_sequence,
+                        &sequence,
+                    )?;
                 }
             }
         }
@@ -209,16 +232,28 @@ impl Slice {
                 .preservation_map()
                 .is_reference_required()
             {
-                let reference_sequence_name = record
+                let (reference_sequence_name, reference_sequence) = record
                     .reference_sequence(header.reference_sequences())
                     .transpose()?
-                    .map(|(name, _)| name)
                     .expect("invalid reference sequence ID");
 
-                let sequence = reference_sequence_repository
-                    .get(reference_sequence_name)
-                    .transpose()?
-                    .expect("invalid reference sequence name");
+                let sequence =
+                    get_reference_sequence(reference_sequence_repository, reference_sequence_name)?;
+
+                // Multi-reference slices do not have a slice reference MD5.
+                if self.header().reference_sequence_context().is_many() {
+                    if let Some(verified_ids) = verified_reference_sequence_ids.as_deref_mut() {
+                        verify_reference_sequence_md5(
+                            verified_ids,
+                            record
+                                .reference_sequence_id()
+                                .expect("invalid reference sequence ID"),
+                            reference_sequence_name,
+                            reference_sequence,
+                            &sequence,
+                        )?;
+                    }
+                }
 
                 Some(sequence)
             } else if let Some(ref sequence) = embedded_reference_sequence {
@@ -268,6 +303,65 @@ impl Slice {
     }
 }
 
+// A slice reference MD5 of all zeros means no checksum was recorded.
+const MISSING_REFERENCE_MD5: [u8; 16] = [0; 16];
+
+fn get_reference_sequence(
+    reference_sequence_repository: &fasta::Repository,
+    name: &str,
+) -> io::Result<fasta::record::Sequence> {
+    reference_sequence_repository
+        .get(name)
+        .transpose()?
+        .ok_or_else(|| reference_sequence_error(ReferenceSequenceError::Missing(name.into())))
+}
+
+fn check_reference_md5(name: &str, expected: &[u8], actual: [u8; 16]) -> io::Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        let expected = <[u8; 16]>::try_from(expected)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Err(reference_sequence_error(
+            ReferenceSequenceError::ChecksumMismatch {
+                name: name.into(),
+                expected,
+                actual,
+            },
+        ))
+    }
+}
+
+fn verify_reference_sequence_md5(
+    verified_ids: &mut HashSet<usize>,
+    id: usize,
+    name: &str,
+    reference_sequence: &sam::header::record::value::Map<
+        sam::header::record::value::map::ReferenceSequence,
+    >,
+    sequence: &fasta::record::Sequence,
+) -> io::Result<()> {
+    if verified_ids.contains(&id) {
+        return Ok(());
+    }
+
+    let expected_md5 = reference_sequence.md5_checksum().ok_or_else(|| {
+        reference_sequence_error(ReferenceSequenceError::MissingChecksum(name.into()))
+    })?;
+
+    let actual_md5 = builder::calculate_normalized_sequence_digest(sequence.as_ref());
+    check_reference_md5(name, &expected_md5, actual_md5)?;
+
+    verified_ids.insert(id);
+
+    Ok(())
+}
+
+fn reference_sequence_error(e: ReferenceSequenceError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
 fn resolve_mates(records: &mut [Record]) -> io::Result<()> {
     let mut mate_indices = vec![None; records.len()];
 
@@ -527,4 +621,114 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_get_reference_sequence() -> io::Result<()> {
+        use fasta::record::{Definition, Sequence};
+
+        let sq0 = fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"ACGT".to_vec()),
+        );
+        let repository = fasta::Repository::new(vec![sq0.clone()]);
+
+        assert_eq!(
+            get_reference_sequence(&repository, "sq0")?,
+            sq0.sequence().clone()
+        );
+
+        let e = get_reference_sequence(&repository, "sq1").unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            e.into_inner()
+                .and_then(|e| e.downcast::<ReferenceSequenceError>().ok())
+                .map(|e| *e),
+            Some(ReferenceSequenceError::Missing(String::from("sq1")))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_reference_md5() {
+        let expected = builder::calculate_normalized_sequence_digest(b"ACGT");
+
+        assert!(check_reference_md5("sq0", &expected, expected).is_ok());
+
+        let actual = builder::calculate_normalized_sequence_digest(b"ACGA");
+        let e = check_reference_md5("sq0", &expected, actual).unwrap_err();
+
+        assert_eq!(
+            e.into_inner()
+                .and_then(|e| e.downcast::<ReferenceSequenceError>().ok())
+                .map(|e| *e),
+            Some(ReferenceSequenceError::ChecksumMismatch {
+                name: String::from("sq0"),
+                expected,
+                actual,
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_reference_sequence_md5() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use sam::header::record::value::{
+            map::{reference_sequence::Md5Checksum, ReferenceSequence},
+            Map,
+        };
+
+        let length = NonZeroUsize::try_from(4)?;
+        let sequence = fasta::record::Sequence::from(b"ACGT".to_vec());
+        let md5_checksum = builder::calculate_normalized_sequence_digest(sequence.as_ref());
+
+        let reference_sequence = Map::<ReferenceSequence>::builder()
+            .set_length(length)
+            .set_md5_checksum(Md5Checksum::from(md5_checksum))
+            .build()?;
+
+        let mut verified_ids = HashSet::new();
+        verify_reference_sequence_md5(&mut verified_ids, 0, "sq0", &reference_sequence, &sequence)?;
+        assert_eq!(verified_ids, [0].into_iter().collect());
+
+        // A verified reference sequence is not checked again.
+        let other_sequence = fasta::record::Sequence::from(b"ACGA".to_vec());
+        verify_reference_sequence_md5(
+            &mut verified_ids,
+            0,
+            "sq0",
+            &reference_sequence,
+            &other_sequence,
+        )?;
+
+        assert!(verify_reference_sequence_md5(
+            &mut verified_ids,
+            1,
+            "sq1",
+            &reference_sequence,
+            &other_sequence,
+        )
+        .is_err());
+
+        let reference_sequence = Map::<ReferenceSequence>::new(length);
+        let e = verify_reference_sequence_md5(
+            &mut verified_ids,
+            2,
+            "sq2",
+            &reference_sequence,
+            &sequence,
+        )
+        .unwrap_err();
+        assert_eq!(
+            e.into_inner()
+                .and_then(|e| e.downcast::<ReferenceSequenceError>().ok())
+                .map(|e| *e),
+            Some(ReferenceSequenceError::MissingChecksum(String::from("sq2")))
+        );
+
+        assert_eq!(verified_ids, [0].into_iter().collect());
+
+        Ok(())
+    }
 }
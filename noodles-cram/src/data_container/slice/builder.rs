@@ -6,20 +6,24 @@ use noodles_fasta as fasta;
 use noodles_sam as sam;
 
 use crate::{
-    codecs::Encoder,
+    codecs::{fqzcomp, Encoder},
     container::{block, Block},
     data_container::{
+        builder::is_reference_sequence_changed,
         compression_header::data_series_encoding_map::data_series::STANDARD_DATA_SERIES,
         BlockContentEncoderMap, CompressionHeader, ReferenceSequenceContext,
     },
     record::Flags,
-    writer, BitWriter, Record,
+    writer::{self, ReferenceSequenceMode, Selector},
+    BitWriter, Record,
 };
 
 use super::{Header, Slice};
 
 const CORE_DATA_BLOCK_CONTENT_ID: i32 = 0;
-const MAX_RECORD_COUNT: usize = 10240;
+// This is outside the range of both the standard data series (1-30) and tag (3-byte key) block
+// content IDs.
+const EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID: i32 = 64;
 
 #[derive(Debug, Default)]
 pub struct Builder {
@@ -30,6 +34,7 @@ pub struct Builder {
 #[derive(Clone, Debug, PartialEq)]
 pub enum AddRecordError {
     SliceFull(Record),
+    ReferenceSequenceChanged(Record),
 }
 
 impl Builder {
@@ -46,11 +51,22 @@ impl Builder {
     }
 
     #[allow(clippy::result_large_err)]
-    pub fn add_record(&mut self, record: Record) -> Result<&Record, AddRecordError> {
-        if self.records.len() >= MAX_RECORD_COUNT {
+    pub fn add_record(
+        &mut self,
+        options: &writer::Options,
+        record: Record,
+    ) -> Result<&Record, AddRecordError> {
+        if self.records.len() >= options.records_per_slice.get() {
             return Err(AddRecordError::SliceFull(record));
         }
 
+        if !self.is_empty()
+            && !options.multi_reference_slices
+            && is_reference_sequence_changed(self.reference_sequence_context, &record)
+        {
+            return Err(AddRecordError::ReferenceSequenceChanged(record));
+        }
+
         if self.is_empty() {
             self.reference_sequence_context = match (
                 record.reference_sequence_id(),
@@ -77,28 +93,29 @@ impl Builder {
 
     pub fn build(
         mut self,
-        block_content_encoder_map: &BlockContentEncoderMap,
+        options: &writer::Options,
         reference_sequence_repostitory: &fasta::repository::Repository,
         header: &sam::Header,
         compression_header: &CompressionHeader,
         record_counter: u64,
+        block_content_encoder_selector: Option<&mut Selector>,
     ) -> io::Result<Slice> {
-        let (core_data_block, external_blocks) = write_records(
-            block_content_encoder_map,
+        let (core_data_block, mut external_blocks) = write_records(
+            &options.block_content_encoder_map,
+            block_content_encoder_selector,
             compression_header,
             self.reference_sequence_context,
             &mut self.records,
         )?;
 
-        let mut block_content_ids = Vec::with_capacity(external_blocks.len() + 1);
-        block_content_ids.push(core_data_block.content_id());
+        let mut embedded_reference_bases_block_content_id = None;
 
-        for block in &external_blocks {
-            block_content_ids.push(block.content_id());
-        }
-
-        let reference_md5 = match self.reference_sequence_context {
-            ReferenceSequenceContext::Some(context) => {
+        let reference_md5 = match (
+            options.reference_sequence_mode,
+            self.reference_sequence_context,
+        ) {
+            (ReferenceSequenceMode::None, _) => [0; 16],
+            (mode, ReferenceSequenceContext::Some(context)) => {
                 let reference_sequence_name = header
                     .reference_sequences()
                     .get_index(context.reference_sequence_id())
@@ -113,19 +130,48 @@ impl Builder {
                 let (start, end) = (context.alignment_start(), context.alignment_end());
                 let sequence = &reference_sequence[start..=end];
 
+                if mode == ReferenceSequenceMode::Embedded {
+                    let block_content_id =
+                        block::ContentId::from(EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID);
+
+                    let block = Block::builder()
+                        .set_content_type(block::ContentType::ExternalData)
+                        .set_content_id(block_content_id)
+                        .compress_and_set_data(
+                            sequence.to_vec(),
+                            Encoder::Gzip(Default::default()),
+                        )?
+                        .build();
+
+                    external_blocks.push(block);
+                    embedded_reference_bases_block_content_id = Some(block_content_id);
+                }
+
                 calculate_normalized_sequence_digest(sequence)
             }
             _ => [0; 16],
         };
 
-        let header = Header::builder()
+        let mut block_content_ids = Vec::with_capacity(external_blocks.len() + 1);
+        block_content_ids.push(core_data_block.content_id());
+
+        for block in &external_blocks {
+            block_content_ids.push(block.content_id());
+        }
+
+        let mut builder = Header::builder()
             .set_reference_sequence_context(self.reference_sequence_context)
             .set_record_count(self.records.len())
             .set_record_counter(record_counter)
             .set_block_count(block_content_ids.len())
             .set_block_content_ids(block_content_ids)
-            .set_reference_md5(reference_md5)
-            .build();
+            .set_reference_md5(reference_md5);
+
+        if let Some(id) = embedded_reference_bases_block_content_id {
+            builder = builder.set_embedded_reference_bases_block_content_id(id);
+        }
+
+        let header = builder.build();
 
         Ok(Slice::new(header, core_data_block, external_blocks))
     }
@@ -133,12 +179,11 @@ impl Builder {
 
 fn write_records(
     block_content_encoder_map: &BlockContentEncoderMap,
+    mut block_content_encoder_selector: Option<&mut Selector>,
     compression_header: &CompressionHeader,
     reference_sequence_context: ReferenceSequenceContext,
     records: &mut [Record],
 ) -> io::Result<(Block, Vec<Block>)> {
-    use crate::codecs::fqzcomp;
-
     fn set_block_data(
         builder: block::Builder,
         buf: Vec<u8>,
@@ -193,6 +238,12 @@ fn write_records(
         Ok(builder.build())
     })?;
 
+    let quality_score_records = if all_quality_scores_stored_as_arrays {
+        Some(&records[..])
+    } else {
+        None
+    };
+
     let external_blocks: Vec<_> = external_data_writers
         .into_iter()
         .filter(|(_, buf)| !buf.is_empty())
@@ -201,14 +252,20 @@ fn write_records(
                 .set_content_type(block::ContentType::ExternalData)
                 .set_content_id(block_content_id);
 
-            builder = if let Some(encoder) =
+            builder = if let Some(selector) = block_content_encoder_selector.as_deref_mut() {
+                selector.compress_and_set_data(
+                    builder,
+                    block_content_id,
+                    buf,
+                    quality_score_records,
+                )?
+            } else if let Some(encoder) =
                 block_content_encoder_map.get_data_series_encoder(block_content_id)
             {
                 match encoder {
-                    Some(Encoder::Fqzcomp) => {
-                        if all_quality_scores_stored_as_arrays {
-                            let lens: Vec<_> = records.iter().map(|r| r.read_length()).collect();
-                            let data = fqzcomp::encode(&lens, &buf)?;
+                    Some(Encoder::Fqzcomp(options)) => {
+                        if let Some(records) = quality_score_records {
+                            let data = encode_quality_scores(options, records, &buf)?;
 
                             builder
                                 .set_uncompressed_len(buf.len())
@@ -235,6 +292,33 @@ fn write_records(
     Ok((core_data_block, external_blocks))
 }
 
+pub(crate) fn encode_quality_scores(
+    options: &fqzcomp::Options,
+    records: &[Record],
+    src: &[u8],
+) -> io::Result<Vec<u8>> {
+    use crate::codecs::fqzcomp::Selector;
+
+    let lens: Vec<_> = records.iter().map(|r| r.read_length()).collect();
+
+    let sels: Vec<_> = match options.selector() {
+        Selector::None => Vec::new(),
+        Selector::ReadNumber => records
+            .iter()
+            .map(|r| u8::from(r.flags().is_segmented() && r.flags().is_last_segment()))
+            .collect(),
+        Selector::ReadGroup => records
+            .iter()
+            .map(|r| match r.read_group_id() {
+                Some(id) => u8::try_from(id + 1).unwrap_or(u8::MAX),
+                None => 0,
+            })
+            .collect(),
+    };
+
+    fqzcomp::encode(options, &lens, &sels, src)
+}
+
 fn set_mates(records: &mut [Record]) {
     assert!(!records.is_empty());
 
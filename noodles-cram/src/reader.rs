@@ -1,5 +1,6 @@
 //! CRAM reader and record iterator.
 
+mod builder;
 pub(crate) mod container;
 pub(crate) mod data_container;
 pub(crate) mod header_container;
@@ -7,10 +8,21 @@ pub(crate) mod num;
 mod query;
 pub(crate) mod record;
 mod records;
-
-pub use self::{query::Query, records::Records};
-
-use std::io::{self, Read, Seek, SeekFrom};
+mod reference_sequence_error;
+mod regions_query;
+
+pub use self::{
+    builder::Builder,
+    query::Query,
+    records::Records,
+    reference_sequence_error::ReferenceSequenceError,
+    regions_query::{RegionsQuery, WithRegionIndices},
+};
+
+use std::{
+    collections::HashSet,
+    io::{self, Read, Seek, SeekFrom},
+};
 
 use bytes::BytesMut;
 use noodles_core::Region;
@@ -49,6 +61,8 @@ use crate::data_container::DataContainer;
 pub struct Reader<R> {
     inner: R,
     buf: BytesMut,
+    strict_reference_sequence_checks: bool,
+    verified_reference_sequence_ids: HashSet<usize>,
 }
 
 impl<R> Reader<R>
@@ -66,10 +80,7 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn new(reader: R) -> Self {
-        Self {
-            inner: reader,
-            buf: BytesMut::new(),
-        }
+        Builder::default().build_from_reader(reader)
     }
 
     /// Returns a reference to the underlying reader.
@@ -114,6 +125,32 @@ where
         self.inner
     }
 
+    /// Returns whether reference sequences are strictly checked.
+    ///
+    /// See [`Builder::strict_reference_sequence_checks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let reader = cram::Reader::new(&data[..]);
+    /// assert!(!reader.strict_reference_sequence_checks());
+    /// ```
+    pub fn strict_reference_sequence_checks(&self) -> bool {
+        self.strict_reference_sequence_checks
+    }
+
+    // Returns the IDs of the reference sequences whose `M5` checksums were verified, if reference
+    // sequences are strictly checked.
+    pub(crate) fn verified_reference_sequence_ids(&mut self) -> Option<&mut HashSet<usize>> {
+        if self.strict_reference_sequence_checks {
+            Some(&mut self.verified_reference_sequence_ids)
+        } else {
+            None
+        }
+    }
+
     /// Reads the CRAM file definition.
     ///
     /// The CRAM magic number is also checked.
@@ -319,6 +356,62 @@ where
             region.interval(),
         ))
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Each container is read at most once, and each record is returned at most once, even if
+    /// the regions overlap or are close together. Use [`RegionsQuery::with_region_indices`] to
+    /// also get the regions each record intersects.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_core::Region;
+    /// use noodles_cram::{self as cram, crai};
+    /// use noodles_fasta as fasta;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    ///
+    /// let header = reader.read_file_header()?.parse()?;
+    ///
+    /// let index = crai::read("sample.cram.crai")?;
+    /// let regions: Vec<Region> = vec!["sq0:8-13".parse()?, "sq0:21-34".parse()?];
+    /// let repository = fasta::Repository::default();
+    /// let query = reader.query_regions(&repository, &header, &index, &regions)?;
+    ///
+    /// for result in query.with_region_indices() {
+    ///     let (record, region_indices) = result?;
+    ///     println!("{:?} {:?}", record, region_indices);
+    /// }
+    /// Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'a>(
+        &'a mut self,
+        reference_sequence_repository: &'a fasta::Repository,
+        header: &'a sam::Header,
+        index: &crai::Index,
+        regions: &[Region],
+    ) -> io::Result<RegionsQuery<'a, R>> {
+        for region in regions {
+            if !header.reference_sequences().contains_key(region.name()) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid reference sequence name",
+                ));
+            }
+        }
+
+        Ok(RegionsQuery::new(
+            self,
+            reference_sequence_repository,
+            header,
+            index,
+            regions,
+        ))
+    }
 }
 
 impl<R> sam::AlignmentReader<R> for Reader<R>
@@ -346,6 +439,47 @@ where
     }
 }
 
+/// Creates a reference sequence repository backed by the local reference cache.
+///
+/// This is the default repository to use when no reference sequences are given. Sequences are
+/// resolved by the MD5 checksums (`M5`) of the reference sequence records in the given header
+/// using the `REF_CACHE` and `REF_PATH` environment variables. See
+/// [`fasta::repository::adapters::RefCache`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_cram as cram;
+///
+/// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+/// reader.read_file_definition()?;
+///
+/// let header = reader.read_file_header()?.parse()?;
+/// let repository = cram::reader::default_reference_sequence_repository(&header);
+///
+/// for result in reader.records(&repository, &header) {
+///     let record = result?;
+///     // ...
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn default_reference_sequence_repository(header: &sam::Header) -> fasta::Repository {
+    use fasta::repository::adapters::RefCache;
+
+    let mut adapter = RefCache::from_env();
+
+    for (name, reference_sequence) in header.reference_sequences() {
+        if let Some(md5_checksum) = reference_sequence.md5_checksum() {
+            if let Ok(checksum) = <[u8; 16]>::try_from(&md5_checksum[..]) {
+                adapter.insert(name.to_string(), checksum);
+            }
+        }
+    }
+
+    fasta::Repository::new(adapter)
+}
+
 fn read_magic_number<R>(reader: &mut R) -> io::Result<()>
 where
     R: Read,
@@ -428,4 +562,96 @@ mod tests {
             Err(ref e) if e.kind() == io::ErrorKind::InvalidData,
         ));
     }
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{io::Cursor, num::NonZeroUsize};
+
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        use crate::{writer, Record};
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        let mut writer = writer::Builder::default()
+            .set_reference_sequence_repository(repository.clone())
+            .set_records_per_slice(NonZeroUsize::try_from(1)?)
+            .set_slices_per_container(NonZeroUsize::try_from(1)?)
+            .build_with_writer(Vec::new());
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for (start, sequence) in [(1, "TTCA"), (3, "CACC"), (5, "CCCA")] {
+            let alignment_record = sam::alignment::Record::builder()
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence(sequence.parse()?)
+                .build();
+
+            let record = Record::try_from_alignment_record(&header, &alignment_record)?;
+            writer.write_record(&header, record)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let data = writer.get_ref().clone();
+
+        // Index each single-record container.
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut index = Vec::new();
+
+        for start in [1, 3, 5] {
+            let offset = reader.position()?;
+            reader.read_data_container()?.expect("missing container");
+            let alignment_start = Position::new(start);
+            index.push(crai::Record::new(Some(0), alignment_start, 4, offset, 0, 0));
+        }
+
+        let regions = ["sq0:1-1".parse()?, "sq0:4-5".parse()?, "sq0:8-8".parse()?];
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let query = reader.query_regions(&repository, &header, &index, &regions)?;
+
+        let actual: Vec<_> = query
+            .with_region_indices()
+            .map(|result| {
+                result.map(|(record, region_indices)| {
+                    (record.alignment_start().map(usize::from), region_indices)
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (Some(1), vec![0, 1]),
+            (Some(3), vec![1]),
+            (Some(5), vec![1, 2]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let regions = ["sq1:1-1".parse()?];
+        assert!(matches!(
+            reader.query_regions(&repository, &header, &index, &regions),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput,
+        ));
+
+        Ok(())
+    }
 }
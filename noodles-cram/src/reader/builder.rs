@@ -0,0 +1,80 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use bytes::BytesMut;
+
+use super::Reader;
+
+/// A CRAM reader builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    strict_reference_sequence_checks: bool,
+}
+
+impl Builder {
+    /// Sets whether to strictly check reference sequences.
+    ///
+    /// Slice reference sequence checksums are always verified when present. In strict mode,
+    /// resolving a slice additionally fails if neither the slice header nor the SAM header
+    /// reference sequence record (`@SQ` `M5`) has a checksum, and the `M5` checksum is verified
+    /// when the slice checksum is missing. This includes each reference sequence used by a
+    /// multi-reference slice. Each reference sequence is verified at most once per reader.
+    ///
+    /// Slices with an embedded reference sequence are not checked against `M5`, as the reference
+    /// sequence repository is not used.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::reader::Builder::default().strict_reference_sequence_checks(true);
+    /// ```
+    pub fn strict_reference_sequence_checks(mut self, value: bool) -> Self {
+        self.strict_reference_sequence_checks = value;
+        self
+    }
+
+    /// Builds a CRAM reader from a path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// let reader = cram::reader::Builder::default().build_from_path("sample.cram")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, src: P) -> io::Result<Reader<File>>
+    where
+        P: AsRef<Path>,
+    {
+        File::open(src).map(|file| self.build_from_reader(file))
+    }
+
+    /// Builds a CRAM reader from a reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// let reader = cram::reader::Builder::default().build_from_reader(io::empty());
+    /// ```
+    pub fn build_from_reader<R>(self, reader: R) -> Reader<R>
+    where
+        R: Read,
+    {
+        Reader {
+            inner: reader,
+            buf: BytesMut::new(),
+            strict_reference_sequence_checks: self.strict_reference_sequence_checks,
+            verified_reference_sequence_ids: HashSet::new(),
+        }
+    }
+}
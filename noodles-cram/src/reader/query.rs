@@ -68,41 +68,17 @@ where
             return Some(Err(e));
         }
 
-        let container = match self.reader.read_data_container() {
-            Ok(Some(c)) => c,
+        let records = match read_data_container_records(
+            self.reader,
+            self.reference_sequence_repository,
+            self.header,
+        ) {
+            Ok(Some(records)) => records,
             Ok(None) => return None,
             Err(e) => return Some(Err(e)),
         };
 
-        let records = container
-            .slices()
-            .iter()
-            .map(|slice| {
-                let compression_header = container.compression_header();
-
-                slice.records(compression_header).and_then(|mut records| {
-                    slice.resolve_records(
-                        self.reference_sequence_repository,
-                        self.header,
-                        compression_header,
-                        &mut records,
-                    )?;
-
-                    Ok(records)
-                })
-            })
-            .collect::<Result<Vec<_>, _>>();
-
-        let records = match records {
-            Ok(records) => records,
-            Err(e) => return Some(Err(e)),
-        };
-
-        self.records = records
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .into_iter();
+        self.records = records.into_iter();
 
         Some(Ok(()))
     }
@@ -135,3 +111,37 @@ where
         }
     }
 }
+
+// Reads the next data container and returns its resolved records.
+pub(super) fn read_data_container_records<R>(
+    reader: &mut Reader<R>,
+    reference_sequence_repository: &fasta::Repository,
+    header: &sam::Header,
+) -> io::Result<Option<Vec<Record>>>
+where
+    R: Read,
+{
+    let container = match reader.read_data_container()? {
+        Some(c) => c,
+        None => return Ok(None),
+    };
+
+    let compression_header = container.compression_header();
+    let mut records = Vec::new();
+
+    for slice in container.slices() {
+        let mut slice_records = slice.records(compression_header)?;
+
+        slice.resolve_records_inner(
+            reference_sequence_repository,
+            header,
+            compression_header,
+            &mut slice_records,
+            reader.verified_reference_sequence_ids(),
+        )?;
+
+        records.extend(slice_records);
+    }
+
+    Ok(Some(records))
+}
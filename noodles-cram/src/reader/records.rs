@@ -52,11 +52,12 @@ where
                 let compression_header = container.compression_header();
 
                 slice.records(compression_header).and_then(|mut records| {
-                    slice.resolve_records(
+                    slice.resolve_records_inner(
                         self.reference_sequence_repository,
                         self.header,
                         compression_header,
                         &mut records,
+                        self.reader.verified_reference_sequence_ids(),
                     )?;
 
                     Ok(records)
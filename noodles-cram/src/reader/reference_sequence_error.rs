@@ -0,0 +1,76 @@
+use std::{error, fmt};
+
+/// An error returned when a reference sequence fails to validate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReferenceSequenceError {
+    /// The reference sequence is missing from the repository.
+    Missing(String),
+    /// The reference sequence checksum is missing.
+    ///
+    /// This is only returned in strict mode, when neither the slice header nor the SAM header
+    /// reference sequence record (`@SQ` `M5`) has a checksum.
+    MissingChecksum(String),
+    /// The reference sequence checksum does not match.
+    ChecksumMismatch {
+        /// The reference sequence name.
+        name: String,
+        /// The expected checksum.
+        expected: [u8; 16],
+        /// The checksum of the reference sequence from the repository.
+        actual: [u8; 16],
+    },
+}
+
+impl error::Error for ReferenceSequenceError {}
+
+impl fmt::Display for ReferenceSequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(name) => write!(f, "missing reference sequence: {name}"),
+            Self::MissingChecksum(name) => {
+                write!(f, "missing reference sequence checksum: {name}")
+            }
+            Self::ChecksumMismatch {
+                name,
+                expected,
+                actual,
+            } => {
+                write!(f, "reference sequence checksum mismatch: {name}: expected ")?;
+                write_hex(f, expected)?;
+                f.write_str(", got ")?;
+                write_hex(f, actual)
+            }
+        }
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, buf: &[u8]) -> fmt::Result {
+    for b in buf {
+        write!(f, "{b:02x}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(
+            ReferenceSequenceError::Missing(String::from("sq0")).to_string(),
+            "missing reference sequence: sq0"
+        );
+
+        assert_eq!(
+            ReferenceSequenceError::ChecksumMismatch {
+                name: String::from("sq0"),
+                expected: [0x00; 16],
+                actual: [0xff; 16],
+            }
+            .to_string(),
+            "reference sequence checksum mismatch: sq0: expected 00000000000000000000000000000000, got ffffffffffffffffffffffffffffffff"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    vec,
+};
+
+use noodles_core::{interval_set::OverlapIndex, region::Interval, Feature, Position, Region};
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+
+use super::{query::read_data_container_records, Reader};
+use crate::{crai, Record};
+
+/// An iterator over records that intersect any of the given regions.
+///
+/// Each container is read at most once, and each record is returned at most once, even if it
+/// intersects more than one region.
+///
+/// This is created by calling [`Reader::query_regions`].
+pub struct RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: &'a mut Reader<R>,
+
+    reference_sequence_repository: &'a fasta::Repository,
+    header: &'a sam::Header,
+
+    offsets: vec::IntoIter<u64>,
+
+    regions: OverlapIndex<usize>,
+
+    records: vec::IntoIter<Record>,
+}
+
+impl<'a, R> RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(super) fn new(
+        reader: &'a mut Reader<R>,
+        reference_sequence_repository: &'a fasta::Repository,
+        header: &'a sam::Header,
+        index: &crai::Index,
+        regions: &[Region],
+    ) -> Self {
+        let regions: OverlapIndex<usize> = regions.iter().cloned().zip(0..).collect();
+
+        let mut offsets: Vec<_> = index
+            .iter()
+            .filter(|index_record| intersects(&regions, header, index_record))
+            .map(|index_record| index_record.offset())
+            .collect();
+
+        // A container may have more than one index record, e.g., one per slice or per reference
+        // sequence in a multi-reference slice.
+        offsets.sort_unstable();
+        offsets.dedup();
+
+        Self {
+            reader,
+
+            reference_sequence_repository,
+            header,
+
+            offsets: offsets.into_iter(),
+
+            regions,
+
+            records: Vec::new().into_iter(),
+        }
+    }
+
+    /// Returns an iterator that also yields the indices of the regions each record intersects.
+    ///
+    /// The indices are positions in the list of regions given to [`Reader::query_regions`], in
+    /// ascending order.
+    pub fn with_region_indices(self) -> WithRegionIndices<'a, R> {
+        WithRegionIndices { query: self }
+    }
+
+    fn read_next_container(&mut self) -> Option<io::Result<()>> {
+        let offset = self.offsets.next()?;
+
+        if let Err(e) = self.reader.seek(SeekFrom::Start(offset)) {
+            return Some(Err(e));
+        }
+
+        match read_data_container_records(
+            self.reader,
+            self.reference_sequence_repository,
+            self.header,
+        ) {
+            Ok(Some(records)) => {
+                self.records = records.into_iter();
+                Some(Ok(()))
+            }
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    fn next_with_region_indices(&mut self) -> Option<io::Result<(Record, Vec<usize>)>> {
+        loop {
+            match self.records.next() {
+                Some(record) => {
+                    let region_indices =
+                        intersecting_region_indices(&self.regions, self.header, &record);
+
+                    if !region_indices.is_empty() {
+                        return Some(Ok((record, region_indices)));
+                    }
+                }
+                None => match self.read_next_container() {
+                    Some(Ok(())) => {}
+                    Some(Err(e)) => return Some(Err(e)),
+                    None => return None,
+                },
+            }
+        }
+    }
+}
+
+impl<'a, R> Iterator for RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_region_indices()
+            .map(|result| result.map(|(record, _)| record))
+    }
+}
+
+/// An iterator over records and the indices of the regions they intersect.
+///
+/// This is created by calling [`RegionsQuery::with_region_indices`].
+pub struct WithRegionIndices<'a, R>
+where
+    R: Read + Seek,
+{
+    query: RegionsQuery<'a, R>,
+}
+
+impl<'a, R> Iterator for WithRegionIndices<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<(Record, Vec<usize>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.query.next_with_region_indices()
+    }
+}
+
+fn intersects(
+    regions: &OverlapIndex<usize>,
+    header: &sam::Header,
+    index_record: &crai::Record,
+) -> bool {
+    let name = match index_record
+        .reference_sequence_id()
+        .and_then(|id| header.reference_sequences().get_index(id))
+    {
+        Some((name, _)) => name,
+        None => return false,
+    };
+
+    let interval = match index_record.alignment_start() {
+        Some(start) => {
+            let end = usize::from(start)
+                .checked_add(index_record.alignment_span())
+                .and_then(|n| n.checked_sub(1))
+                .and_then(Position::new)
+                .map(|end| end.max(start))
+                .unwrap_or(start);
+
+            Interval::from(start..=end)
+        }
+        // An index record without an alignment start cannot be filtered by position.
+        None => Interval::from(..),
+    };
+
+    let region = Region::new(name.to_string(), interval);
+    regions.overlapping(&region).next().is_some()
+}
+
+fn intersecting_region_indices(
+    regions: &OverlapIndex<usize>,
+    header: &sam::Header,
+    record: &Record,
+) -> Vec<usize> {
+    let region = match record.region(header) {
+        Ok(region) => region,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut indices: Vec<_> = regions.overlapping(&region).map(|(_, &i)| i).collect();
+    indices.sort_unstable();
+    indices
+}
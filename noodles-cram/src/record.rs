@@ -15,7 +15,7 @@ pub use self::{
 
 use std::io;
 
-use noodles_core::Position;
+use noodles_core::{self as core, region::Interval, Position};
 use noodles_sam::{
     self as sam,
     header::record::value::{
@@ -238,6 +238,30 @@ impl Default for Record {
     }
 }
 
+impl core::Feature<sam::Header> for Record {
+    fn reference_sequence_name<'a>(&'a self, header: &'a sam::Header) -> core::Result<&'a str> {
+        self.reference_sequence(header.reference_sequences())
+            .ok_or_else(|| {
+                core::Error::new(core::error::Kind::Other, "missing reference sequence ID")
+            })?
+            .map(|(name, _)| name.as_ref())
+            .map_err(core::Error::from)
+    }
+
+    fn interval(&self) -> core::Result<Interval> {
+        match (self.alignment_start(), self.alignment_end()) {
+            // A record without alignment operations (e.g., a placed unmapped read) spans its
+            // alignment start.
+            (Some(start), Some(end)) => Ok(Interval::from(start..=end.max(start))),
+            (Some(start), None) => Ok(Interval::from(start..=start)),
+            _ => Err(core::Error::new(
+                core::error::Kind::Other,
+                "missing alignment start",
+            )),
+        }
+    }
+}
+
 fn calculate_alignment_span(read_length: usize, features: &Features) -> usize {
     features
         .iter()
@@ -291,4 +315,31 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_region() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use noodles_core::Feature as _;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(21)?),
+            )
+            .build();
+
+        let record = Record::builder()
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .set_read_length(4)
+            .build();
+
+        assert_eq!(record.region(&header)?, "sq0:8-11".parse()?);
+
+        let record = Record::builder().set_read_length(4).build();
+        assert!(record.region(&header).is_err());
+
+        Ok(())
+    }
 }
@@ -7,9 +7,14 @@ pub(crate) mod header_container;
 pub(crate) mod num;
 mod options;
 pub(crate) mod record;
+mod reference_sequence_mode;
+mod trial_compression;
 
-pub use self::builder::Builder;
-pub(crate) use self::options::Options;
+pub use self::{
+    builder::Builder, reference_sequence_mode::ReferenceSequenceMode,
+    trial_compression::TrialCompression,
+};
+pub(crate) use self::{options::Options, trial_compression::Selector};
 
 use std::{
     io::{self, Write},
@@ -52,6 +57,7 @@ where
     inner: W,
     reference_sequence_repository: fasta::Repository,
     options: Options,
+    block_content_encoder_selector: Option<Selector>,
     data_container_builder: crate::data_container::Builder,
     record_counter: u64,
 }
@@ -143,7 +149,8 @@ where
     /// The position of the stream is expected to be directly after the file definition.
     ///
     /// Entries in the reference sequence dictionary that are missing MD5 checksums (`M5`) will
-    /// automatically be calculated and added to the written record.
+    /// automatically be calculated and added to the written record, unless the reference sequence
+    /// mode is [`ReferenceSequenceMode::None`].
     ///
     /// # Examples
     ///
@@ -166,12 +173,18 @@ where
 
         let mut header = header.clone();
 
-        add_missing_reference_sequence_checksums(
-            &self.reference_sequence_repository,
-            header.reference_sequences_mut(),
-        )?;
+        if self.options.reference_sequence_mode != ReferenceSequenceMode::None {
+            add_missing_reference_sequence_checksums(
+                &self.reference_sequence_repository,
+                header.reference_sequences_mut(),
+            )?;
+        }
 
-        write_header_container(&mut self.inner, &header)
+        write_header_container(
+            &mut self.inner,
+            &header,
+            self.options.reference_sequence_mode,
+        )
     }
 
     /// Writes a CRAM record.
@@ -200,7 +213,10 @@ where
         use super::data_container::builder::AddRecordError;
 
         loop {
-            match self.data_container_builder.add_record(record) {
+            match self
+                .data_container_builder
+                .add_record(&self.options, record)
+            {
                 Ok(_) => {
                     self.record_counter += 1;
                     return Ok(());
@@ -237,6 +253,7 @@ where
             &self.options,
             &self.reference_sequence_repository,
             header,
+            self.block_content_encoder_selector.as_mut(),
         )?;
 
         write_data_container(&mut self.inner, &data_container, base_count)
@@ -360,4 +377,354 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_record_with_reference_sequence_modes() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        let alignment_record = sam::alignment::Record::builder()
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(2)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("TCAC".parse()?)
+            .set_quality_scores("NDLS".parse()?)
+            .build();
+
+        for mode in [ReferenceSequenceMode::Embedded, ReferenceSequenceMode::None] {
+            let mut writer = Builder::default()
+                .set_reference_sequence_repository(repository.clone())
+                .set_reference_sequence_mode(mode)
+                .build_with_writer(Vec::new());
+
+            writer.write_file_definition()?;
+            writer.write_file_header(&header)?;
+
+            let record = Record::try_from_alignment_record(&header, &alignment_record)?;
+            writer.write_record(&header, record)?;
+            writer.try_finish(&header)?;
+
+            let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+            reader.read_file_definition()?;
+            let actual_header: sam::Header = reader.read_file_header()?.parse()?;
+
+            let actual_md5_checksum = actual_header
+                .reference_sequences()
+                .get("sq0")
+                .and_then(|rs| rs.md5_checksum());
+            assert_eq!(
+                actual_md5_checksum.is_some(),
+                mode == ReferenceSequenceMode::Embedded
+            );
+
+            let container = reader.read_data_container()?.expect("missing container");
+            let compression_header = container.compression_header();
+            assert!(!compression_header
+                .preservation_map()
+                .is_reference_required());
+
+            let slice = &container.slices()[0];
+            assert_eq!(
+                slice
+                    .header()
+                    .embedded_reference_bases_block_content_id()
+                    .is_some(),
+                mode == ReferenceSequenceMode::Embedded
+            );
+
+            let mut records = slice.records(compression_header)?;
+            slice.resolve_records(
+                &fasta::Repository::default(),
+                &header,
+                compression_header,
+                &mut records,
+            )?;
+
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].bases(), alignment_record.sequence());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_multiple_slices_per_container(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        let repository = fasta::Repository::new(vec![
+            fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"TTCACCCA".to_vec()),
+            ),
+            fasta::Record::new(
+                Definition::new("sq1", None),
+                Sequence::from(b"GATCTTACTTTTT".to_vec()),
+            ),
+        ]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .add_reference_sequence(
+                "sq1".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+            )
+            .build();
+
+        let alignment_records = [
+            sam::alignment::Record::builder()
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(2)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("TCAC".parse()?)
+                .set_quality_scores("NDLS".parse()?)
+                .build(),
+            sam::alignment::Record::builder()
+                .set_reference_sequence_id(1)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("GATC".parse()?)
+                .set_quality_scores("NDLS".parse()?)
+                .build(),
+        ];
+
+        let mut writer = Builder::default()
+            .set_reference_sequence_repository(repository.clone())
+            .set_records_per_slice(NonZeroUsize::try_from(1)?)
+            .set_slices_per_container(NonZeroUsize::try_from(2)?)
+            .build_with_writer(Vec::new());
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for alignment_record in &alignment_records {
+            let record = Record::try_from_alignment_record(&header, alignment_record)?;
+            writer.write_record(&header, record)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let container = reader.read_data_container()?.expect("missing container");
+        assert_eq!(container.slices().len(), 2);
+        assert!(reader.read_data_container()?.is_none());
+
+        let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records(&repository, &header)
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(records.len(), 2);
+
+        for (record, alignment_record) in records.iter().zip(&alignment_records) {
+            assert_eq!(record.bases(), alignment_record.sequence());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_trial_compression() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        let alignment_records: Vec<_> = (0..4)
+            .map(|i| {
+                Ok(sam::alignment::Record::builder()
+                    .set_read_name(format!("r{i}").parse()?)
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::try_from(2)?)
+                    .set_cigar("4M".parse()?)
+                    .set_sequence("TCAC".parse()?)
+                    .set_quality_scores("NDLS".parse()?)
+                    .build())
+            })
+            .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+
+        let trial_compression = TrialCompression::archive()
+            .set_trial_count(NonZeroUsize::try_from(2)?)
+            .set_reevaluation_interval(NonZeroUsize::try_from(1)?);
+
+        let mut writer = Builder::default()
+            .set_reference_sequence_repository(repository.clone())
+            .set_records_per_slice(NonZeroUsize::try_from(1)?)
+            .set_trial_compression(trial_compression)
+            .build_with_writer(Vec::new());
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for alignment_record in &alignment_records {
+            let record = Record::try_from_alignment_record(&header, alignment_record)?;
+            writer.write_record(&header, record)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+        let file_definition = reader.read_file_definition()?;
+        assert_eq!(file_definition.version(), Version::new(3, 1));
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records(&repository, &header)
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(records.len(), alignment_records.len());
+
+        for (record, alignment_record) in records.iter().zip(&alignment_records) {
+            assert_eq!(record.read_name(), alignment_record.read_name());
+            assert_eq!(record.bases(), alignment_record.sequence());
+            assert_eq!(record.quality_scores(), alignment_record.quality_scores());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_fqzcomp_and_name_tokenizer_options(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        use crate::{
+            codecs::{fqzcomp, name_tokenizer, Encoder},
+            data_container::{
+                compression_header::data_series_encoding_map::DataSeries, BlockContentEncoderMap,
+            },
+        };
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        let alignment_records: Vec<_> = ["NDLS", "NNDD", "SSSN"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, quality_scores)| {
+                Ok(sam::alignment::Record::builder()
+                    .set_read_name(format!("r{i}").parse()?)
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::try_from(2)?)
+                    .set_cigar("4M".parse()?)
+                    .set_sequence("TCAC".parse()?)
+                    .set_quality_scores(quality_scores.parse()?)
+                    .build())
+            })
+            .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+
+        let quality_table: Vec<_> = (0..=u8::MAX).map(|q| q / 8).collect();
+
+        for (fqzcomp_options, name_tokenizer_options) in [
+            (
+                fqzcomp::Options::from_level(9),
+                name_tokenizer::Options::new(9).use_arithmetic_coder(true),
+            ),
+            (
+                fqzcomp::Options::default()
+                    .set_strategy(fqzcomp::Strategy::MiSeq)
+                    .use_symbol_map(true)
+                    .set_quality_table(quality_table.clone())
+                    .set_selector(fqzcomp::Selector::ReadGroup),
+                name_tokenizer::Options::new(5),
+            ),
+        ] {
+            let block_content_encoder_map = BlockContentEncoderMap::builder()
+                .set_data_series_encoder(
+                    DataSeries::QualityScores,
+                    Some(Encoder::Fqzcomp(fqzcomp_options)),
+                )
+                .set_data_series_encoder(
+                    DataSeries::ReadNames,
+                    Some(Encoder::NameTokenizer(name_tokenizer_options)),
+                )
+                .build();
+
+            let mut writer = Builder::default()
+                .set_reference_sequence_repository(repository.clone())
+                .set_block_content_encoder_map(block_content_encoder_map)
+                .build_with_writer(Vec::new());
+
+            writer.write_file_definition()?;
+            writer.write_file_header(&header)?;
+
+            for alignment_record in &alignment_records {
+                let record = Record::try_from_alignment_record(&header, alignment_record)?;
+                writer.write_record(&header, record)?;
+            }
+
+            writer.try_finish(&header)?;
+
+            let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+            reader.read_file_definition()?;
+            reader.read_file_header()?;
+
+            let records: Vec<_> = reader
+                .records(&repository, &header)
+                .collect::<Result<_, _>>()?;
+
+            assert_eq!(records.len(), alignment_records.len());
+
+            for (record, alignment_record) in records.iter().zip(&alignment_records) {
+                assert_eq!(record.read_name(), alignment_record.read_name());
+                assert_eq!(record.quality_scores(), alignment_record.quality_scores());
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -1,8 +1,8 @@
-use std::io::Write;
+use std::{io::Write, num::NonZeroUsize};
 
 use noodles_fasta as fasta;
 
-use super::{Options, Writer};
+use super::{Options, ReferenceSequenceMode, Selector, TrialCompression, Writer};
 use crate::{
     codecs::Encoder, data_container::BlockContentEncoderMap, file_definition::Version,
     DataContainer,
@@ -71,6 +71,84 @@ impl Builder {
         self
     }
 
+    /// Sets the reference sequence mode.
+    ///
+    /// This controls whether slices reference an external reference sequence, embed the
+    /// reference sequence span, or are encoded without a reference. See
+    /// [`ReferenceSequenceMode`].
+    ///
+    /// The default is [`ReferenceSequenceMode::External`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::ReferenceSequenceMode};
+    /// let builder = cram::writer::Builder::default()
+    ///     .set_reference_sequence_mode(ReferenceSequenceMode::Embedded);
+    /// ```
+    pub fn set_reference_sequence_mode(mut self, mode: ReferenceSequenceMode) -> Self {
+        self.options.reference_sequence_mode = mode;
+        self
+    }
+
+    /// Sets the maximum number of records per slice.
+    ///
+    /// The default is 10240.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram as cram;
+    ///
+    /// let builder = cram::writer::Builder::default()
+    ///     .set_records_per_slice(NonZeroUsize::try_from(25000)?);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_records_per_slice(mut self, records_per_slice: NonZeroUsize) -> Self {
+        self.options.records_per_slice = records_per_slice;
+        self
+    }
+
+    /// Sets the maximum number of slices per container.
+    ///
+    /// The default is 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram as cram;
+    ///
+    /// let builder = cram::writer::Builder::default()
+    ///     .set_slices_per_container(NonZeroUsize::try_from(4)?);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_slices_per_container(mut self, slices_per_container: NonZeroUsize) -> Self {
+        self.options.slices_per_container = slices_per_container;
+        self
+    }
+
+    /// Sets whether to pack records of differing reference sequences into multi-reference
+    /// slices.
+    ///
+    /// This is typically useful for unsorted, query name-sorted, or unmapped data. If `false`, a
+    /// change in reference sequence ends both the current slice and container, i.e., each slice
+    /// and container refers to a single reference sequence (or none).
+    ///
+    /// The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::writer::Builder::default().use_multi_reference_slices(false);
+    /// ```
+    pub fn use_multi_reference_slices(mut self, value: bool) -> Self {
+        self.options.multi_reference_slices = value;
+        self
+    }
+
     /// Sets the block content-encoder map.
     ///
     /// # Examples
@@ -87,6 +165,23 @@ impl Builder {
         self
     }
 
+    /// Sets the trial compression configuration.
+    ///
+    /// If set, the encoders of external blocks are selected by trial compression rather than the
+    /// block content-encoder map. See [`TrialCompression`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::TrialCompression};
+    /// let builder = cram::writer::Builder::default()
+    ///     .set_trial_compression(TrialCompression::small());
+    /// ```
+    pub fn set_trial_compression(mut self, trial_compression: TrialCompression) -> Self {
+        self.options.trial_compression = Some(trial_compression);
+        self
+    }
+
     /// Builds a CRAM writer.
     ///
     /// # Examples
@@ -99,14 +194,25 @@ impl Builder {
     where
         W: Write,
     {
-        if uses_cram_3_1_codecs(&self.options.block_content_encoder_map) {
+        if uses_cram_3_1_codecs(&self.options.block_content_encoder_map)
+            || self
+                .options
+                .trial_compression
+                .as_ref()
+                .map(|t| t.candidates().iter().any(is_cram_3_1_codec))
+                .unwrap_or(false)
+        {
             self.options.version = Version::new(3, 1);
         }
 
+        let block_content_encoder_selector =
+            self.options.trial_compression.clone().map(Selector::new);
+
         Writer {
             inner: writer,
             reference_sequence_repository: self.reference_sequence_repository,
             options: self.options,
+            block_content_encoder_selector,
             data_container_builder: DataContainer::builder(0),
             record_counter: 0,
         }
@@ -114,13 +220,6 @@ impl Builder {
 }
 
 pub fn uses_cram_3_1_codecs(block_content_encoder_map: &BlockContentEncoderMap) -> bool {
-    fn is_cram_3_1_codec(encoder: &Encoder) -> bool {
-        matches!(
-            encoder,
-            Encoder::RansNx16(_) | Encoder::AdaptiveArithmeticCoding(_) | Encoder::NameTokenizer
-        )
-    }
-
     if let Some(encoder) = block_content_encoder_map.core_data_encoder() {
         if is_cram_3_1_codec(encoder) {
             return true;
@@ -135,6 +234,16 @@ pub fn uses_cram_3_1_codecs(block_content_encoder_map: &BlockContentEncoderMap)
         .any(is_cram_3_1_codec)
 }
 
+pub fn is_cram_3_1_codec(encoder: &Encoder) -> bool {
+    matches!(
+        encoder,
+        Encoder::RansNx16(_)
+            | Encoder::AdaptiveArithmeticCoding(_)
+            | Encoder::NameTokenizer(_)
+            | Encoder::Fqzcomp(_)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,7 +53,7 @@ fn build_container(
     let mut landmarks = Vec::new();
 
     let container_reference_sequence_context =
-        build_container_reference_sequence_context(data_container.slices())?;
+        build_container_reference_sequence_context(data_container.slices());
 
     let mut container_record_count = 0;
     let container_record_counter = data_container
@@ -109,9 +109,7 @@ fn build_container(
     Ok((header, blocks))
 }
 
-fn build_container_reference_sequence_context(
-    slices: &[Slice],
-) -> io::Result<ReferenceSequenceContext> {
+fn build_container_reference_sequence_context(slices: &[Slice]) -> ReferenceSequenceContext {
     assert!(!slices.is_empty());
 
     let first_slice = slices.first().expect("slices cannot be empty");
@@ -148,17 +146,11 @@ fn build_container_reference_sequence_context(
                 );
             }
             (ReferenceSequenceContext::None, ReferenceSequenceContext::None) => {}
-            (ReferenceSequenceContext::Many, ReferenceSequenceContext::Many) => {}
-            _ => {
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!(
-                        "invalid slice reference sequence context: expected {container_reference_sequence_context:?}, got {slice_reference_sequence_context:?}"
-                    ),
-                ));
-            }
+            // Slices with differing reference sequence contexts make a multi-reference
+            // container.
+            _ => container_reference_sequence_context = ReferenceSequenceContext::Many,
         }
     }
 
-    Ok(container_reference_sequence_context)
+    container_reference_sequence_context
 }
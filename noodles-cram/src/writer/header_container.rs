@@ -6,14 +6,20 @@ use bytes::BufMut;
 use noodles_sam as sam;
 
 use self::header::write_header;
-use super::container::write_block;
+use super::{container::write_block, ReferenceSequenceMode};
 use crate::container::{block::ContentType, Block};
 
-pub fn write_header_container<W>(writer: &mut W, header: &sam::Header) -> io::Result<()>
+pub fn write_header_container<W>(
+    writer: &mut W,
+    header: &sam::Header,
+    reference_sequence_mode: ReferenceSequenceMode,
+) -> io::Result<()>
 where
     W: Write,
 {
-    validate_reference_sequences(header.reference_sequences())?;
+    if reference_sequence_mode != ReferenceSequenceMode::None {
+        validate_reference_sequences(header.reference_sequences())?;
+    }
 
     let header_data = header.to_string().into_bytes();
     let header_data_len = i32::try_from(header_data.len())
@@ -65,7 +71,7 @@ mod tests {
         let header = sam::Header::builder().set_header(header_header).build();
 
         let mut buf = Vec::new();
-        write_header_container(&mut buf, &header)?;
+        write_header_container(&mut buf, &header, ReferenceSequenceMode::default())?;
 
         let mut expected = Vec::new();
 
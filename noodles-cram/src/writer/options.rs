@@ -1,11 +1,29 @@
+use std::num::NonZeroUsize;
+
+use super::{ReferenceSequenceMode, TrialCompression};
 use crate::{data_container::BlockContentEncoderMap, file_definition::Version};
 
+pub(crate) const DEFAULT_RECORDS_PER_SLICE: NonZeroUsize = match NonZeroUsize::new(10240) {
+    Some(n) => n,
+    None => unreachable!(),
+};
+
+pub(crate) const DEFAULT_SLICES_PER_CONTAINER: NonZeroUsize = match NonZeroUsize::new(1) {
+    Some(n) => n,
+    None => unreachable!(),
+};
+
 #[derive(Clone, Debug)]
 pub struct Options {
     pub preserve_read_names: bool,
     pub encode_alignment_start_positions_as_deltas: bool,
+    pub reference_sequence_mode: ReferenceSequenceMode,
+    pub records_per_slice: NonZeroUsize,
+    pub slices_per_container: NonZeroUsize,
+    pub multi_reference_slices: bool,
     pub version: Version,
     pub block_content_encoder_map: BlockContentEncoderMap,
+    pub trial_compression: Option<TrialCompression>,
 }
 
 impl Default for Options {
@@ -13,8 +31,13 @@ impl Default for Options {
         Self {
             preserve_read_names: true,
             encode_alignment_start_positions_as_deltas: true,
+            reference_sequence_mode: ReferenceSequenceMode::default(),
+            records_per_slice: DEFAULT_RECORDS_PER_SLICE,
+            slices_per_container: DEFAULT_SLICES_PER_CONTAINER,
+            multi_reference_slices: true,
             version: Version::default(),
             block_content_encoder_map: BlockContentEncoderMap::default(),
+            trial_compression: None,
         }
     }
 }
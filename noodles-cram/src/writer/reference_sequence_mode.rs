@@ -0,0 +1,38 @@
+/// A CRAM writer reference sequence mode.
+///
+/// This determines how, if at all, reference sequences are used when writing slices.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReferenceSequenceMode {
+    /// Reference sequences are external to the file.
+    ///
+    /// Reference sequences are read from the reference sequence repository and are required to
+    /// decode the output (`RR` = `true`).
+    #[default]
+    External,
+    /// The reference sequence span of each single reference slice is embedded in the slice.
+    ///
+    /// Reference sequences are read from the reference sequence repository, but the output does
+    /// not require an external reference to decode (`RR` = `false`).
+    Embedded,
+    /// No reference sequences are used.
+    ///
+    /// Sequences are encoded without a reference (`RR` = `false`), and the reference sequence
+    /// repository is not read.
+    None,
+}
+
+impl ReferenceSequenceMode {
+    /// Returns whether this mode requires an external reference to decode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::writer::ReferenceSequenceMode;
+    /// assert!(ReferenceSequenceMode::External.is_reference_required());
+    /// assert!(!ReferenceSequenceMode::Embedded.is_reference_required());
+    /// assert!(!ReferenceSequenceMode::None.is_reference_required());
+    /// ```
+    pub fn is_reference_required(self) -> bool {
+        matches!(self, Self::External)
+    }
+}
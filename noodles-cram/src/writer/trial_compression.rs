@@ -0,0 +1,447 @@
+use std::{collections::HashMap, io, num::NonZeroUsize};
+
+use bytes::Bytes;
+
+use crate::{
+    codecs::{aac, fqzcomp, name_tokenizer, rans_4x8, rans_nx16, Encoder},
+    container::block::{self, CompressionMethod},
+    data_container::compression_header::data_series_encoding_map::DataSeries,
+    Record,
+};
+
+const DEFAULT_TRIAL_COUNT: NonZeroUsize = match NonZeroUsize::new(3) {
+    Some(n) => n,
+    None => unreachable!(),
+};
+
+const DEFAULT_REEVALUATION_INTERVAL: NonZeroUsize = match NonZeroUsize::new(64) {
+    Some(n) => n,
+    None => unreachable!(),
+};
+
+/// A CRAM writer trial compression configuration.
+///
+/// When set, each external data series and tag values block is trial compressed with every
+/// candidate encoder for the first few slices. The encoder with the smallest total output for
+/// that block is then used for subsequent slices, and the candidates are periodically
+/// reevaluated.
+///
+/// This replaces the external block encoders of the block content-encoder map. The core data
+/// block encoder is unchanged.
+///
+/// The name tokenizer is only a candidate for read names, and fqzcomp is only a candidate for
+/// quality scores.
+#[derive(Clone, Debug)]
+pub struct TrialCompression {
+    candidates: Vec<Encoder>,
+    trial_count: NonZeroUsize,
+    reevaluation_interval: NonZeroUsize,
+}
+
+impl TrialCompression {
+    /// Creates a trial compression configuration with the given candidate encoders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{codecs::Encoder, writer::TrialCompression};
+    ///
+    /// let trial_compression = TrialCompression::new(vec![
+    ///     Encoder::Gzip(Default::default()),
+    ///     Encoder::Bzip2(Default::default()),
+    /// ]);
+    /// ```
+    pub fn new(candidates: Vec<Encoder>) -> Self {
+        Self {
+            candidates,
+            trial_count: DEFAULT_TRIAL_COUNT,
+            reevaluation_interval: DEFAULT_REEVALUATION_INTERVAL,
+        }
+    }
+
+    /// Creates a trial compression configuration that favors speed.
+    ///
+    /// The candidates are fast gzip and order-0 rANS 4x8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::writer::TrialCompression;
+    /// let trial_compression = TrialCompression::fast();
+    /// ```
+    pub fn fast() -> Self {
+        Self::new(vec![
+            Encoder::Gzip(flate2::Compression::fast()),
+            Encoder::Rans4x8(rans_4x8::Order::Zero),
+        ])
+    }
+
+    /// Creates a trial compression configuration that balances speed and size.
+    ///
+    /// The candidates are gzip, rANS Nx16 (orders 0 and 1), and the name tokenizer (level 5).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::writer::TrialCompression;
+    /// let trial_compression = TrialCompression::normal();
+    /// ```
+    pub fn normal() -> Self {
+        Self::new(vec![
+            Encoder::Gzip(flate2::Compression::default()),
+            Encoder::RansNx16(rans_nx16::Flags::empty()),
+            Encoder::RansNx16(rans_nx16::Flags::ORDER),
+            Encoder::NameTokenizer(name_tokenizer::Options::new(5)),
+        ])
+    }
+
+    /// Creates a trial compression configuration that favors size.
+    ///
+    /// This extends [`Self::normal`] with bzip2, the adaptive arithmetic coder (orders 0 and 1),
+    /// and fqzcomp (level 5).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::writer::TrialCompression;
+    /// let trial_compression = TrialCompression::small();
+    /// ```
+    pub fn small() -> Self {
+        let mut trial_compression = Self::normal();
+
+        trial_compression.candidates.extend([
+            Encoder::Bzip2(bzip2::Compression::default()),
+            Encoder::AdaptiveArithmeticCoding(aac::Flags::empty()),
+            Encoder::AdaptiveArithmeticCoding(aac::Flags::ORDER),
+            Encoder::Fqzcomp(fqzcomp::Options::from_level(5)),
+        ]);
+
+        trial_compression
+    }
+
+    /// Creates a trial compression configuration that favors size over speed.
+    ///
+    /// This extends [`Self::small`] with best gzip, best bzip2, xz, striped and run-length
+    /// encoded rANS Nx16 variants, the name tokenizer (level 9, with and without the arithmetic
+    /// coder), and fqzcomp (level 9).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::writer::TrialCompression;
+    /// let trial_compression = TrialCompression::archive();
+    /// ```
+    pub fn archive() -> Self {
+        let mut trial_compression = Self::small();
+
+        trial_compression.candidates.extend([
+            Encoder::Gzip(flate2::Compression::best()),
+            Encoder::Bzip2(bzip2::Compression::best()),
+            Encoder::Lzma(9),
+            Encoder::RansNx16(rans_nx16::Flags::ORDER | rans_nx16::Flags::STRIPE),
+            Encoder::RansNx16(rans_nx16::Flags::RLE),
+            Encoder::RansNx16(rans_nx16::Flags::ORDER | rans_nx16::Flags::RLE),
+            Encoder::NameTokenizer(name_tokenizer::Options::new(9)),
+            Encoder::NameTokenizer(name_tokenizer::Options::new(9).use_arithmetic_coder(true)),
+            Encoder::Fqzcomp(fqzcomp::Options::from_level(9)),
+        ]);
+
+        trial_compression
+    }
+
+    /// Returns the candidate encoders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{codecs::Encoder, writer::TrialCompression};
+    /// let trial_compression = TrialCompression::new(vec![Encoder::Gzip(Default::default())]);
+    /// assert_eq!(trial_compression.candidates().len(), 1);
+    /// ```
+    pub fn candidates(&self) -> &[Encoder] {
+        &self.candidates
+    }
+
+    /// Sets the number of slices each block is trial compressed for before an encoder is chosen.
+    ///
+    /// The default is 3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram::writer::TrialCompression;
+    ///
+    /// let trial_compression = TrialCompression::normal()
+    ///     .set_trial_count(NonZeroUsize::try_from(1)?);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_trial_count(mut self, trial_count: NonZeroUsize) -> Self {
+        self.trial_count = trial_count;
+        self
+    }
+
+    /// Sets the number of slices a chosen encoder is used for before the candidates are
+    /// reevaluated.
+    ///
+    /// The default is 64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram::writer::TrialCompression;
+    ///
+    /// let trial_compression = TrialCompression::normal()
+    ///     .set_reevaluation_interval(NonZeroUsize::try_from(128)?);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_reevaluation_interval(mut self, reevaluation_interval: NonZeroUsize) -> Self {
+        self.reevaluation_interval = reevaluation_interval;
+        self
+    }
+}
+
+impl Default for TrialCompression {
+    fn default() -> Self {
+        Self::normal()
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    remaining_trial_count: usize,
+    sizes: Vec<Option<usize>>,
+    selected_candidate: Option<usize>,
+    use_count: usize,
+}
+
+/// A block content encoder selector.
+///
+/// This holds the per block content ID trial results across containers.
+#[derive(Debug)]
+pub(crate) struct Selector {
+    trial_compression: TrialCompression,
+    states: HashMap<block::ContentId, State>,
+}
+
+impl Selector {
+    pub fn new(trial_compression: TrialCompression) -> Self {
+        Self {
+            trial_compression,
+            states: HashMap::new(),
+        }
+    }
+
+    /// Compresses the given data, selecting the encoder by trial compression.
+    ///
+    /// `records` are used by fqzcomp and are only set when all quality scores are stored as
+    /// arrays.
+    pub fn compress_and_set_data(
+        &mut self,
+        builder: block::Builder,
+        block_content_id: block::ContentId,
+        src: Vec<u8>,
+        records: Option<&[Record]>,
+    ) -> io::Result<block::Builder> {
+        let trial_compression = &self.trial_compression;
+        let candidate_count = trial_compression.candidates.len();
+
+        let state = self
+            .states
+            .entry(block_content_id)
+            .or_insert_with(|| State {
+                remaining_trial_count: trial_compression.trial_count.get(),
+                ..Default::default()
+            });
+
+        let is_applicable =
+            |encoder: &Encoder| is_applicable(encoder, block_content_id, records.is_some());
+
+        let (compression_method, data) = if state.remaining_trial_count > 0 {
+            state.sizes.resize(candidate_count, Some(0));
+
+            let mut best: Option<(CompressionMethod, Vec<u8>)> = None;
+
+            for (encoder, total_size) in trial_compression.candidates.iter().zip(&mut state.sizes) {
+                let result = if is_applicable(encoder) {
+                    encode(encoder, &src, records).ok()
+                } else {
+                    None
+                };
+
+                match result {
+                    Some((compression_method, data)) => {
+                        *total_size = total_size.map(|n| n + data.len());
+
+                        if best
+                            .as_ref()
+                            .map(|(_, b)| data.len() < b.len())
+                            .unwrap_or(true)
+                        {
+                            best = Some((compression_method, data));
+                        }
+                    }
+                    None => *total_size = None,
+                }
+            }
+
+            state.remaining_trial_count -= 1;
+
+            if state.remaining_trial_count == 0 {
+                state.selected_candidate = state
+                    .sizes
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, size)| size.map(|n| (i, n)))
+                    .min_by_key(|(_, n)| *n)
+                    .map(|(i, _)| i);
+
+                state.sizes.clear();
+                state.use_count = 0;
+            }
+
+            best.unwrap_or_else(|| (CompressionMethod::None, src.clone()))
+        } else {
+            state.use_count += 1;
+
+            if state.use_count >= trial_compression.reevaluation_interval.get() {
+                state.remaining_trial_count = trial_compression.trial_count.get();
+            }
+
+            match state
+                .selected_candidate
+                .map(|i| &trial_compression.candidates[i])
+                .filter(|encoder| is_applicable(encoder))
+            {
+                Some(encoder) => encode(encoder, &src, records)?,
+                None => (CompressionMethod::None, src.clone()),
+            }
+        };
+
+        Ok(builder
+            .set_uncompressed_len(src.len())
+            .set_compression_method(compression_method)
+            .set_data(Bytes::from(data)))
+    }
+}
+
+fn is_applicable(encoder: &Encoder, block_content_id: block::ContentId, has_records: bool) -> bool {
+    match encoder {
+        Encoder::NameTokenizer(_) => {
+            block_content_id == block::ContentId::from(DataSeries::ReadNames)
+        }
+        Encoder::Fqzcomp(_) => {
+            has_records && block_content_id == block::ContentId::from(DataSeries::QualityScores)
+        }
+        _ => true,
+    }
+}
+
+fn encode(
+    encoder: &Encoder,
+    src: &[u8],
+    records: Option<&[Record]>,
+) -> io::Result<(CompressionMethod, Vec<u8>)> {
+    use crate::data_container::slice::builder::encode_quality_scores;
+
+    match (encoder, records) {
+        (Encoder::Fqzcomp(options), Some(records)) => encode_quality_scores(options, records, src)
+            .map(|data| (CompressionMethod::Fqzcomp, data)),
+        _ => block::encode(encoder, src),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress_and_set_data() -> io::Result<()> {
+        let trial_compression = TrialCompression::new(vec![
+            Encoder::Gzip(Default::default()),
+            Encoder::Rans4x8(rans_4x8::Order::Zero),
+            Encoder::NameTokenizer(Default::default()),
+        ])
+        .set_trial_count(NonZeroUsize::new(1).unwrap())
+        .set_reevaluation_interval(NonZeroUsize::new(2).unwrap());
+
+        let mut selector = Selector::new(trial_compression);
+
+        let block_content_id = block::ContentId::from(DataSeries::MappingQualities);
+        let src = vec![0x3c; 1024];
+
+        let compress = |selector: &mut Selector| {
+            selector
+                .compress_and_set_data(
+                    block::Builder::default()
+                        .set_content_type(block::ContentType::ExternalData)
+                        .set_content_id(block_content_id),
+                    block_content_id,
+                    src.clone(),
+                    None,
+                )
+                .map(|builder| builder.build())
+        };
+
+        // trial
+        let block = compress(&mut selector)?;
+        assert_eq!(&block.decompressed_data()?[..], &src[..]);
+        assert!(selector.states[&block_content_id]
+            .selected_candidate
+            .is_some());
+        assert_eq!(selector.states[&block_content_id].remaining_trial_count, 0);
+
+        let compression_method = block.compression_method();
+
+        // selected
+        let block = compress(&mut selector)?;
+        assert_eq!(block.compression_method(), compression_method);
+        assert_eq!(&block.decompressed_data()?[..], &src[..]);
+
+        // selected, then reevaluation is scheduled
+        compress(&mut selector)?;
+        assert_eq!(selector.states[&block_content_id].remaining_trial_count, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_applicable() {
+        let read_names = block::ContentId::from(DataSeries::ReadNames);
+        let quality_scores = block::ContentId::from(DataSeries::QualityScores);
+
+        assert!(is_applicable(
+            &Encoder::NameTokenizer(Default::default()),
+            read_names,
+            false
+        ));
+        assert!(!is_applicable(
+            &Encoder::NameTokenizer(Default::default()),
+            quality_scores,
+            false
+        ));
+
+        assert!(is_applicable(
+            &Encoder::Fqzcomp(Default::default()),
+            quality_scores,
+            true
+        ));
+        assert!(!is_applicable(
+            &Encoder::Fqzcomp(Default::default()),
+            quality_scores,
+            false
+        ));
+        assert!(!is_applicable(
+            &Encoder::Fqzcomp(Default::default()),
+            read_names,
+            true
+        ));
+
+        assert!(is_applicable(
+            &Encoder::Gzip(Default::default()),
+            read_names,
+            false
+        ));
+    }
+}
@@ -1,13 +1,14 @@
 //! Binning index utilities.
 
 mod reference_sequence_ext;
+mod statistics;
 
-pub use reference_sequence_ext::ReferenceSequenceExt;
+pub use self::{reference_sequence_ext::ReferenceSequenceExt, statistics::Statistics};
 
 use std::io;
 
 use noodles_bgzf as bgzf;
-use noodles_core::region::Interval;
+use noodles_core::{region::Interval, Position};
 
 use super::index::reference_sequence::bin::Chunk;
 
@@ -27,6 +28,173 @@ pub trait BinningIndex {
     where
         I: Into<Interval>;
 
+    /// Returns the linear index of a reference sequence.
+    ///
+    /// Each entry is the start position of a fixed-size window and the virtual position of the
+    /// first record that overlaps it, in position order. Windows without records are omitted.
+    ///
+    /// The default implementation returns an empty list, i.e., the index has no linear index.
+    fn linear_index(
+        &self,
+        reference_sequence_id: usize,
+    ) -> io::Result<Vec<(Position, bgzf::VirtualPosition)>> {
+        let _ = reference_sequence_id;
+        Ok(Vec::new())
+    }
+
+    /// Returns the merged chunks that overlap with any of the given intervals.
+    ///
+    /// Each query is a reference sequence ID and an interval. The chunks of all queries (see
+    /// [`Self::query`]) are merged (see [`merge_chunks`]), i.e., the result is a sorted list of
+    /// non-overlapping chunks, and each compressed block is covered at most once.
+    fn query_intervals<I, J>(&self, queries: I) -> io::Result<Vec<Chunk>>
+    where
+        I: IntoIterator<Item = (usize, J)>,
+        J: Into<Interval>,
+    {
+        let mut chunks = Vec::new();
+
+        for (reference_sequence_id, interval) in queries {
+            let query_chunks = self.query(reference_sequence_id, interval)?;
+            chunks.extend(query_chunks);
+        }
+
+        Ok(merge_chunks(&chunks))
+    }
+
+    /// Returns the index statistics of each reference sequence.
+    ///
+    /// This includes the same data as `samtools idxstats`, i.e., the number of mapped and
+    /// unmapped records, and the start and end virtual positions of the records of each reference
+    /// sequence, as read from the index metadata. Counts are 0 and positions are `None` if the
+    /// reference sequence does not have metadata, e.g., when it has no records.
+    ///
+    /// Each entry also has the estimated number of compressed bytes of the records of the
+    /// reference sequence (see [`Self::estimated_compressed_size`]).
+    ///
+    /// The list is parallel to the list of reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::{
+    ///     self as csi,
+    ///     index::{
+    ///         reference_sequence::{bin::Chunk, Bin, Metadata},
+    ///         ReferenceSequence,
+    ///     },
+    ///     BinningIndex,
+    /// };
+    ///
+    /// let start_position = bgzf::VirtualPosition::try_from((8, 0))?;
+    /// let end_position = bgzf::VirtualPosition::try_from((21, 0))?;
+    /// let metadata = Metadata::new(start_position, end_position, 55, 0);
+    ///
+    /// let chunks = vec![Chunk::new(start_position, end_position)];
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_reference_sequences(vec![
+    ///         ReferenceSequence::new(
+    ///             vec![Bin::new(0, bgzf::VirtualPosition::default(), chunks)],
+    ///             Some(metadata),
+    ///         ),
+    ///         ReferenceSequence::new(Vec::new(), None),
+    ///     ])
+    ///     .build();
+    ///
+    /// let statistics = index.statistics()?;
+    /// assert_eq!(statistics.len(), 2);
+    ///
+    /// assert_eq!(statistics[0].mapped_record_count(), 55);
+    /// assert_eq!(statistics[0].unmapped_record_count(), 0);
+    /// assert_eq!(statistics[0].start_position(), Some(start_position));
+    /// assert_eq!(statistics[0].end_position(), Some(end_position));
+    /// assert_eq!(statistics[0].estimated_compressed_size(), 13);
+    ///
+    /// assert_eq!(statistics[1].mapped_record_count(), 0);
+    /// assert!(statistics[1].start_position().is_none());
+    /// assert_eq!(statistics[1].estimated_compressed_size(), 0);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn statistics(&self) -> io::Result<Vec<Statistics>> {
+        self.reference_sequences()
+            .iter()
+            .enumerate()
+            .map(|(reference_sequence_id, reference_sequence)| {
+                let metadata = reference_sequence.metadata();
+
+                Ok(Statistics {
+                    mapped_record_count: metadata
+                        .map(|m| m.mapped_record_count())
+                        .unwrap_or_default(),
+                    unmapped_record_count: metadata
+                        .map(|m| m.unmapped_record_count())
+                        .unwrap_or_default(),
+                    start_position: metadata.map(|m| m.start_position()),
+                    end_position: metadata.map(|m| m.end_position()),
+                    estimated_compressed_size: self
+                        .estimated_compressed_size(reference_sequence_id, ..)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the estimated number of compressed bytes to read to query the given interval.
+    ///
+    /// This is computed from the merged chunks that overlap the interval (see [`Self::query`]).
+    /// Since a chunk only spans compressed block boundaries, chunks that start and end in the
+    /// same block are counted as 0 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::{
+    ///     self as csi,
+    ///     index::{
+    ///         reference_sequence::{bin::Chunk, Bin},
+    ///         ReferenceSequence,
+    ///     },
+    ///     BinningIndex,
+    /// };
+    ///
+    /// let chunks = vec![Chunk::new(
+    ///     bgzf::VirtualPosition::try_from((8, 0))?,
+    ///     bgzf::VirtualPosition::try_from((21, 0))?,
+    /// )];
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(
+    ///         vec![Bin::new(0, bgzf::VirtualPosition::default(), chunks)],
+    ///         None,
+    ///     )])
+    ///     .build();
+    ///
+    /// assert_eq!(index.estimated_compressed_size(0, ..)?, 13);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn estimated_compressed_size<I>(
+        &self,
+        reference_sequence_id: usize,
+        interval: I,
+    ) -> io::Result<u64>
+    where
+        I: Into<Interval>,
+    {
+        let chunks = self.query(reference_sequence_id, interval)?;
+
+        Ok(merge_chunks(&chunks)
+            .iter()
+            .map(|chunk| {
+                chunk
+                    .end()
+                    .compressed()
+                    .saturating_sub(chunk.start().compressed())
+            })
+            .sum())
+    }
+
     /// Returns the start position of the first record in the last linear bin.
     ///
     /// This is the closest position to the unplaced, unmapped records, if any, that is available
@@ -218,4 +386,91 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_estimated_compressed_size() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::index::{reference_sequence::Bin, ReferenceSequence};
+
+        fn build_chunk(start: u64, end: u64) -> Result<Chunk, Box<dyn std::error::Error>> {
+            Ok(Chunk::new(
+                bgzf::VirtualPosition::try_from((start, 0))?,
+                bgzf::VirtualPosition::try_from((end, 0))?,
+            ))
+        }
+
+        let bins = vec![
+            Bin::new(
+                0,
+                bgzf::VirtualPosition::default(),
+                vec![build_chunk(5, 13)?, build_chunk(55, 89)?],
+            ),
+            Bin::new(
+                1,
+                bgzf::VirtualPosition::default(),
+                vec![build_chunk(8, 21)?],
+            ),
+        ];
+
+        let index = crate::Index::builder()
+            .set_reference_sequences(vec![ReferenceSequence::new(bins, None)])
+            .build();
+
+        // 5..21 (merged) + 55..89
+        assert_eq!(index.estimated_compressed_size(0, ..)?, 50);
+
+        assert!(index.estimated_compressed_size(1, ..).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_intervals() -> io::Result<()> {
+        use crate::index::ReferenceSequence;
+
+        // Returns the chunk from `build_chunks` at the reference sequence ID, regardless of the
+        // interval.
+        struct Index(Vec<Chunk>);
+
+        impl BinningIndex for Index {
+            type ReferenceSequence = ReferenceSequence;
+
+            fn reference_sequences(&self) -> &[Self::ReferenceSequence] {
+                &[]
+            }
+
+            fn unplaced_unmapped_record_count(&self) -> Option<u64> {
+                None
+            }
+
+            fn query<I>(&self, reference_sequence_id: usize, _: I) -> io::Result<Vec<Chunk>>
+            where
+                I: Into<Interval>,
+            {
+                Ok(vec![self.0[reference_sequence_id]])
+            }
+        }
+
+        let index = Index(build_chunks());
+
+        let actual = index.query_intervals([(3, ..), (0, ..), (1, ..), (4, ..)])?;
+
+        let expected = [
+            Chunk::new(
+                bgzf::VirtualPosition::from(2),
+                bgzf::VirtualPosition::from(5),
+            ),
+            Chunk::new(
+                bgzf::VirtualPosition::from(9),
+                bgzf::VirtualPosition::from(15),
+            ),
+        ];
+
+        assert_eq!(actual, expected);
+
+        assert!(index
+            .query_intervals(Vec::<(usize, Interval)>::new())?
+            .is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,89 @@
+use noodles_bgzf as bgzf;
+
+/// Binning index statistics of a reference sequence.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Statistics {
+    pub(super) mapped_record_count: u64,
+    pub(super) unmapped_record_count: u64,
+    pub(super) start_position: Option<bgzf::VirtualPosition>,
+    pub(super) end_position: Option<bgzf::VirtualPosition>,
+    pub(super) estimated_compressed_size: u64,
+}
+
+impl Statistics {
+    /// Returns the number of mapped records.
+    ///
+    /// This is 0 if the reference sequence does not have metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::binning_index::Statistics;
+    /// let statistics = Statistics::default();
+    /// assert_eq!(statistics.mapped_record_count(), 0);
+    /// ```
+    pub fn mapped_record_count(&self) -> u64 {
+        self.mapped_record_count
+    }
+
+    /// Returns the number of unmapped records.
+    ///
+    /// This is 0 if the reference sequence does not have metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::binning_index::Statistics;
+    /// let statistics = Statistics::default();
+    /// assert_eq!(statistics.unmapped_record_count(), 0);
+    /// ```
+    pub fn unmapped_record_count(&self) -> u64 {
+        self.unmapped_record_count
+    }
+
+    /// Returns the start virtual position of the reference sequence records.
+    ///
+    /// This is `None` if the reference sequence does not have metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::binning_index::Statistics;
+    /// let statistics = Statistics::default();
+    /// assert!(statistics.start_position().is_none());
+    /// ```
+    pub fn start_position(&self) -> Option<bgzf::VirtualPosition> {
+        self.start_position
+    }
+
+    /// Returns the end virtual position of the reference sequence records.
+    ///
+    /// This is `None` if the reference sequence does not have metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::binning_index::Statistics;
+    /// let statistics = Statistics::default();
+    /// assert!(statistics.end_position().is_none());
+    /// ```
+    pub fn end_position(&self) -> Option<bgzf::VirtualPosition> {
+        self.end_position
+    }
+
+    /// Returns the estimated number of compressed bytes of the reference sequence records.
+    ///
+    /// This is computed from the merged chunks of all bins of the reference sequence (see
+    /// [`super::BinningIndex::estimated_compressed_size`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::binning_index::Statistics;
+    /// let statistics = Statistics::default();
+    /// assert_eq!(statistics.estimated_compressed_size(), 0);
+    /// ```
+    pub fn estimated_compressed_size(&self) -> u64 {
+        self.estimated_compressed_size
+    }
+}
@@ -7,9 +7,10 @@ pub use self::{builder::Builder, reference_sequence::ReferenceSequence};
 
 use std::io;
 
+use noodles_bgzf as bgzf;
 use noodles_core::{region::Interval, Position};
 
-use super::{index::reference_sequence::bin::Chunk, BinningIndex};
+use super::{binning_index::optimize_chunks, index::reference_sequence::bin::Chunk, BinningIndex};
 
 /// A coordinate-sorted index (CSI).
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -117,6 +118,8 @@ impl BinningIndex for Index {
                 )
             })?;
 
+        let interval = interval.into();
+
         let query_bins = reference_sequence
             .query(self.min_shift(), self.depth(), interval)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
@@ -127,7 +130,28 @@ impl BinningIndex for Index {
             .copied()
             .collect();
 
-        Ok(chunks)
+        let start = interval.start().unwrap_or(Position::MIN);
+        let min_offset = reference_sequence.min_offset(self.min_shift(), self.depth(), start);
+        let merged_chunks = optimize_chunks(&chunks, min_offset);
+
+        Ok(merged_chunks)
+    }
+
+    fn linear_index(
+        &self,
+        reference_sequence_id: usize,
+    ) -> io::Result<Vec<(Position, bgzf::VirtualPosition)>> {
+        self.reference_sequences()
+            .get(reference_sequence_id)
+            .map(|reference_sequence| {
+                reference_sequence.linear_index(self.min_shift(), self.depth())
+            })
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid reference sequence ID: {reference_sequence_id}"),
+                )
+            })
     }
 }
 
@@ -165,3 +189,52 @@ where
         Ok((start, end))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::reference_sequence::Bin;
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        const FIRST_LEAF_BIN_ID: usize = 4681;
+
+        let bins = vec![
+            Bin::new(
+                0,
+                bgzf::VirtualPosition::default(),
+                vec![Chunk::new(
+                    bgzf::VirtualPosition::from(5),
+                    bgzf::VirtualPosition::from(13),
+                )],
+            ),
+            Bin::new(
+                FIRST_LEAF_BIN_ID,
+                bgzf::VirtualPosition::from(21),
+                vec![Chunk::new(
+                    bgzf::VirtualPosition::from(21),
+                    bgzf::VirtualPosition::from(34),
+                )],
+            ),
+        ];
+
+        let index = Index::builder()
+            .set_reference_sequences(vec![ReferenceSequence::new(bins, None)])
+            .build();
+
+        let start = Position::try_from(1)?;
+        let end = Position::try_from(8)?;
+
+        // The root bin chunk ends before the minimum offset of the leaf bin.
+        let actual = index.query(0, start..=end)?;
+        let expected = [Chunk::new(
+            bgzf::VirtualPosition::from(21),
+            bgzf::VirtualPosition::from(34),
+        )];
+        assert_eq!(actual, expected);
+
+        assert!(index.query(1, start..=end).is_err());
+
+        Ok(())
+    }
+}
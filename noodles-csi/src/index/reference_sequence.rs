@@ -135,6 +135,31 @@ impl ReferenceSequence {
 
         bgzf::VirtualPosition::default()
     }
+
+    /// Returns the linear index built from the loffsets of the bins in the last level.
+    pub(super) fn linear_index(
+        &self,
+        min_shift: u8,
+        depth: u8,
+    ) -> Vec<(Position, bgzf::VirtualPosition)> {
+        let first_id = ((1 << (3 * usize::from(depth))) - 1) / 7;
+        let last_id = Bin::max_id(depth);
+
+        let mut linear_index: Vec<_> = self
+            .bins
+            .iter()
+            .filter(|bin| (first_id..last_id).contains(&bin.id()))
+            .filter(|bin| bin.loffset() != bgzf::VirtualPosition::default())
+            .filter_map(|bin| {
+                Position::new(((bin.id() - first_id) << min_shift) + 1)
+                    .map(|start| (start, bin.loffset()))
+            })
+            .collect();
+
+        linear_index.sort_unstable_by_key(|(start, _)| *start);
+
+        linear_index
+    }
 }
 
 impl ReferenceSequenceExt for ReferenceSequence {
@@ -283,6 +308,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_linear_index() -> Result<(), noodles_core::position::TryFromIntError> {
+        const MIN_SHIFT: u8 = 4;
+        const DEPTH: u8 = 2;
+
+        let reference_sequence = ReferenceSequence::new(
+            vec![
+                Bin::new(11, bgzf::VirtualPosition::from(13), Vec::new()),
+                Bin::new(0, bgzf::VirtualPosition::from(5), Vec::new()),
+                Bin::new(10, bgzf::VirtualPosition::default(), Vec::new()),
+                Bin::new(9, bgzf::VirtualPosition::from(8), Vec::new()),
+            ],
+            None,
+        );
+
+        let actual = reference_sequence.linear_index(MIN_SHIFT, DEPTH);
+
+        let expected = [
+            (Position::try_from(1)?, bgzf::VirtualPosition::from(8)),
+            (Position::try_from(33)?, bgzf::VirtualPosition::from(13)),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
     #[test]
     fn test_reg2bin() -> Result<(), noodles_core::position::TryFromIntError> {
         const MIN_SHIFT: u8 = 4;
@@ -2,6 +2,8 @@ mod field;
 
 use std::{error, fmt, str::FromStr};
 
+use noodles_core::{self as core, region::Interval, region_list::Dictionary, Feature, Position};
+
 use self::field::Field;
 
 const FIELD_DELIMITER: char = '\t';
@@ -103,6 +105,47 @@ impl Record {
     }
 }
 
+impl Feature for Record {
+    fn reference_sequence_name<'a>(&'a self, _: &'a ()) -> core::Result<&'a str> {
+        Ok(self.name())
+    }
+
+    fn interval(&self) -> core::Result<Interval> {
+        let end = usize::try_from(self.len())
+            .ok()
+            .and_then(Position::new)
+            .ok_or_else(|| core::Error::new(core::error::Kind::TryFrom, "invalid length"))?;
+
+        Ok(Interval::from(Position::MIN..=end))
+    }
+}
+
+impl<'a> FromIterator<&'a Record> for Dictionary {
+    /// Creates a reference sequence dictionary from FASTA index records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// use noodles_fasta::fai;
+    ///
+    /// let index = vec![fai::Record::new(String::from("sq0"), 8, 4, 80, 81)];
+    /// let dictionary: Dictionary = index.iter().collect();
+    ///
+    /// assert_eq!(dictionary.get("sq0"), Some(Some(8)));
+    /// ```
+    fn from_iter<T: IntoIterator<Item = &'a Record>>(iter: T) -> Self {
+        let mut dictionary = Self::new();
+
+        for record in iter {
+            let length = usize::try_from(record.len()).ok();
+            dictionary.insert(record.name(), length);
+        }
+
+        dictionary
+    }
+}
+
 /// An error returned when a raw FASTA index record fails to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -177,6 +220,17 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_region() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::new(String::from("sq0"), 8, 4, 80, 81);
+        assert_eq!(record.region(&())?, "sq0:1-8".parse()?);
+
+        let record = Record::new(String::from("sq0"), 0, 4, 80, 81);
+        assert!(record.interval().is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!(
@@ -3,5 +3,6 @@
 mod empty;
 mod indexed_reader;
 mod records;
+mod ref_cache;
 
-pub use self::{empty::Empty, indexed_reader::IndexedReader};
+pub use self::{empty::Empty, indexed_reader::IndexedReader, ref_cache::RefCache};
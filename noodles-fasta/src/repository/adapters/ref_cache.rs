@@ -0,0 +1,354 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+use md5::{Digest, Md5};
+
+use crate::{
+    record::{Definition, Sequence},
+    repository::Adapter,
+    Record,
+};
+
+const REF_PATH: &str = "REF_PATH";
+const REF_CACHE: &str = "REF_CACHE";
+
+/// A local reference cache adapter.
+///
+/// This resolves sequences by their MD5 checksums using htslib-style `REF_PATH`/`REF_CACHE` path
+/// templates, e.g., `/data/cache/%2s/%2s/%s`. In a template, `%Ns` is replaced with the next `N`
+/// characters of the lowercase hex-encoded checksum, and `%s`, with the remaining characters. If
+/// a template has no `%s`, `/%s` is appended.
+///
+/// Each file is expected to contain only the sequence. Whitespace is ignored. The MD5 checksum of
+/// the normalized sequence is verified on load.
+#[derive(Debug, Default)]
+pub struct RefCache {
+    templates: Vec<String>,
+    checksums: HashMap<String, [u8; 16]>,
+}
+
+impl RefCache {
+    /// Creates a local reference cache adapter with the given path templates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::repository::adapters::RefCache;
+    /// let adapter = RefCache::new(vec![String::from("/data/cache/%2s/%2s/%s")]);
+    /// ```
+    pub fn new(templates: Vec<String>) -> Self {
+        Self {
+            templates,
+            checksums: HashMap::new(),
+        }
+    }
+
+    /// Creates a local reference cache adapter using the `REF_CACHE` and `REF_PATH` environment
+    /// variables.
+    ///
+    /// `REF_CACHE` is searched first, followed by each entry of the colon-separated `REF_PATH`.
+    /// Remote (URL) entries in `REF_PATH` are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::repository::adapters::RefCache;
+    /// let adapter = RefCache::from_env();
+    /// ```
+    pub fn from_env() -> Self {
+        let ref_cache = env::var(REF_CACHE).ok();
+        let ref_path = env::var(REF_PATH).ok();
+        Self::new(parse_templates(ref_cache.as_deref(), ref_path.as_deref()))
+    }
+
+    /// Returns the path templates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::repository::adapters::RefCache;
+    /// let adapter = RefCache::new(vec![String::from("/data/cache/%2s/%2s/%s")]);
+    /// assert_eq!(adapter.templates(), [String::from("/data/cache/%2s/%2s/%s")]);
+    /// ```
+    pub fn templates(&self) -> &[String] {
+        &self.templates
+    }
+
+    /// Associates a sequence name with an MD5 checksum.
+    ///
+    /// Only sequences with known checksums can be resolved. These typically come from the `M5`
+    /// field of SAM header `@SQ` records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::repository::adapters::RefCache;
+    /// let mut adapter = RefCache::new(Vec::new());
+    /// adapter.insert("sq0", [
+    ///     0xf1, 0xf8, 0xf4, 0xbf, 0x41, 0x3b, 0x16, 0xad, 0x13, 0x57, 0x22, 0xaa, 0x45, 0x91,
+    ///     0x04, 0x3e,
+    /// ]);
+    /// ```
+    pub fn insert<N>(&mut self, name: N, checksum: [u8; 16])
+    where
+        N: Into<String>,
+    {
+        self.checksums.insert(name.into(), checksum);
+    }
+
+    fn resolve(&self, checksum: &[u8; 16]) -> Option<io::Result<Vec<u8>>> {
+        let hex_checksum = encode_hex(checksum);
+
+        for template in &self.templates {
+            let path = expand_template(template, &hex_checksum);
+
+            match fs::read(&path) {
+                Ok(buf) => return Some(Ok(buf)),
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+}
+
+impl Adapter for RefCache {
+    fn get(&mut self, name: &str) -> Option<io::Result<Record>> {
+        let checksum = self.checksums.get(name)?;
+
+        let buf = match self.resolve(checksum)? {
+            Ok(buf) => buf,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let sequence: Vec<_> = buf
+            .into_iter()
+            .filter(|b| !b.is_ascii_whitespace())
+            .collect();
+
+        let actual_checksum = calculate_normalized_sequence_digest(&sequence);
+
+        if &actual_checksum != checksum {
+            return Some(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "reference sequence checksum mismatch for {name}: expected {}, got {}",
+                    encode_hex(checksum),
+                    encode_hex(&actual_checksum)
+                ),
+            )));
+        }
+
+        let definition = Definition::new(name, None);
+        Some(Ok(Record::new(definition, Sequence::from(sequence))))
+    }
+}
+
+fn parse_templates(ref_cache: Option<&str>, ref_path: Option<&str>) -> Vec<String> {
+    let mut templates = Vec::new();
+
+    if let Some(s) = ref_cache.filter(|s| !s.is_empty()) {
+        templates.push(s.into());
+    }
+
+    if let Some(s) = ref_path {
+        templates.extend(
+            split_ref_path(s)
+                .into_iter()
+                .filter(|t| !t.is_empty() && !is_url(t)),
+        );
+    }
+
+    templates
+}
+
+// `REF_PATH` entries are colon-separated, but URLs (e.g., `http://...`) contain colons, so a
+// scheme separator (`://`) is rejoined with its prefix.
+fn split_ref_path(s: &str) -> Vec<String> {
+    let mut entries: Vec<String> = Vec::new();
+
+    for component in s.split(':') {
+        match entries.last_mut() {
+            Some(last) if component.starts_with("//") && is_scheme(last) => {
+                last.push(':');
+                last.push_str(component);
+            }
+            _ => entries.push(component.into()),
+        }
+    }
+
+    entries
+}
+
+fn is_scheme(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_url(s: &str) -> bool {
+    s.split_once("://")
+        .map(|(scheme, _)| is_scheme(scheme))
+        .unwrap_or(false)
+}
+
+fn expand_template(template: &str, hex_checksum: &str) -> PathBuf {
+    let mut dst = String::new();
+    let mut remaining = hex_checksum;
+    let mut has_rest = false;
+
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            dst.push(c);
+            continue;
+        }
+
+        let mut width = String::new();
+
+        while let Some(d) = chars.peek().copied().filter(char::is_ascii_digit) {
+            width.push(d);
+            chars.next();
+        }
+
+        match chars.next() {
+            Some('s') => {
+                let len = if width.is_empty() {
+                    has_rest = true;
+                    remaining.len()
+                } else {
+                    width.parse().unwrap_or(0).min(remaining.len())
+                };
+
+                let (head, tail) = remaining.split_at(len);
+                dst.push_str(head);
+                remaining = tail;
+            }
+            Some('%') if width.is_empty() => dst.push('%'),
+            Some(d) => {
+                dst.push('%');
+                dst.push_str(&width);
+                dst.push(d);
+            }
+            None => {
+                dst.push('%');
+                dst.push_str(&width);
+            }
+        }
+    }
+
+    if !has_rest {
+        if !dst.ends_with('/') {
+            dst.push('/');
+        }
+
+        dst.push_str(remaining);
+    }
+
+    PathBuf::from(dst)
+}
+
+fn encode_hex(buf: &[u8]) -> String {
+    buf.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// _Sequence Alignment/Map Format Specification_ (2021-06-03) § 1.3.2 "Reference MD5 calculation"
+fn calculate_normalized_sequence_digest(sequence: &[u8]) -> [u8; 16] {
+    let mut hasher = Md5::new();
+
+    for &b in sequence {
+        if b.is_ascii_graphic() {
+            hasher.update([b.to_ascii_uppercase()]);
+        }
+    }
+
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQ0_CHECKSUM: [u8; 16] = [
+        0xf1, 0xf8, 0xf4, 0xbf, 0x41, 0x3b, 0x16, 0xad, 0x13, 0x57, 0x22, 0xaa, 0x45, 0x91, 0x04,
+        0x3e,
+    ];
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let dir = env::temp_dir().join(format!("noodles-fasta-ref-cache-{}", std::process::id()));
+        let dst = dir.join("f1").join("f8");
+        fs::create_dir_all(&dst)?;
+        fs::write(dst.join("f4bf413b16ad135722aa4591043e"), b"ACgt\n")?;
+
+        let template = format!("{}/%2s/%2s/%s", dir.display());
+        let mut adapter = RefCache::new(vec![template]);
+        adapter.insert("sq0", SQ0_CHECKSUM);
+        adapter.insert("sq1", [0; 16]);
+
+        let record = adapter.get("sq0").transpose()?;
+        assert_eq!(
+            record.map(|r| r.sequence().as_ref().to_vec()),
+            Some(b"ACgt".to_vec())
+        );
+
+        assert!(adapter.get("sq1").is_none());
+        assert!(adapter.get("sq2").is_none());
+
+        fs::write(dst.join("f4bf413b16ad135722aa4591043e"), b"ACGA")?;
+        assert!(matches!(
+            adapter.get("sq0"),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_templates() {
+        assert_eq!(
+            parse_templates(
+                Some("/data/cache/%2s/%2s/%s"),
+                Some("/data/ref/%s:http://www.ebi.ac.uk/ena/cram/md5/%s::/data/ref2")
+            ),
+            [
+                String::from("/data/cache/%2s/%2s/%s"),
+                String::from("/data/ref/%s"),
+                String::from("/data/ref2"),
+            ]
+        );
+
+        assert!(parse_templates(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let hex_checksum = "f1f8f4bf413b16ad135722aa4591043e";
+
+        assert_eq!(
+            expand_template("/data/cache/%2s/%2s/%s", hex_checksum),
+            PathBuf::from("/data/cache/f1/f8/f4bf413b16ad135722aa4591043e")
+        );
+
+        assert_eq!(
+            expand_template("/data/ref/%s.fa", hex_checksum),
+            PathBuf::from("/data/ref/f1f8f4bf413b16ad135722aa4591043e.fa")
+        );
+
+        assert_eq!(
+            expand_template("/data/ref", hex_checksum),
+            PathBuf::from("/data/ref/f1f8f4bf413b16ad135722aa4591043e")
+        );
+
+        assert_eq!(
+            expand_template("/data/100%%/%2s/%s", hex_checksum),
+            PathBuf::from("/data/100%/f1/f8f4bf413b16ad135722aa4591043e")
+        );
+    }
+}
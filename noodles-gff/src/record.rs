@@ -12,7 +12,7 @@ pub use self::{
 
 use std::{error, fmt, num, str::FromStr};
 
-use noodles_core::Position;
+use noodles_core::{self as core, region::Interval, Feature, Position};
 
 pub(crate) const MISSING_FIELD: &str = ".";
 const FIELD_DELIMITER: char = '\t';
@@ -180,6 +180,16 @@ impl Default for Record {
     }
 }
 
+impl Feature for Record {
+    fn reference_sequence_name<'a>(&'a self, _: &'a ()) -> core::Result<&'a str> {
+        Ok(self.reference_sequence_name())
+    }
+
+    fn interval(&self) -> core::Result<Interval> {
+        Ok(Interval::from(self.start()..=self.end()))
+    }
+}
+
 impl fmt::Display for Record {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -1,5 +1,7 @@
 //! Alignment record and fields.
 
+#[cfg(feature = "filter")]
+pub mod filter;
 pub mod record;
 
 pub use self::record::Record;
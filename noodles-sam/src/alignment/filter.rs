@@ -0,0 +1,449 @@
+//! Alignment record filter expressions.
+//!
+//! A filter expression is a predicate on an alignment record, similar to the `-e` expressions of
+//! samtools, e.g., `mapq >= 30 && flag.proper_pair && !flag.dup && [NM] < 5`.
+//!
+//! The following fields are available.
+//!
+//!   * `flag`: the flags as an integer.
+//!   * `flag.paired`, `flag.proper_pair`, `flag.unmap`, `flag.munmap`, `flag.reverse`,
+//!     `flag.mreverse`, `flag.read1`, `flag.read2`, `flag.secondary`, `flag.qcfail`, `flag.dup`,
+//!     `flag.supplementary`: whether a flag is set.
+//!   * `qname`: the read name.
+//!   * `rname`: the reference sequence name.
+//!   * `pos`: the alignment start (1-based).
+//!   * `endpos`: the alignment end (1-based).
+//!   * `mapq`: the mapping quality.
+//!   * `mrname`: the mate reference sequence name.
+//!   * `mpos`: the mate alignment start (1-based).
+//!   * `tlen`: the template length.
+//!   * `qlen`: the sequence length.
+//!   * `rlen`: the alignment span, i.e., the number of reference bases the alignment covers.
+//!   * `sclen`: the total length of soft clips.
+//!   * `hclen`: the total length of hard clips.
+//!   * `nindel`: the number of insertion and deletion CIGAR operations.
+//!   * `ncigar`: the number of CIGAR operations.
+//!   * `library`: the library of the read group of the record.
+//!   * `sample`: the sample of the read group of the record.
+//!   * `[XX]`: the value of the data field with tag `XX`, e.g., `[NM]` or `[RG]`.
+//!
+//! A field that is not set in a record, e.g., the mapping quality of an unmapped record, is
+//! missing. Comparisons with a missing value are false.
+//!
+//! Data field values are typed: characters, strings, and hex strings are strings; integers and
+//! floating-points are numbers; and arrays are lists of numbers. Strings can only be compared with
+//! strings, and comparisons with a list are true if any element satisfies the comparison. An
+//! element of a list is selected with an index, e.g., `[XA][0]`.
+//!
+//! Numbers support the arithmetic operators `+`, `-`, `*`, and `/`; values can be compared with
+//! `==` (or `=`), `!=`, `<`, `<=`, `>`, and `>=`; and predicates are combined with `&&`, `||`,
+//! `!`, and parentheses. `x in (a, b, ...)` tests whether a value is equal to any of the given
+//! literals, e.g., `[RG] in ("rg0", "rg1")` tests read group membership.
+//!
+//! Expressions are parsed and evaluated by [`noodles_core::expression`]. This module is enabled by
+//! the `filter` feature.
+
+pub use noodles_core::expression::ParseError;
+
+use std::{borrow::Cow, convert::Infallible, io, str::FromStr};
+
+use noodles_core::{
+    expression::{self, Value},
+    Position,
+};
+
+use super::Record;
+use crate::{
+    record::{cigar::op::Kind, data::field::Tag, Flags},
+    Header,
+};
+
+/// A parsed alignment record filter expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression(expression::Expression<Field, Infallible>);
+
+impl Expression {
+    /// Evaluates the filter expression against an alignment record.
+    ///
+    /// The header is used to resolve reference sequence names and read groups. The record is
+    /// borrowed and is not modified.
+    ///
+    /// An error is returned if a reference sequence is missing from the header or if values of
+    /// incompatible types are compared or used in arithmetic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::{filter::Expression, Record},
+    ///     record::MappingQuality,
+    /// };
+    ///
+    /// let header = sam::Header::default();
+    /// let expression: Expression = "mapq >= 30 && !flag.unmap".parse()?;
+    ///
+    /// let record = Record::builder()
+    ///     .set_flags(sam::record::Flags::empty())
+    ///     .set_mapping_quality(MappingQuality::try_from(34)?)
+    ///     .build();
+    ///
+    /// assert!(expression.evaluate(&header, &record)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn evaluate(&self, header: &Header, record: &Record) -> io::Result<bool> {
+        let evaluator = Evaluator { header, record };
+        self.0.evaluate(&evaluator).map(|value| value.is_truthy())
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        expression::Expression::parse(s, &Resolver).map(Self)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Field {
+    Flags,
+    Flag(Flags),
+    ReadName,
+    ReferenceSequenceName,
+    AlignmentStart,
+    AlignmentEnd,
+    MappingQuality,
+    MateReferenceSequenceName,
+    MateAlignmentStart,
+    TemplateLength,
+    SequenceLength,
+    AlignmentSpan,
+    SoftClipLength,
+    HardClipLength,
+    IndelCount,
+    CigarOpCount,
+    Library,
+    Sample,
+    Tag(Tag),
+}
+
+struct Resolver;
+
+impl expression::Resolver for Resolver {
+    type Field = Field;
+    type Function = Infallible;
+
+    fn is_namespace(&self, _: &str) -> bool {
+        false
+    }
+
+    fn resolve_field(&self, name: &str) -> Option<Self::Field> {
+        let field = match name {
+            "flag" => Field::Flags,
+            "flag.paired" => Field::Flag(Flags::SEGMENTED),
+            "flag.proper_pair" => Field::Flag(Flags::PROPERLY_ALIGNED),
+            "flag.unmap" => Field::Flag(Flags::UNMAPPED),
+            "flag.munmap" => Field::Flag(Flags::MATE_UNMAPPED),
+            "flag.reverse" => Field::Flag(Flags::REVERSE_COMPLEMENTED),
+            "flag.mreverse" => Field::Flag(Flags::MATE_REVERSE_COMPLEMENTED),
+            "flag.read1" => Field::Flag(Flags::FIRST_SEGMENT),
+            "flag.read2" => Field::Flag(Flags::LAST_SEGMENT),
+            "flag.secondary" => Field::Flag(Flags::SECONDARY),
+            "flag.qcfail" => Field::Flag(Flags::QC_FAIL),
+            "flag.dup" => Field::Flag(Flags::DUPLICATE),
+            "flag.supplementary" => Field::Flag(Flags::SUPPLEMENTARY),
+            "qname" => Field::ReadName,
+            "rname" => Field::ReferenceSequenceName,
+            "pos" => Field::AlignmentStart,
+            "endpos" => Field::AlignmentEnd,
+            "mapq" => Field::MappingQuality,
+            "mrname" => Field::MateReferenceSequenceName,
+            "mpos" => Field::MateAlignmentStart,
+            "tlen" => Field::TemplateLength,
+            "qlen" => Field::SequenceLength,
+            "rlen" => Field::AlignmentSpan,
+            "sclen" => Field::SoftClipLength,
+            "hclen" => Field::HardClipLength,
+            "nindel" => Field::IndelCount,
+            "ncigar" => Field::CigarOpCount,
+            "library" => Field::Library,
+            "sample" => Field::Sample,
+            _ => {
+                let raw_tag = name.strip_prefix('[')?.strip_suffix(']')?;
+                return raw_tag.parse().ok().map(Field::Tag);
+            }
+        };
+
+        Some(field)
+    }
+
+    fn resolve_function(&self, _: &str) -> Option<Self::Function> {
+        None
+    }
+}
+
+struct Evaluator<'a> {
+    header: &'a Header,
+    record: &'a Record,
+}
+
+impl<'a> expression::Evaluator<'a> for Evaluator<'a> {
+    type Field = Field;
+    type Function = Infallible;
+
+    fn evaluate_field(&self, field: &Self::Field) -> io::Result<Value<'a>> {
+        field_value(*field, self.header, self.record)
+    }
+
+    fn evaluate_call(
+        &self,
+        function: &Self::Function,
+        _: &expression::Expression<Self::Field, Self::Function>,
+    ) -> io::Result<Value<'a>> {
+        match *function {}
+    }
+}
+
+fn field_value<'a>(field: Field, header: &'a Header, record: &'a Record) -> io::Result<Value<'a>> {
+    let value = match field {
+        Field::Flags => Value::Number(f64::from(u16::from(record.flags()))),
+        Field::Flag(flag) => Value::Bool(record.flags().contains(flag)),
+        Field::ReadName => record
+            .read_name()
+            .map(|name| Value::String(Cow::Borrowed(name.as_ref())))
+            .unwrap_or(Value::Missing),
+        Field::ReferenceSequenceName => match record.reference_sequence(header).transpose()? {
+            Some((name, _)) => Value::String(Cow::Borrowed(name)),
+            None => Value::Missing,
+        },
+        Field::AlignmentStart => position(record.alignment_start()),
+        Field::AlignmentEnd => position(record.alignment_end()),
+        Field::MappingQuality => record
+            .mapping_quality()
+            .map(|mapq| Value::Number(f64::from(u8::from(mapq))))
+            .unwrap_or(Value::Missing),
+        Field::MateReferenceSequenceName => {
+            match record.mate_reference_sequence(header).transpose()? {
+                Some((name, _)) => Value::String(Cow::Borrowed(name)),
+                None => Value::Missing,
+            }
+        }
+        Field::MateAlignmentStart => position(record.mate_alignment_start()),
+        Field::TemplateLength => Value::Number(f64::from(record.template_length())),
+        Field::SequenceLength => Value::Number(record.sequence().len() as f64),
+        Field::AlignmentSpan => Value::Number(record.alignment_span() as f64),
+        Field::SoftClipLength => Value::Number(cigar_len(record, |kind| kind == Kind::SoftClip)),
+        Field::HardClipLength => Value::Number(cigar_len(record, |kind| kind == Kind::HardClip)),
+        Field::IndelCount => {
+            let n = record
+                .cigar()
+                .iter()
+                .filter(|op| matches!(op.kind(), Kind::Insertion | Kind::Deletion))
+                .count();
+
+            Value::Number(n as f64)
+        }
+        Field::CigarOpCount => Value::Number(record.cigar().len() as f64),
+        Field::Library => read_group_value(header, record, |read_group| read_group.library()),
+        Field::Sample => read_group_value(header, record, |read_group| read_group.sample()),
+        Field::Tag(tag) => record
+            .data()
+            .get(tag)
+            .map(data_field_value)
+            .unwrap_or(Value::Missing),
+    };
+
+    Ok(value)
+}
+
+fn position(position: Option<Position>) -> Value<'static> {
+    position
+        .map(|position| Value::Number(usize::from(position) as f64))
+        .unwrap_or(Value::Missing)
+}
+
+fn cigar_len<F>(record: &Record, predicate: F) -> f64
+where
+    F: Fn(Kind) -> bool,
+{
+    let len: usize = record
+        .cigar()
+        .iter()
+        .filter(|op| predicate(op.kind()))
+        .map(|op| op.len())
+        .sum();
+
+    len as f64
+}
+
+fn read_group_value<'a, F>(header: &'a Header, record: &'a Record, f: F) -> Value<'a>
+where
+    F: Fn(
+        &'a crate::header::record::value::Map<crate::header::record::value::map::ReadGroup>,
+    ) -> Option<&'a str>,
+{
+    let id = match record.data().get(Tag::ReadGroup) {
+        Some(value) => value.as_str(),
+        None => None,
+    };
+
+    id.and_then(|id| header.read_groups().get(id))
+        .and_then(f)
+        .map(|s| Value::String(Cow::Borrowed(s)))
+        .unwrap_or(Value::Missing)
+}
+
+fn data_field_value(value: &crate::record::data::field::Value) -> Value<'_> {
+    use crate::record::data::field::Value as DataValue;
+
+    fn list<'a, T>(values: &[T]) -> Value<'a>
+    where
+        T: Copy + Into<f64>,
+    {
+        Value::List(values.iter().map(|&n| Value::Number(n.into())).collect())
+    }
+
+    match value {
+        DataValue::Character(c) => Value::String(Cow::Owned(char::from(*c).to_string())),
+        DataValue::Int8(n) => Value::Number(f64::from(*n)),
+        DataValue::UInt8(n) => Value::Number(f64::from(*n)),
+        DataValue::Int16(n) => Value::Number(f64::from(*n)),
+        DataValue::UInt16(n) => Value::Number(f64::from(*n)),
+        DataValue::Int32(n) => Value::Number(f64::from(*n)),
+        DataValue::UInt32(n) => Value::Number(f64::from(*n)),
+        DataValue::Float(n) => Value::Number(f64::from(*n)),
+        DataValue::String(s) => Value::String(Cow::Borrowed(s)),
+        DataValue::Hex(s) => Value::String(Cow::Borrowed(s.as_ref())),
+        DataValue::Int8Array(values) => list(values),
+        DataValue::UInt8Array(values) => list(values),
+        DataValue::Int16Array(values) => list(values),
+        DataValue::UInt16Array(values) => list(values),
+        DataValue::Int32Array(values) => list(values),
+        DataValue::UInt32Array(values) => list(values),
+        DataValue::FloatArray(values) => list(values),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_header() -> Result<Header, Box<dyn std::error::Error>> {
+        let header = "@HD\tVN:1.6
+@SQ\tSN:sq0\tLN:1000
+@SQ\tSN:sq1\tLN:1000
+@RG\tID:rg0\tSM:sample0\tLB:lib0
+@RG\tID:rg1\tSM:sample1
+";
+
+        Ok(header.parse()?)
+    }
+
+    fn parse_record(s: &str, data: &str, header: &Header) -> io::Result<Record> {
+        let mut record = Record::default();
+        crate::reader::record::parse_record(s.as_bytes(), header, &mut record)?;
+        *record.data_mut() = crate::reader::record::data::parse_data(data.as_bytes())?;
+        Ok(record)
+    }
+
+    fn eval(header: &Header, record: &Record, s: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        let expression: Expression = s.parse()?;
+        Ok(expression.evaluate(header, record)?)
+    }
+
+    #[test]
+    fn test_evaluate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let record = parse_record(
+            "r0\t99\tsq0\t8\t40\t2S3M1I2M1D1M2H\tsq1\t100\t150\tACGTACGTA\tNDLSNDLSN",
+            "NM:i:2\tRG:Z:rg0\tXA:B:s,-1,5\tXC:A:c\tXF:f:0.5",
+            &header,
+        )?;
+
+        assert!(eval(
+            &header,
+            &record,
+            "flag == 99 && flag.paired && flag.proper_pair"
+        )?);
+        assert!(eval(&header, &record, "flag.mreverse && flag.read1")?);
+        assert!(!eval(
+            &header,
+            &record,
+            "flag.unmap || flag.reverse || flag.dup"
+        )?);
+        assert!(eval(&header, &record, "qname == 'r0'")?);
+        assert!(eval(&header, &record, "rname == 'sq0' && mrname == 'sq1'")?);
+        assert!(eval(
+            &header,
+            &record,
+            "pos == 8 && endpos == 14 && mpos == 100"
+        )?);
+        assert!(eval(&header, &record, "mapq >= 30 && mapq < 50")?);
+        assert!(eval(&header, &record, "tlen == 150 && -tlen < 0")?);
+        assert!(eval(&header, &record, "qlen == 9 && rlen == 7")?);
+        assert!(eval(&header, &record, "sclen == 2 && hclen == 2")?);
+        assert!(eval(&header, &record, "nindel == 2 && ncigar == 7")?);
+
+        assert!(eval(&header, &record, "[NM] <= 2 && [NM] / qlen < 0.25")?);
+        assert!(eval(&header, &record, "[XA] > 4 && [XA] < 0")?);
+        assert!(eval(&header, &record, "[XC] == 'c' && [XF] == 0.5")?);
+        assert!(!eval(&header, &record, "[NH] == 1 || [NH] != 1")?);
+        assert!(eval(&header, &record, "[XA][1] == 5")?);
+
+        assert!(eval(&header, &record, "[RG] in ('rg0', 'rg1')")?);
+        assert!(!eval(&header, &record, "[RG] in ('rg1')")?);
+        assert!(eval(
+            &header,
+            &record,
+            "sample == 'sample0' && library == 'lib0'"
+        )?);
+
+        assert!(eval(&header, &record, "[NM] == 'a'").is_err());
+        assert!(eval(&header, &record, "qname + 1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!("mapq >= 30 && !flag.dup && [NM] / qlen < 0.05"
+            .parse::<Expression>()
+            .is_ok());
+
+        assert_eq!(
+            "[NMM] > 1".parse::<Expression>(),
+            Err(ParseError::InvalidField(String::from("[NMM]")))
+        );
+        assert_eq!(
+            "flag.unmapped".parse::<Expression>(),
+            Err(ParseError::InvalidField(String::from("flag.unmapped")))
+        );
+        assert_eq!(
+            "any(flag.dup)".parse::<Expression>(),
+            Err(ParseError::InvalidFunction(String::from("any")))
+        );
+        assert_eq!(
+            "[RG] in (qname)".parse::<Expression>(),
+            Err(ParseError::UnexpectedToken(String::from("qname")))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_with_unmapped_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let record = parse_record(
+            "r1\t4\t*\t0\t255\t*\t*\t0\t0\tACGT\tNDLS",
+            "RG:Z:rg1",
+            &header,
+        )?;
+
+        assert!(eval(&header, &record, "flag.unmap")?);
+        assert!(!eval(&header, &record, "mapq < 30 || mapq >= 30")?);
+        assert!(!eval(&header, &record, "rname == 'sq0' || rname != 'sq0'")?);
+        assert!(eval(&header, &record, "!pos && !endpos")?);
+        assert!(eval(&header, &record, "sample == 'sample1' && !library")?);
+
+        Ok(())
+    }
+}
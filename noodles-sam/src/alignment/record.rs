@@ -6,7 +6,7 @@ pub use self::builder::Builder;
 
 use std::io;
 
-use noodles_core::Position;
+use noodles_core::{self as core, region::Interval, Feature, Position};
 
 use crate::{
     header::{
@@ -491,6 +491,30 @@ impl Default for Record {
     }
 }
 
+impl Feature<Header> for Record {
+    fn reference_sequence_name<'a>(&'a self, header: &'a Header) -> core::Result<&'a str> {
+        self.reference_sequence(header)
+            .ok_or_else(|| {
+                core::Error::new(core::error::Kind::Other, "missing reference sequence ID")
+            })?
+            .map(|(name, _)| name.as_ref())
+            .map_err(core::Error::from)
+    }
+
+    fn interval(&self) -> core::Result<Interval> {
+        match (self.alignment_start(), self.alignment_end()) {
+            // A record without alignment operations (e.g., a placed unmapped read) spans its
+            // alignment start.
+            (Some(start), Some(end)) => Ok(Interval::from(start..=end.max(start))),
+            (Some(start), None) => Ok(Interval::from(start..=start)),
+            _ => Err(core::Error::new(
+                core::error::Kind::Other,
+                "missing alignment start",
+            )),
+        }
+    }
+}
+
 fn get_reference_sequence(
     reference_sequences: &ReferenceSequences,
     reference_sequence_id: Option<usize>,
@@ -501,3 +525,41 @@ fn get_reference_sequence(
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        let header = Header::builder()
+            .add_reference_sequence(
+                "sq0".parse()?,
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(21)?),
+            )
+            .build();
+
+        let record = Record::builder()
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("5M".parse()?)
+            .build();
+
+        assert_eq!(record.region(&header)?, "sq0:8-12".parse()?);
+
+        let record = Record::builder()
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .build();
+
+        assert_eq!(record.region(&header)?, "sq0:8-8".parse()?);
+
+        let record = Record::default();
+        assert!(record.reference_sequence_name(&header).is_err());
+        assert!(record.interval().is_err());
+
+        Ok(())
+    }
+}
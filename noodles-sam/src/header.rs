@@ -78,6 +78,7 @@ pub mod record;
 use std::{fmt, str::FromStr};
 
 use indexmap::IndexMap;
+use noodles_core::region_list::Dictionary;
 
 pub use self::{builder::Builder, parser::ParseError, record::Record};
 
@@ -430,6 +431,30 @@ impl fmt::Display for Header {
     }
 }
 
+impl From<&Header> for Dictionary {
+    /// Creates a reference sequence dictionary from the reference sequences in a SAM header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// use noodles_sam as sam;
+    ///
+    /// let header: sam::Header = "@SQ\tSN:sq0\tLN:8\n".parse()?;
+    /// let dictionary = Dictionary::from(&header);
+    ///
+    /// assert_eq!(dictionary.get("sq0"), Some(Some(8)));
+    /// # Ok::<_, sam::header::ParseError>(())
+    /// ```
+    fn from(header: &Header) -> Self {
+        header
+            .reference_sequences()
+            .iter()
+            .map(|(name, reference_sequence)| (name.to_string(), reference_sequence.length().get()))
+            .collect()
+    }
+}
+
 impl FromStr for Header {
     type Err = ParseError;
 
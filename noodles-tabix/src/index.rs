@@ -11,7 +11,8 @@ pub use self::{
 
 use std::io;
 
-use noodles_core::{region::Interval, Position};
+use noodles_bgzf as bgzf;
+use noodles_core::{region::Interval, Position, Region};
 use noodles_csi::{
     binning_index::optimize_chunks, index::reference_sequence::bin::Chunk, BinningIndex,
 };
@@ -63,6 +64,60 @@ impl Index {
     pub fn header(&self) -> &Header {
         &self.header
     }
+
+    /// Returns the estimated number of compressed bytes to read to query the given region.
+    ///
+    /// The region reference sequence name is resolved using the reference sequence names of the
+    /// index header. See [`BinningIndex::estimated_compressed_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_core::Region;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    /// use noodles_tabix::{
+    ///     self as tabix,
+    ///     index::{header::ReferenceSequenceNames, reference_sequence::Bin, ReferenceSequence},
+    /// };
+    ///
+    /// let reference_sequence_names: ReferenceSequenceNames =
+    ///     [String::from("sq0")].into_iter().collect();
+    ///
+    /// let chunks = vec![Chunk::new(
+    ///     bgzf::VirtualPosition::try_from((8, 0))?,
+    ///     bgzf::VirtualPosition::try_from((21, 0))?,
+    /// )];
+    /// let bins = vec![Bin::new(0, chunks)];
+    ///
+    /// let index = tabix::Index::builder()
+    ///     .set_reference_sequence_names(reference_sequence_names)
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(bins, Vec::new(), None)])
+    ///     .build();
+    ///
+    /// let region = "sq0:5-8".parse()?;
+    /// assert_eq!(index.estimated_compressed_size_of_region(&region)?, 13);
+    ///
+    /// let region = Region::new("sq1", ..);
+    /// assert!(index.estimated_compressed_size_of_region(&region).is_err());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn estimated_compressed_size_of_region(&self, region: &Region) -> io::Result<u64> {
+        let reference_sequence_id = self
+            .header()
+            .reference_sequence_names()
+            .get_index_of(region.name())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in reference sequences: {region:?}"
+                    ),
+                )
+            })?;
+
+        self.estimated_compressed_size(reference_sequence_id, region.interval())
+    }
 }
 
 impl BinningIndex for Index {
@@ -128,6 +183,21 @@ impl BinningIndex for Index {
 
         Ok(merged_chunks)
     }
+
+    fn linear_index(
+        &self,
+        reference_sequence_id: usize,
+    ) -> io::Result<Vec<(Position, bgzf::VirtualPosition)>> {
+        self.reference_sequences()
+            .get(reference_sequence_id)
+            .map(|reference_sequence| reference_sequence.linear_index())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid reference sequence ID: {reference_sequence_id}"),
+                )
+            })
+    }
 }
 
 impl Default for Index {
@@ -162,3 +232,49 @@ where
         Ok((start, end))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimated_compressed_size_of_region_resolves_region_name(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use self::{header::ReferenceSequenceNames, reference_sequence::Bin};
+
+        let reference_sequence_names: ReferenceSequenceNames =
+            [String::from("sq0"), String::from("sq1")]
+                .into_iter()
+                .collect();
+
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::try_from((55, 0))?,
+            bgzf::VirtualPosition::try_from((89, 0))?,
+        );
+
+        let index = Index::builder()
+            .set_reference_sequence_names(reference_sequence_names)
+            .set_reference_sequences(vec![
+                ReferenceSequence::new(Vec::new(), Vec::new(), None),
+                ReferenceSequence::new(vec![Bin::new(0, vec![chunk])], Vec::new(), None),
+            ])
+            .build();
+
+        // Only the second reference sequence has records.
+        let region = "sq1:2-5".parse()?;
+        let actual = index.estimated_compressed_size_of_region(&region)?;
+        assert_eq!(
+            actual,
+            index.estimated_compressed_size(1, region.interval())?
+        );
+        assert!(actual > 0);
+
+        let region = Region::new("sq2", ..);
+        assert!(matches!(
+            index.estimated_compressed_size_of_region(&region),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
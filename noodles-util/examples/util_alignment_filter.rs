@@ -0,0 +1,37 @@
+//! Prints the records in an alignment file that match a filter expression in the SAM format.
+//!
+//! The result is similar to the output of `samtools view --no-PG --with-header --expr <expr>
+//! <src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_sam::{self as sam, AlignmentWriter};
+use noodles_util::alignment::{self, filter::Expression};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let expression: Expression = args.next().expect("missing expr").parse()?;
+
+    let mut reader = alignment::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = sam::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in reader.records(&header) {
+        let record = result?;
+
+        if expression.evaluate(&header, &record)? {
+            writer.write_alignment_record(&header, &record)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+//! Counts the number of placed records in a BAM file using multiple threads.
+//!
+//! The input BAM must have an index in the same directory.
+//!
+//! The total matches the output of `samtools view --count <src>` for inputs without unplaced,
+//! unmapped records.
+
+use std::{
+    env, io,
+    num::{NonZeroU64, NonZeroUsize},
+    thread,
+};
+
+use noodles_util::shard;
+
+const SHARD_SIZE: u64 = 1 << 26;
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let shard_size = NonZeroU64::new(SHARD_SIZE).unwrap();
+    let worker_count =
+        thread::available_parallelism().unwrap_or_else(|_| NonZeroUsize::new(1).unwrap());
+
+    let counts = shard::bam::process(src, shard_size, worker_count, |shard, _, records| {
+        let mut n = 0;
+
+        for result in records {
+            result?;
+            n += 1;
+        }
+
+        Ok((shard.region().clone(), n))
+    })?;
+
+    let mut total = 0;
+
+    for (region, n) in counts {
+        eprintln!("{region}\t{n}");
+        total += n;
+    }
+
+    println!("{total}");
+
+    Ok(())
+}
@@ -4,4 +4,6 @@ mod format;
 pub mod reader;
 pub mod writer;
 
+pub use noodles_sam::alignment::filter;
+
 pub use self::{format::Format, reader::Reader, writer::Writer};
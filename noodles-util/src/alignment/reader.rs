@@ -6,13 +6,14 @@ pub use self::builder::Builder;
 
 use std::io::{self, Read};
 
+use noodles_cram as cram;
 use noodles_fasta as fasta;
 use noodles_sam::{self as sam, alignment::Record, AlignmentReader};
 
 /// An alignment reader.
 pub struct Reader<R> {
     inner: Box<dyn AlignmentReader<R>>,
-    reference_sequence_repository: fasta::Repository,
+    reference_sequence_repository: Option<fasta::Repository>,
 }
 
 impl<R> Reader<R>
@@ -75,7 +76,11 @@ where
         &'a mut self,
         header: &'a sam::Header,
     ) -> impl Iterator<Item = io::Result<Record>> + 'a {
+        let reference_sequence_repository = self
+            .reference_sequence_repository
+            .get_or_insert_with(|| cram::reader::default_reference_sequence_repository(header));
+
         self.inner
-            .alignment_records(&self.reference_sequence_repository, header)
+            .alignment_records(reference_sequence_repository, header)
     }
 }
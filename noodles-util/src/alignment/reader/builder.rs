@@ -18,7 +18,7 @@ use crate::alignment::Format;
 #[derive(Default)]
 pub struct Builder {
     format: Option<Format>,
-    reference_sequence_repository: fasta::Repository,
+    reference_sequence_repository: Option<fasta::Repository>,
 }
 
 impl Builder {
@@ -39,6 +39,10 @@ impl Builder {
 
     /// Sets the reference sequence repository.
     ///
+    /// By default, reference sequences are resolved by their MD5 checksums using the local
+    /// reference cache (`REF_CACHE`/`REF_PATH`). See
+    /// [`noodles_cram::reader::default_reference_sequence_repository`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -54,7 +58,7 @@ impl Builder {
         mut self,
         reference_sequence_repository: fasta::Repository,
     ) -> Self {
-        self.reference_sequence_repository = reference_sequence_repository;
+        self.reference_sequence_repository = Some(reference_sequence_repository);
         self
     }
 
@@ -4,3 +4,9 @@
 
 #[cfg(feature = "alignment")]
 pub mod alignment;
+
+#[cfg(feature = "shard")]
+pub mod shard;
+
+#[cfg(feature = "variant")]
+pub mod variant;
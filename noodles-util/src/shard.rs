@@ -0,0 +1,487 @@
+//! Region-sharded parallel processing of indexed files.
+//!
+//! A shard is a region of a reference sequence. Shards are built from the index of a file so that
+//! each shard covers roughly the same number of compressed bytes, and together, they tile every
+//! indexed reference sequence.
+//!
+//! Each format module (e.g., [`bam`]) has a `process` function that runs a closure on each shard
+//! in parallel, with each worker using its own reader. Records are assigned to the shard that
+//! contains their start position, i.e., each placed record is given to exactly one shard, even if
+//! it overlaps several. Unplaced records are not processed. Results are returned in genome order.
+
+pub mod bam;
+pub mod bcf;
+pub mod cram;
+pub mod vcf;
+
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    num::{NonZeroU64, NonZeroUsize},
+    panic,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    thread,
+};
+
+use noodles_core::{region::Interval, region_list::Dictionary, Position, Region};
+use noodles_cram::crai;
+use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
+
+/// A region of a reference sequence to be processed as a unit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Shard {
+    region: Region,
+    compressed_size: u64,
+}
+
+impl Shard {
+    /// Creates a shard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Region;
+    /// use noodles_util::shard::Shard;
+    /// let shard = Shard::new(Region::new("sq0", ..), 0);
+    /// ```
+    pub fn new(region: Region, compressed_size: u64) -> Self {
+        Self {
+            region,
+            compressed_size,
+        }
+    }
+
+    /// Returns the region of the shard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Region;
+    /// use noodles_util::shard::Shard;
+    /// let shard = Shard::new(Region::new("sq0", ..), 0);
+    /// assert_eq!(shard.region(), &Region::new("sq0", ..));
+    /// ```
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    /// Returns the estimated compressed size of the shard in bytes.
+    ///
+    /// This is estimated from the index, so it is only approximate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Region;
+    /// use noodles_util::shard::Shard;
+    /// let shard = Shard::new(Region::new("sq0", ..), 8);
+    /// assert_eq!(shard.compressed_size(), 8);
+    /// ```
+    pub fn compressed_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    /// Returns whether the shard owns a record with the given start position.
+    fn contains(&self, position: Position) -> bool {
+        let interval = self.region.interval();
+
+        interval
+            .start()
+            .map(|start| start <= position)
+            .unwrap_or(true)
+            && interval.end().map(|end| position <= end).unwrap_or(true)
+    }
+}
+
+/// Builds shards from a binning index, e.g., a BAM index (BAI), CSI, or tabix index.
+///
+/// The dictionary lists the reference sequences in the order of the reference sequence IDs in the
+/// index. Shards are cut at linear index windows once they reach the given compressed size.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroU64;
+/// use noodles_core::region_list::Dictionary;
+/// use noodles_csi as csi;
+/// use noodles_util::shard;
+///
+/// let dictionary: Dictionary = [("sq0", 8)].into_iter().collect();
+/// let index = csi::Index::default();
+///
+/// let shards = shard::from_binning_index(&dictionary, &index, NonZeroU64::new(1 << 20).unwrap())?;
+/// assert!(shards.is_empty());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn from_binning_index<I>(
+    dictionary: &Dictionary,
+    index: &I,
+    shard_size: NonZeroU64,
+) -> io::Result<Vec<Shard>>
+where
+    I: BinningIndex,
+{
+    let reference_sequence_count = index.reference_sequences().len();
+
+    let linear_indices = (0..reference_sequence_count)
+        .map(|i| {
+            index.linear_index(i).map(|linear_index| {
+                linear_index
+                    .into_iter()
+                    .map(|(start, pos)| (start, pos.compressed()))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut shards = Vec::new();
+
+    for (i, ((name, length), points)) in dictionary.iter().zip(&linear_indices).enumerate() {
+        // Without metadata, the data of a reference sequence is assumed to end where the data of
+        // the next one starts.
+        let end = index.reference_sequences()[i]
+            .metadata()
+            .map(|metadata| metadata.end_position().compressed())
+            .or_else(|| {
+                linear_indices[i + 1..]
+                    .iter()
+                    .find_map(|points| points.first().map(|(_, offset)| *offset))
+            })
+            .or_else(|| points.last().map(|(_, offset)| *offset))
+            .unwrap_or_default();
+
+        partition(name, length, points, end, shard_size.get(), &mut shards);
+    }
+
+    Ok(shards)
+}
+
+/// Builds shards from a CRAM index.
+///
+/// The dictionary lists the reference sequences in the order of the reference sequence IDs in the
+/// index, i.e., the reference sequences of the SAM header. Shards are cut at slice boundaries
+/// once they reach the given compressed size. Reference sequences without index records are
+/// skipped.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroU64;
+/// use noodles_core::{region_list::Dictionary, Position};
+/// use noodles_cram::crai;
+/// use noodles_util::shard;
+///
+/// let dictionary: Dictionary = [("sq0", 8)].into_iter().collect();
+/// let index = vec![crai::Record::new(Some(0), Position::new(1), 8, 0, 0, 55)];
+///
+/// let shards = shard::from_crai(&dictionary, &index, NonZeroU64::new(1 << 20).unwrap());
+/// assert_eq!(shards.len(), 1);
+/// assert_eq!(shards[0].region(), &"sq0:1-8".parse()?);
+/// assert_eq!(shards[0].compressed_size(), 55);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn from_crai(
+    dictionary: &Dictionary,
+    index: &crai::Index,
+    shard_size: NonZeroU64,
+) -> Vec<Shard> {
+    let mut shards = Vec::new();
+
+    for (i, (name, length)) in dictionary.iter().enumerate() {
+        let records: Vec<_> = index
+            .iter()
+            .filter(|record| record.reference_sequence_id() == Some(i))
+            .collect();
+
+        if records.is_empty() {
+            continue;
+        }
+
+        let mut points: Vec<_> = records
+            .iter()
+            .filter_map(|record| {
+                record
+                    .alignment_start()
+                    .map(|start| (start, record.offset() + record.landmark()))
+            })
+            .collect();
+
+        points.sort_unstable();
+
+        let end = records
+            .iter()
+            .map(|record| record.offset() + record.landmark() + record.slice_length())
+            .max()
+            .unwrap_or_default();
+
+        partition(name, length, &points, end, shard_size.get(), &mut shards);
+    }
+
+    shards
+}
+
+/// Runs a function on each shard using the given number of worker threads.
+///
+/// The results are returned in the order of the shards. If any function call fails, no new shards
+/// are started, and the error of the first failed shard is returned.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroUsize;
+/// use noodles_core::Region;
+/// use noodles_util::shard::{self, Shard};
+///
+/// let shards = [
+///     Shard::new(Region::new("sq0", ..), 8),
+///     Shard::new(Region::new("sq1", ..), 13),
+/// ];
+///
+/// let worker_count = NonZeroUsize::new(2).unwrap();
+/// let sizes = shard::run(&shards, worker_count, |shard| Ok(shard.compressed_size()))?;
+/// assert_eq!(sizes, [8, 13]);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn run<T, F>(shards: &[Shard], worker_count: NonZeroUsize, f: F) -> io::Result<Vec<T>>
+where
+    F: Fn(&Shard) -> io::Result<T> + Sync,
+    T: Send,
+{
+    let next_index = AtomicUsize::new(0);
+    let is_failed = AtomicBool::new(false);
+
+    let worker_count = worker_count.get().min(shards.len());
+
+    let mut results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..worker_count)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+
+                    while !is_failed.load(Ordering::Relaxed) {
+                        let i = next_index.fetch_add(1, Ordering::Relaxed);
+
+                        let shard = match shards.get(i) {
+                            Some(shard) => shard,
+                            None => break,
+                        };
+
+                        let result = f(shard);
+
+                        if result.is_err() {
+                            is_failed.store(true, Ordering::Relaxed);
+                        }
+
+                        results.push((i, result));
+                    }
+
+                    results
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| match handle.join() {
+                Ok(results) => results,
+                Err(e) => panic::resume_unwind(e),
+            })
+            .collect()
+    });
+
+    results.sort_unstable_by_key(|(i, _)| *i);
+
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+fn partition(
+    name: &str,
+    length: Option<usize>,
+    points: &[(Position, u64)],
+    end: u64,
+    shard_size: u64,
+    shards: &mut Vec<Shard>,
+) {
+    let mut start = Position::MIN;
+    let mut start_offset = points.first().map(|(_, offset)| *offset).unwrap_or(end);
+    let mut max_offset = start_offset;
+
+    for &(position, offset) in points {
+        if length
+            .map(|len| usize::from(position) > len)
+            .unwrap_or(false)
+        {
+            break;
+        }
+
+        // Offsets are expected to be nondecreasing, but windows with long records may point
+        // backwards.
+        max_offset = max_offset.max(offset);
+
+        if position <= start || max_offset - start_offset < shard_size {
+            continue;
+        }
+
+        if let Some(end_position) = usize::from(position).checked_sub(1).and_then(Position::new) {
+            let region = Region::new(name, start..=end_position);
+            shards.push(Shard::new(region, max_offset - start_offset));
+
+            start = position;
+            start_offset = max_offset;
+        }
+    }
+
+    let interval: Interval = match length.and_then(Position::new) {
+        Some(end_position) => (start..=end_position).into(),
+        None => (start..).into(),
+    };
+
+    let region = Region::new(name, interval);
+    let compressed_size = end.max(max_offset) - start_offset;
+    shards.push(Shard::new(region, compressed_size));
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition() -> Result<(), Box<dyn std::error::Error>> {
+        fn t(
+            length: Option<usize>,
+            points: &[(usize, u64)],
+            end: u64,
+            expected: &[(&str, u64)],
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            let points: Vec<_> = points
+                .iter()
+                .map(|&(start, offset)| Position::try_from(start).map(|start| (start, offset)))
+                .collect::<Result<_, _>>()?;
+
+            let mut actual = Vec::new();
+            partition("sq0", length, &points, end, 8, &mut actual);
+
+            let expected: Vec<_> = expected
+                .iter()
+                .map(|(region, compressed_size)| {
+                    region
+                        .parse()
+                        .map(|region| Shard::new(region, *compressed_size))
+                })
+                .collect::<Result<_, _>>()?;
+
+            assert_eq!(actual, expected);
+
+            Ok(())
+        }
+
+        t(
+            Some(100),
+            &[(1, 0), (11, 5), (21, 10), (31, 13), (41, 21)],
+            25,
+            &[("sq0:1-20", 10), ("sq0:21-40", 11), ("sq0:41-100", 4)],
+        )?;
+
+        // unknown length
+        t(
+            None,
+            &[(1, 0), (11, 8)],
+            13,
+            &[("sq0:1-10", 8), ("sq0:11", 5)],
+        )?;
+
+        // nonmonotonic offsets
+        t(
+            Some(100),
+            &[(1, 0), (11, 13), (21, 5), (31, 21)],
+            21,
+            &[("sq0:1-10", 13), ("sq0:11-30", 8), ("sq0:31-100", 0)],
+        )?;
+
+        // no linear index
+        t(Some(100), &[], 0, &[("sq0:1-100", 0)])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_binning_index() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bam::bai;
+        use noodles_bgzf as bgzf;
+
+        let build_reference_sequence = |offsets: &[u64]| {
+            let intervals = offsets
+                .iter()
+                .map(|&offset| bgzf::VirtualPosition::try_from((offset, 0)))
+                .collect::<Result<_, _>>()?;
+
+            Ok::<_, bgzf::virtual_position::TryFromU64U16TupleError>(
+                bai::index::ReferenceSequence::new(Vec::new(), intervals, None),
+            )
+        };
+
+        let index = bai::Index::new(
+            vec![
+                build_reference_sequence(&[100, 200, 300, 300])?,
+                build_reference_sequence(&[400])?,
+                build_reference_sequence(&[])?,
+            ],
+            None,
+        );
+
+        let dictionary: Dictionary = [("sq0", 65536), ("sq1", 20000), ("sq2", 10)]
+            .into_iter()
+            .collect();
+
+        let actual = from_binning_index(&dictionary, &index, NonZeroU64::new(150).unwrap())?;
+
+        let expected = [
+            Shard::new("sq0:1-32768".parse()?, 200),
+            Shard::new("sq0:32769-65536".parse()?, 100),
+            Shard::new("sq1:1-20000".parse()?, 0),
+            Shard::new("sq2:1-10".parse()?, 0),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_run() -> io::Result<()> {
+        let shards: Vec<_> = (0..8)
+            .map(|i| Shard::new(Region::new(format!("sq{i}"), ..), i))
+            .collect();
+
+        for worker_count in [1, 3, 16] {
+            let worker_count = NonZeroUsize::new(worker_count).unwrap();
+            let actual = run(&shards, worker_count, |shard| Ok(shard.compressed_size()))?;
+            assert_eq!(actual, (0..8).collect::<Vec<_>>());
+        }
+
+        let result = run(&shards, NonZeroUsize::new(3).unwrap(), |shard| {
+            if shard.compressed_size() == 5 {
+                Err(io::Error::from(io::ErrorKind::InvalidData))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(matches!(result, Err(ref e) if e.kind() == io::ErrorKind::InvalidData));
+
+        assert!(run(&[], NonZeroUsize::new(1).unwrap(), |_| Ok(()))?.is_empty());
+
+        Ok(())
+    }
+}
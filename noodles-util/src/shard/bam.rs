@@ -0,0 +1,87 @@
+//! Region-sharded processing of BAM files.
+
+use std::{
+    fs::File,
+    io,
+    num::{NonZeroU64, NonZeroUsize},
+    path::Path,
+};
+
+use noodles_bam::{self as bam, bai};
+use noodles_core::region_list::Dictionary;
+use noodles_sam::{self as sam, alignment::Record, AlignmentReader};
+
+use super::{from_binning_index, push_ext, run, Shard};
+
+/// Runs a function on the records of each shard of a BAM file in parallel.
+///
+/// The BAM index is read from `<src>.bai`. Shards are built from the index (see
+/// [`super::from_binning_index`]) with the given target compressed size. Each shard is processed
+/// with its own reader, and each record is given to the shard that contains its alignment start.
+///
+/// The results are returned in genome order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::num::{NonZeroU64, NonZeroUsize};
+/// use noodles_util::shard;
+///
+/// let counts = shard::bam::process(
+///     "sample.bam",
+///     NonZeroU64::new(1 << 26).unwrap(),
+///     NonZeroUsize::new(8).unwrap(),
+///     |_, _, records| {
+///         let mut n = 0;
+///
+///         for result in records {
+///             result?;
+///             n += 1;
+///         }
+///
+///         Ok(n)
+///     },
+/// )?;
+///
+/// let n: usize = counts.iter().sum();
+/// println!("{n}");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn process<P, F, T>(
+    src: P,
+    shard_size: NonZeroU64,
+    worker_count: NonZeroUsize,
+    f: F,
+) -> io::Result<Vec<T>>
+where
+    P: AsRef<Path>,
+    F: Fn(&Shard, &sam::Header, &mut dyn Iterator<Item = io::Result<Record>>) -> io::Result<T>
+        + Sync,
+    T: Send,
+{
+    let src = src.as_ref();
+
+    let index = bai::read(push_ext(src.into(), "bai"))?;
+
+    let header = File::open(src)
+        .map(bam::Reader::new)?
+        .read_alignment_header()?;
+
+    let dictionary = Dictionary::from(&header);
+    let shards = from_binning_index(&dictionary, &index, shard_size)?;
+
+    run(&shards, worker_count, |shard| {
+        let mut reader = File::open(src).map(bam::Reader::new)?;
+        let query = reader.query(&header, &index, shard.region())?;
+
+        let mut records = query.filter(|result| match result {
+            Ok(record) => record
+                .alignment_start()
+                .map(|start| shard.contains(start))
+                .unwrap_or(false),
+            Err(_) => true,
+        });
+
+        f(shard, &header, &mut records)
+    })
+}
@@ -0,0 +1,116 @@
+//! Region-sharded processing of BCF files.
+
+use std::{
+    fs::File,
+    io,
+    num::{NonZeroU64, NonZeroUsize},
+    path::Path,
+};
+
+use noodles_bcf::{self as bcf, header::StringMaps, Record};
+use noodles_core::{region_list::Dictionary, Position};
+use noodles_csi as csi;
+use noodles_vcf as vcf;
+
+use super::{from_binning_index, push_ext, run, Shard};
+
+/// Runs a function on the records of each shard of a BCF file in parallel.
+///
+/// The CSI is read from `<src>.csi`. Shards are built from the index (see
+/// [`super::from_binning_index`]) with the given target compressed size. Each shard is processed
+/// with its own reader, and each record is given to the shard that contains its position.
+///
+/// The function is given the VCF header and the string maps of the BCF header, e.g., to convert
+/// records to VCF records. The results are returned in genome order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::num::{NonZeroU64, NonZeroUsize};
+/// use noodles_util::shard;
+///
+/// let counts = shard::bcf::process(
+///     "sample.bcf",
+///     NonZeroU64::new(1 << 26).unwrap(),
+///     NonZeroUsize::new(8).unwrap(),
+///     |_, _, _, records| {
+///         let mut n = 0;
+///
+///         for result in records {
+///             result?;
+///             n += 1;
+///         }
+///
+///         Ok(n)
+///     },
+/// )?;
+///
+/// let n: usize = counts.iter().sum();
+/// println!("{n}");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn process<P, F, T>(
+    src: P,
+    shard_size: NonZeroU64,
+    worker_count: NonZeroUsize,
+    f: F,
+) -> io::Result<Vec<T>>
+where
+    P: AsRef<Path>,
+    F: Fn(
+            &Shard,
+            &vcf::Header,
+            &StringMaps,
+            &mut dyn Iterator<Item = io::Result<Record>>,
+        ) -> io::Result<T>
+        + Sync,
+    T: Send,
+{
+    let src = src.as_ref();
+
+    let index = csi::read(push_ext(src.into(), "csi"))?;
+
+    let mut reader = File::open(src).map(bcf::Reader::new)?;
+    reader.read_file_format()?;
+    let raw_header = reader.read_header()?;
+
+    let header: vcf::Header = raw_header
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let string_maps: StringMaps = raw_header
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    // The reference sequence IDs of a CSI refer to the contig string map.
+    let mut dictionary = Dictionary::new();
+
+    for i in 0.. {
+        let name = match string_maps.contigs().get_index(i) {
+            Some(name) => name,
+            None => break,
+        };
+
+        let length = header
+            .contigs()
+            .get(name)
+            .and_then(|contig| contig.length());
+        dictionary.insert(name, length);
+    }
+
+    let shards = from_binning_index(&dictionary, &index, shard_size)?;
+
+    run(&shards, worker_count, |shard| {
+        let mut reader = File::open(src).map(bcf::Reader::new)?;
+        let query = reader.query(string_maps.contigs(), &index, shard.region())?;
+
+        let mut records = query.filter(|result| match result {
+            Ok(record) => Position::new(usize::from(record.position()))
+                .map(|position| shard.contains(position))
+                .unwrap_or(false),
+            Err(_) => true,
+        });
+
+        f(shard, &header, &string_maps, &mut records)
+    })
+}
@@ -0,0 +1,102 @@
+//! Region-sharded processing of CRAM files.
+
+use std::{
+    fs::File,
+    io,
+    num::{NonZeroU64, NonZeroUsize},
+    path::Path,
+};
+
+use noodles_core::region_list::Dictionary;
+use noodles_cram::{self as cram, crai, Record};
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, AlignmentReader};
+
+use super::{from_crai, push_ext, run, Shard};
+
+/// Runs a function on the records of each shard of a CRAM file in parallel.
+///
+/// The CRAM index is read from `<src>.crai`. Shards are built from the index (see
+/// [`super::from_crai`]) with the given target compressed size. Each shard is processed with its
+/// own reader, and each record is given to the shard that contains its alignment start.
+///
+/// A reference sequence repository is not required to be thread-safe, so one is built for each
+/// shard using `make_reference_sequence_repository`.
+///
+/// The results are returned in genome order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::num::{NonZeroU64, NonZeroUsize};
+/// use noodles_fasta as fasta;
+/// use noodles_util::shard;
+///
+/// let counts = shard::cram::process(
+///     "sample.cram",
+///     fasta::Repository::default,
+///     NonZeroU64::new(1 << 26).unwrap(),
+///     NonZeroUsize::new(8).unwrap(),
+///     |_, _, records| {
+///         let mut n = 0;
+///
+///         for result in records {
+///             result?;
+///             n += 1;
+///         }
+///
+///         Ok(n)
+///     },
+/// )?;
+///
+/// let n: usize = counts.iter().sum();
+/// println!("{n}");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn process<P, G, F, T>(
+    src: P,
+    make_reference_sequence_repository: G,
+    shard_size: NonZeroU64,
+    worker_count: NonZeroUsize,
+    f: F,
+) -> io::Result<Vec<T>>
+where
+    P: AsRef<Path>,
+    G: Fn() -> fasta::Repository + Sync,
+    F: Fn(&Shard, &sam::Header, &mut dyn Iterator<Item = io::Result<Record>>) -> io::Result<T>
+        + Sync,
+    T: Send,
+{
+    let src = src.as_ref();
+
+    let index = crai::read(push_ext(src.into(), "crai"))?;
+
+    let header = File::open(src)
+        .map(cram::Reader::new)?
+        .read_alignment_header()?;
+
+    let dictionary = Dictionary::from(&header);
+    let shards = from_crai(&dictionary, &index, shard_size);
+
+    run(&shards, worker_count, |shard| {
+        let reference_sequence_repository = make_reference_sequence_repository();
+
+        let mut reader = File::open(src).map(cram::Reader::new)?;
+        let query = reader.query(
+            &reference_sequence_repository,
+            &header,
+            &index,
+            shard.region(),
+        )?;
+
+        let mut records = query.filter(|result| match result {
+            Ok(record) => record
+                .alignment_start()
+                .map(|start| shard.contains(start))
+                .unwrap_or(false),
+            Err(_) => true,
+        });
+
+        f(shard, &header, &mut records)
+    })
+}
@@ -0,0 +1,104 @@
+//! Region-sharded processing of bgzipped VCF files.
+
+use std::{
+    fs::File,
+    io,
+    num::{NonZeroU64, NonZeroUsize},
+    path::Path,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::{region_list::Dictionary, Position};
+use noodles_tabix as tabix;
+use noodles_vcf::{self as vcf, Record};
+
+use super::{from_binning_index, push_ext, run, Shard};
+
+/// Runs a function on the records of each shard of a bgzipped VCF file in parallel.
+///
+/// The tabix index is read from `<src>.tbi`. Shards are built from the index (see
+/// [`super::from_binning_index`]) with the given target compressed size. Each shard is processed
+/// with its own reader, and each record is given to the shard that contains its position.
+///
+/// The results are returned in genome order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::num::{NonZeroU64, NonZeroUsize};
+/// use noodles_util::shard;
+///
+/// let counts = shard::vcf::process(
+///     "sample.vcf.gz",
+///     NonZeroU64::new(1 << 26).unwrap(),
+///     NonZeroUsize::new(8).unwrap(),
+///     |_, _, records| {
+///         let mut n = 0;
+///
+///         for result in records {
+///             result?;
+///             n += 1;
+///         }
+///
+///         Ok(n)
+///     },
+/// )?;
+///
+/// let n: usize = counts.iter().sum();
+/// println!("{n}");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn process<P, F, T>(
+    src: P,
+    shard_size: NonZeroU64,
+    worker_count: NonZeroUsize,
+    f: F,
+) -> io::Result<Vec<T>>
+where
+    P: AsRef<Path>,
+    F: Fn(&Shard, &vcf::Header, &mut dyn Iterator<Item = io::Result<Record>>) -> io::Result<T>
+        + Sync,
+    T: Send,
+{
+    let src = src.as_ref();
+
+    let index = tabix::read(push_ext(src.into(), "tbi"))?;
+
+    let header: vcf::Header = File::open(src)
+        .map(bgzf::Reader::new)
+        .map(vcf::Reader::new)?
+        .read_header()?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    // The reference sequence IDs of a tabix index refer to its own list of names.
+    let mut dictionary = Dictionary::new();
+
+    for name in index.header().reference_sequence_names() {
+        let length = header
+            .contigs()
+            .get(name.as_str())
+            .and_then(|contig| contig.length());
+
+        dictionary.insert(name.as_str(), length);
+    }
+
+    let shards = from_binning_index(&dictionary, &index, shard_size)?;
+
+    run(&shards, worker_count, |shard| {
+        let mut reader = File::open(src)
+            .map(bgzf::Reader::new)
+            .map(vcf::Reader::new)?;
+
+        let query = reader.query(&header, &index, shard.region())?;
+
+        let mut records = query.filter(|result| match result {
+            Ok(record) => Position::new(usize::from(record.position()))
+                .map(|position| shard.contains(position))
+                .unwrap_or(false),
+            Err(_) => true,
+        });
+
+        f(shard, &header, &mut records)
+    })
+}
@@ -0,0 +1,5 @@
+//! Variant format utilities.
+
+pub mod concat;
+pub mod filter;
+pub mod normalize;
@@ -0,0 +1,701 @@
+//! Concatenation of VCF and BCF files that share samples.
+//!
+//! Inputs are typically the outputs of a chromosome- or region-split workflow. They must have the
+//! same samples, in the same order, and their records are written in input order.
+//!
+//! [`vcf`] and [`bcf`] stream and, if necessary, re-encode each record. When all inputs are
+//! bgzipped and have identical headers, [`copy_vcf_blocks`] and [`copy_bcf_blocks`] are much
+//! faster: the compressed BGZF blocks after each header are copied as-is, and only the blocks
+//! that contain the end of a header are re-encoded.
+
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+use noodles_bcf as bcf;
+use noodles_bgzf as bgzf;
+use noodles_vcf as vcf;
+
+// § 4.1 The BGZF compression format (2021-06-03)
+const BGZF_HEADER_SIZE: usize = 18;
+const GZIP_TRAILER_SIZE: usize = 8;
+
+/// Builds the header of concatenated VCF files.
+///
+/// All headers must have the same sample names, in the same order. The output header is the
+/// first header with the contigs, filters, and `INFO` and `FORMAT` definitions of the other
+/// headers added (see [`vcf::merge::merge_headers`]).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_util::variant::concat;
+/// use noodles_vcf as vcf;
+///
+/// let header_a = vcf::Header::builder().add_sample_name("sample0").build();
+/// let header_b = vcf::Header::builder().add_sample_name("sample0").build();
+/// assert!(concat::merge_headers(&[header_a, header_b]).is_ok());
+///
+/// let header_a = vcf::Header::builder().add_sample_name("sample0").build();
+/// let header_b = vcf::Header::builder().add_sample_name("sample1").build();
+/// assert!(concat::merge_headers(&[header_a, header_b]).is_err());
+/// ```
+pub fn merge_headers(headers: &[vcf::Header]) -> io::Result<vcf::Header> {
+    let first = headers
+        .first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no headers to concatenate"))?;
+
+    let sample_names = first.sample_names();
+
+    for header in &headers[1..] {
+        if !header.sample_names().iter().eq(sample_names.iter()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "sample names mismatch",
+            ));
+        }
+    }
+
+    let headers: Vec<_> = headers
+        .iter()
+        .map(|header| {
+            let mut header = header.clone();
+            header.sample_names_mut().clear();
+            header
+        })
+        .collect();
+
+    let mut header = vcf::merge::merge_headers(&headers)?;
+    *header.sample_names_mut() = sample_names.clone();
+
+    Ok(header)
+}
+
+/// Concatenates VCF files.
+///
+/// The header of each input is read and checked for compatibility (see [`merge_headers`]). The
+/// merged header is written, followed by the records of each input. The merged header is returned.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_util::variant::concat;
+/// use noodles_vcf as vcf;
+///
+/// let src_a = b"##fileformat=VCFv4.3\n##contig=<ID=sq0>
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+/// sq0\t1\t.\tA\t.\t.\tPASS\t.
+/// ";
+///
+/// let src_b = b"##fileformat=VCFv4.3\n##contig=<ID=sq1>
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+/// sq1\t1\t.\tA\t.\t.\tPASS\t.
+/// ";
+///
+/// let mut readers = [vcf::Reader::new(&src_a[..]), vcf::Reader::new(&src_b[..])];
+/// let mut writer = vcf::Writer::new(Vec::new());
+///
+/// let header = concat::vcf(&mut readers, &mut writer)?;
+/// assert_eq!(header.contigs().len(), 2);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn vcf<R, W>(
+    readers: &mut [vcf::Reader<R>],
+    writer: &mut vcf::Writer<W>,
+) -> io::Result<vcf::Header>
+where
+    R: BufRead,
+    W: Write,
+{
+    let headers = readers
+        .iter_mut()
+        .map(|reader| {
+            reader.read_header().and_then(|s| {
+                s.parse::<vcf::Header>()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let header = merge_headers(&headers)?;
+    writer.write_header(&header)?;
+
+    for (reader, reader_header) in readers.iter_mut().zip(&headers) {
+        for result in reader.records(reader_header) {
+            let record = result?;
+            writer.write_record(&record)?;
+        }
+    }
+
+    Ok(header)
+}
+
+/// Concatenates BCF files.
+///
+/// The header of each input is read and checked for compatibility (see [`merge_headers`]). The
+/// merged header is written, followed by the records of each input. Records are copied directly
+/// when the string maps of their input match the merged header; otherwise, they are re-encoded.
+/// The merged header is returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles_bcf as bcf;
+/// use noodles_util::variant::concat;
+///
+/// let mut readers = [
+///     File::open("sq0.bcf").map(bcf::Reader::new)?,
+///     File::open("sq1.bcf").map(bcf::Reader::new)?,
+/// ];
+///
+/// let mut writer = File::create("out.bcf").map(bcf::Writer::new)?;
+/// concat::bcf(&mut readers, &mut writer)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn bcf<R, W>(
+    readers: &mut [bcf::Reader<R>],
+    writer: &mut bcf::Writer<W>,
+) -> io::Result<vcf::Header>
+where
+    R: Read,
+    W: Write,
+{
+    let mut headers = Vec::with_capacity(readers.len());
+    let mut string_maps = Vec::with_capacity(readers.len());
+
+    for reader in readers.iter_mut() {
+        reader.read_file_format()?;
+        let raw_header = reader.read_header()?;
+
+        let header: vcf::Header = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let reader_string_maps: bcf::header::StringMaps = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        headers.push(header);
+        string_maps.push(reader_string_maps);
+    }
+
+    let header = merge_headers(&headers)?;
+    let header_string_maps = bcf::header::StringMaps::from(&header);
+
+    writer.write_file_format()?;
+    writer.write_header(&header)?;
+
+    for ((reader, reader_header), reader_string_maps) in
+        readers.iter_mut().zip(&headers).zip(&string_maps)
+    {
+        let is_compatible = reader_string_maps == &header_string_maps;
+
+        for result in reader.records() {
+            let record = result?;
+
+            if is_compatible {
+                writer.write_record(&record)?;
+            } else {
+                let vcf_record = record.try_into_vcf_record(reader_header, reader_string_maps)?;
+                writer.write_vcf_record(&header, &header_string_maps, &vcf_record)?;
+            }
+        }
+    }
+
+    Ok(header)
+}
+
+/// Concatenates bgzipped VCF files with identical headers by copying BGZF blocks.
+///
+/// The header of the first input is written, followed by the data of each input. Only the block
+/// that contains the end of the header of each input is decompressed and re-encoded; all
+/// subsequent blocks are copied verbatim. Empty blocks, including the end-of-file markers of the
+/// inputs, are dropped, and a single end-of-file marker is written at the end.
+///
+/// `writer` is the raw output stream, i.e., it is not wrapped in a BGZF encoder.
+///
+/// The readers must not have been read from. Their underlying streams can start at any offset,
+/// e.g., after data that is not part of the BGZF file.
+///
+/// An error is returned if a reader was already read from or if any header differs from the
+/// first.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles_bgzf as bgzf;
+/// use noodles_util::variant::concat;
+///
+/// let mut readers = [
+///     File::open("sq0.vcf.gz").map(bgzf::Reader::new)?,
+///     File::open("sq1.vcf.gz").map(bgzf::Reader::new)?,
+/// ];
+///
+/// let mut writer = File::create("out.vcf.gz")?;
+/// concat::copy_vcf_blocks(&mut readers, &mut writer)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn copy_vcf_blocks<R, W>(readers: &mut [bgzf::Reader<R>], writer: W) -> io::Result<()>
+where
+    R: Read + Seek,
+    W: Write,
+{
+    copy_blocks(readers, writer, read_raw_vcf_header)
+}
+
+/// Concatenates BCF files with identical headers by copying BGZF blocks.
+///
+/// This is the same as [`copy_vcf_blocks`] but for BCF inputs. The header includes the file
+/// format version, which must also match.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles_bgzf as bgzf;
+/// use noodles_util::variant::concat;
+///
+/// let mut readers = [
+///     File::open("sq0.bcf").map(bgzf::Reader::new)?,
+///     File::open("sq1.bcf").map(bgzf::Reader::new)?,
+/// ];
+///
+/// let mut writer = File::create("out.bcf")?;
+/// concat::copy_bcf_blocks(&mut readers, &mut writer)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn copy_bcf_blocks<R, W>(readers: &mut [bgzf::Reader<R>], writer: W) -> io::Result<()>
+where
+    R: Read + Seek,
+    W: Write,
+{
+    copy_blocks(readers, writer, read_raw_bcf_header)
+}
+
+fn copy_blocks<R, W, F>(
+    readers: &mut [bgzf::Reader<R>],
+    mut writer: W,
+    read_raw_header: F,
+) -> io::Result<()>
+where
+    R: Read + Seek,
+    W: Write,
+    F: Fn(&mut bgzf::Reader<R>) -> io::Result<Vec<u8>>,
+{
+    let mut first_header: Option<Vec<u8>> = None;
+
+    for reader in readers.iter_mut() {
+        // The (virtual) positions of a BGZF reader are relative to the offset of the underlying
+        // stream when the reader read its first block.
+        if reader.position() != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "BGZF reader was already read from",
+            ));
+        }
+
+        let start = reader.get_mut().stream_position()?;
+
+        let header = read_raw_header(reader)?;
+
+        let mut encoder = match &first_header {
+            Some(h) if h != &header => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "headers mismatch",
+                ));
+            }
+            Some(_) => bgzf::Writer::new(&mut writer),
+            None => {
+                let mut encoder = bgzf::Writer::new(&mut writer);
+                encoder.write_all(&header)?;
+                first_header = Some(header);
+                encoder
+            }
+        };
+
+        // Re-encode the remainder of the block that contains the end of the header.
+        let buf = reader.fill_buf()?;
+        let len = buf.len();
+        encoder.write_all(buf)?;
+        reader.consume(len);
+
+        encoder.flush()?;
+        encoder.into_inner();
+
+        // The block was consumed, so the virtual position is at the start of the next block.
+        let virtual_position = reader.virtual_position();
+        debug_assert_eq!(virtual_position.uncompressed(), 0);
+
+        let inner = reader.get_mut();
+        inner.seek(SeekFrom::Start(start + virtual_position.compressed()))?;
+        copy_raw_blocks(inner, &mut writer)?;
+    }
+
+    bgzf::Writer::new(&mut writer).finish()?;
+
+    Ok(())
+}
+
+fn read_raw_vcf_header<R>(reader: &mut bgzf::Reader<R>) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    vcf::Reader::new(reader)
+        .read_header()
+        .map(|s| s.into_bytes())
+}
+
+fn read_raw_bcf_header<R>(reader: &mut bgzf::Reader<R>) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    // magic (3) + major version (1) + minor version (1) + l_text (4)
+    let mut buf = vec![0; 9];
+    reader.read_exact(&mut buf)?;
+
+    if &buf[..3] != b"BCF" {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid BCF header",
+        ));
+    }
+
+    let l_text = u32::from_le_bytes([buf[5], buf[6], buf[7], buf[8]]);
+    let len = usize::try_from(l_text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let start = buf.len();
+    buf.resize(start + len, 0);
+    reader.read_exact(&mut buf[start..])?;
+
+    Ok(buf)
+}
+
+fn copy_raw_blocks<R, W>(reader: &mut R, writer: &mut W) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    let mut buf = Vec::new();
+
+    loop {
+        buf.resize(BGZF_HEADER_SIZE, 0);
+
+        match reader.read(&mut buf[..1])? {
+            0 => break,
+            _ => reader.read_exact(&mut buf[1..])?,
+        }
+
+        // ID1, ID2, and the BGZF extra subfield identifiers (SI1, SI2)
+        if buf[..2] != [0x1f, 0x8b] || buf[12..14] != *b"BC" {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid BGZF block header",
+            ));
+        }
+
+        let bsize = u16::from_le_bytes([buf[16], buf[17]]);
+        let block_size = usize::from(bsize) + 1;
+
+        if block_size < BGZF_HEADER_SIZE + GZIP_TRAILER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid BGZF block size",
+            ));
+        }
+
+        buf.resize(block_size, 0);
+        reader.read_exact(&mut buf[BGZF_HEADER_SIZE..])?;
+
+        let r#isize = &buf[block_size - 4..];
+
+        if r#isize != [0, 0, 0, 0] {
+            writer.write_all(&buf)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    const HEADER: &str = "##fileformat=VCFv4.3
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+";
+
+    fn build_records(chromosome: &str, n: usize) -> String {
+        (1..=n)
+            .map(|i| format!("{chromosome}\t{i}\t.\tA\t.\t.\tPASS\t.\tGT\t0|0\n"))
+            .collect()
+    }
+
+    fn bgzip(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(data)?;
+        writer.finish()
+    }
+
+    fn bgunzip(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut reader = bgzf::Reader::new(data);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn build_bcf(src: &str) -> io::Result<Vec<u8>> {
+        let mut reader = vcf::Reader::new(src.as_bytes());
+        let header: vcf::Header = reader
+            .read_header()?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let string_maps = bcf::header::StringMaps::from(&header);
+
+        let mut writer = bcf::Writer::new(Vec::new());
+        writer.write_file_format()?;
+        writer.write_header(&header)?;
+
+        for result in reader.records(&header) {
+            let record = result?;
+            writer.write_vcf_record(&header, &string_maps, &record)?;
+        }
+
+        writer.try_finish()?;
+
+        Ok(writer.get_ref().get_ref().clone())
+    }
+
+    fn read_bcf_records(src: &[u8]) -> io::Result<Vec<String>> {
+        let mut reader = bcf::Reader::new(src);
+        reader.read_file_format()?;
+        let raw_header = reader.read_header()?;
+        let header: vcf::Header = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let string_maps: bcf::header::StringMaps = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        reader
+            .records()
+            .map(|result| {
+                result
+                    .and_then(|record| record.try_into_vcf_record(&header, &string_maps))
+                    .map(|record| record.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_headers() -> io::Result<()> {
+        assert!(matches!(
+            merge_headers(&[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let header_a = vcf::Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build();
+
+        let header_b = vcf::Header::builder()
+            .add_sample_name("sample1")
+            .add_sample_name("sample0")
+            .build();
+
+        assert!(matches!(
+            merge_headers(&[header_a.clone(), header_b]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let header = merge_headers(&[header_a.clone(), header_a.clone()])?;
+        assert_eq!(header, header_a);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vcf() -> io::Result<()> {
+        let records_a = build_records("sq0", 2);
+        let records_b = build_records("sq1", 3);
+
+        let src_a = format!("{HEADER}{records_a}");
+        let src_b = format!("{HEADER}{records_b}");
+
+        let mut readers = [
+            vcf::Reader::new(src_a.as_bytes()),
+            vcf::Reader::new(src_b.as_bytes()),
+        ];
+        let mut writer = vcf::Writer::new(Vec::new());
+        let header = vcf(&mut readers, &mut writer)?;
+
+        let expected = format!("{header}{records_a}{records_b}");
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bcf() -> io::Result<()> {
+        let records_a = build_records("sq0", 2);
+        let records_b = build_records("sq1", 3);
+
+        // The second input has a different contig order, so its records are re-encoded.
+        let header_b = HEADER.replace(
+            "##contig=<ID=sq0>\n##contig=<ID=sq1>\n",
+            "##contig=<ID=sq1>\n##contig=<ID=sq0>\n",
+        );
+
+        let src_a = build_bcf(&format!("{HEADER}{records_a}"))?;
+        let src_b = build_bcf(&format!("{header_b}{records_b}"))?;
+
+        let mut readers = [bcf::Reader::new(&src_a[..]), bcf::Reader::new(&src_b[..])];
+        let mut writer = bcf::Writer::new(Vec::new());
+        bcf(&mut readers, &mut writer)?;
+        writer.try_finish()?;
+
+        let actual = read_bcf_records(writer.get_ref().get_ref())?;
+        let expected: Vec<_> = records_a
+            .lines()
+            .chain(records_b.lines())
+            .map(String::from)
+            .collect();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_vcf_blocks() -> io::Result<()> {
+        let records_a = build_records("sq0", 2);
+        let records_b = build_records("sq1", 3);
+
+        let src_a = bgzip(format!("{HEADER}{records_a}").as_bytes())?;
+        let src_b = bgzip(format!("{HEADER}{records_b}").as_bytes())?;
+
+        let mut readers = [
+            bgzf::Reader::new(Cursor::new(src_a)),
+            bgzf::Reader::new(Cursor::new(src_b.clone())),
+        ];
+        let mut dst = Vec::new();
+        copy_vcf_blocks(&mut readers, &mut dst)?;
+
+        let expected = format!("{HEADER}{records_a}{records_b}");
+        assert_eq!(bgunzip(&dst)?, expected.as_bytes());
+        assert!(dst.ends_with(&bgzip(b"")?));
+
+        let src_c = bgzip(HEADER.replace("sample0", "sample1").as_bytes())?;
+        let mut readers = [
+            bgzf::Reader::new(Cursor::new(src_b)),
+            bgzf::Reader::new(Cursor::new(src_c)),
+        ];
+        assert!(matches!(
+            copy_vcf_blocks(&mut readers, io::sink()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_vcf_blocks_with_multiple_blocks() -> io::Result<()> {
+        // Write the header and each record in its own block to exercise verbatim block copies.
+        fn bgzip_lines(src: &str) -> io::Result<Vec<u8>> {
+            let mut writer = bgzf::Writer::new(Vec::new());
+
+            for (i, line) in src.split_inclusive('\n').enumerate() {
+                writer.write_all(line.as_bytes())?;
+
+                if i > 0 {
+                    writer.flush()?;
+                }
+            }
+
+            writer.finish()
+        }
+
+        let records_a = build_records("sq0", 4);
+        let records_b = build_records("sq1", 5);
+
+        let src_a = bgzip_lines(&format!("{HEADER}{records_a}"))?;
+        let src_b = bgzip_lines(&format!("{HEADER}{records_b}"))?;
+
+        let mut readers = [
+            bgzf::Reader::new(Cursor::new(src_a)),
+            bgzf::Reader::new(Cursor::new(src_b)),
+        ];
+        let mut dst = Vec::new();
+        copy_vcf_blocks(&mut readers, &mut dst)?;
+
+        let expected = format!("{HEADER}{records_a}{records_b}");
+        assert_eq!(bgunzip(&dst)?, expected.as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_vcf_blocks_with_stream_offset() -> io::Result<()> {
+        const PREFIX: &[u8] = b"noodles";
+
+        let records_a = build_records("sq0", 2);
+        let records_b = build_records("sq1", 3);
+
+        let src_a = bgzip(format!("{HEADER}{records_a}").as_bytes())?;
+
+        let mut src_b = PREFIX.to_vec();
+        src_b.extend(bgzip(format!("{HEADER}{records_b}").as_bytes())?);
+        let mut inner_b = Cursor::new(src_b);
+        inner_b.seek(SeekFrom::Start(PREFIX.len() as u64))?;
+
+        let mut readers = [
+            bgzf::Reader::new(Cursor::new(src_a.clone())),
+            bgzf::Reader::new(inner_b),
+        ];
+        let mut dst = Vec::new();
+        copy_vcf_blocks(&mut readers, &mut dst)?;
+
+        let expected = format!("{HEADER}{records_a}{records_b}");
+        assert_eq!(bgunzip(&dst)?, expected.as_bytes());
+
+        let mut reader = bgzf::Reader::new(Cursor::new(src_a));
+        reader.read_exact(&mut [0; 1])?;
+        assert!(matches!(
+            copy_vcf_blocks(&mut [reader], io::sink()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_bcf_blocks() -> io::Result<()> {
+        let records_a = build_records("sq0", 2);
+        let records_b = build_records("sq1", 3);
+
+        let src_a = build_bcf(&format!("{HEADER}{records_a}"))?;
+        let src_b = build_bcf(&format!("{HEADER}{records_b}"))?;
+
+        let mut readers = [
+            bgzf::Reader::new(Cursor::new(src_a)),
+            bgzf::Reader::new(Cursor::new(src_b)),
+        ];
+        let mut dst = Vec::new();
+        copy_bcf_blocks(&mut readers, &mut dst)?;
+
+        let actual = read_bcf_records(&dst)?;
+        let expected: Vec<_> = records_a
+            .lines()
+            .chain(records_b.lines())
+            .map(String::from)
+            .collect();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
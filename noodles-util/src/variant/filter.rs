@@ -0,0 +1,641 @@
+//! Variant record filter expressions.
+//!
+//! A filter expression is a predicate on a VCF record, similar to the `-i`/`-e` expressions of
+//! bcftools, e.g., `QUAL >= 30 && INFO/DP > 10 && FILTER == "PASS"`.
+//!
+//! The following fields are available.
+//!
+//!   * `CHROM`: the reference sequence name.
+//!   * `POS`: the position.
+//!   * `ID`: the list of IDs.
+//!   * `REF`: the reference bases.
+//!   * `ALT`: the list of alternate bases.
+//!   * `QUAL`: the quality score.
+//!   * `FILTER`: the list of filters, which is `PASS` when the record passed filters.
+//!   * `TYPE`: the list of variant types of the alternate alleles: `snp`, `mnp`, `indel`, `sv`
+//!     (symbolic and breakend alleles), or `other` (overlapping deletions). The unspecified
+//!     allele (`<*>`) is skipped, and a record without other alternate alleles has the type `ref`.
+//!   * `INFO/<key>`: an `INFO` field. The `INFO/` prefix may be omitted. Flags are true when
+//!     present.
+//!   * `FMT/<key>` or `FORMAT/<key>`: a per-sample `FORMAT` field.
+//!
+//! `INFO` and `FORMAT` keys must be defined in the header. A field that is not set in a record
+//! is missing.
+//!
+//! An element of a list is selected with an index, e.g., `INFO/AC[0]`. Comparisons with a list
+//! are true if any element satisfies the comparison, and comparisons with a missing value are
+//! false.
+//!
+//! An expression that uses `FORMAT` fields is evaluated per sample and is true if it is true for
+//! any sample. This includes the logical operators, e.g., `FMT/GQ > 20 && FMT/DP > 10` requires
+//! a single sample to satisfy both comparisons. The functions `any(...)` and `all(...)` make this
+//! explicit, e.g., `all(FMT/GQ >= 20)` requires every sample to have a genotype quality of at
+//! least 20. A record without samples is evaluated with empty `FORMAT` values.
+//!
+//! Numbers support the arithmetic operators `+`, `-`, `*`, and `/`; values can be compared with
+//! `==` (or `=`), `!=`, `<`, `<=`, `>`, and `>=`; and predicates are combined with `&&`, `||`,
+//! `!`, and parentheses. `x in (a, b, ...)` tests whether a value is equal to any of the given
+//! literals, e.g., `FILTER in ("PASS", "q10")`.
+//!
+//! Expressions are parsed and evaluated by [`noodles_core::expression`].
+
+pub use noodles_core::expression::ParseError;
+
+use std::{borrow::Cow, io};
+
+use noodles_core::expression::{self, BinaryOperator, Value};
+
+use noodles_vcf::{
+    self as vcf,
+    header::{format, info},
+    record::{
+        alternate_bases::{allele::Symbol, Allele},
+        genotypes::genotype,
+        info::field,
+        reference_bases::Base,
+        Chromosome, Filters,
+    },
+};
+
+/// A parsed variant record filter expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression(expression::Expression<Field, Function>);
+
+impl Expression {
+    /// Parses a filter expression.
+    ///
+    /// `INFO` and `FORMAT` keys are resolved using the given header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::filter::{Expression, ParseError};
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::default();
+    ///
+    /// assert!(Expression::parse("QUAL >= 30 && TYPE == \"snp\"", &header).is_ok());
+    ///
+    /// assert_eq!(
+    ///     Expression::parse("INFO/DP > 10", &header),
+    ///     Err(ParseError::InvalidField(String::from("INFO/DP")))
+    /// );
+    /// ```
+    pub fn parse(s: &str, header: &vcf::Header) -> Result<Self, ParseError> {
+        expression::Expression::parse(s, &Resolver { header }).map(Self)
+    }
+
+    /// Evaluates the filter expression against a VCF record.
+    ///
+    /// The record is borrowed and is not modified. To filter a BCF record, first convert it to a
+    /// VCF record.
+    ///
+    /// An error is returned if values of incompatible types are compared or used in arithmetic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::filter::Expression;
+    /// use noodles_vcf::{self as vcf, record::Position};
+    ///
+    /// let header = vcf::Header::default();
+    /// let expression = Expression::parse("QUAL >= 30 && TYPE == \"snp\"", &header)?;
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(Position::from(1))
+    ///     .set_reference_bases("A".parse()?)
+    ///     .set_alternate_bases("G".parse()?)
+    ///     .set_quality_score(vcf::record::QualityScore::try_from(34.0)?)
+    ///     .build()?;
+    ///
+    /// assert!(expression.evaluate(&record)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn evaluate(&self, record: &vcf::Record) -> io::Result<bool> {
+        if !record.genotypes().is_empty() && uses_format_fields(&self.0) {
+            any_sample(&self.0, record)
+        } else {
+            let evaluator = Evaluator {
+                record,
+                sample: None,
+            };
+
+            self.0.evaluate(&evaluator).map(|value| value.is_truthy())
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Field {
+    Chromosome,
+    Position,
+    Ids,
+    ReferenceBases,
+    AlternateBases,
+    QualityScore,
+    Filters,
+    Type,
+    Info(info::Key),
+    Format(format::Key),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Function {
+    Any,
+    All,
+}
+
+struct Resolver<'h> {
+    header: &'h vcf::Header,
+}
+
+impl expression::Resolver for Resolver<'_> {
+    type Field = Field;
+    type Function = Function;
+
+    fn is_namespace(&self, name: &str) -> bool {
+        matches!(name, "INFO" | "FMT" | "FORMAT")
+    }
+
+    fn resolve_field(&self, name: &str) -> Option<Self::Field> {
+        match name {
+            "CHROM" => Some(Field::Chromosome),
+            "POS" => Some(Field::Position),
+            "ID" => Some(Field::Ids),
+            "REF" => Some(Field::ReferenceBases),
+            "ALT" => Some(Field::AlternateBases),
+            "QUAL" => Some(Field::QualityScore),
+            "FILTER" => Some(Field::Filters),
+            "TYPE" => Some(Field::Type),
+            _ => {
+                if let Some(raw_key) = name
+                    .strip_prefix("FMT/")
+                    .or_else(|| name.strip_prefix("FORMAT/"))
+                {
+                    let key: format::Key = raw_key.parse().ok()?;
+
+                    return self
+                        .header
+                        .formats()
+                        .contains_key(&key)
+                        .then_some(Field::Format(key));
+                }
+
+                let raw_key = name.strip_prefix("INFO/").unwrap_or(name);
+                let key: info::Key = raw_key.parse().ok()?;
+
+                self.header
+                    .infos()
+                    .contains_key(&key)
+                    .then_some(Field::Info(key))
+            }
+        }
+    }
+
+    fn resolve_function(&self, name: &str) -> Option<Self::Function> {
+        match name {
+            "any" => Some(Function::Any),
+            "all" => Some(Function::All),
+            _ => None,
+        }
+    }
+}
+
+type Expr = expression::Expression<Field, Function>;
+
+struct Evaluator<'r> {
+    record: &'r vcf::Record,
+    sample: Option<usize>,
+}
+
+impl<'r> expression::Evaluator<'r> for Evaluator<'r> {
+    type Field = Field;
+    type Function = Function;
+
+    fn evaluate_field(&self, field: &Self::Field) -> io::Result<Value<'r>> {
+        Ok(field_value(field, self.record, self.sample))
+    }
+
+    fn evaluate_call(&self, function: &Self::Function, expr: &Expr) -> io::Result<Value<'r>> {
+        let value = match function {
+            Function::Any => any_sample(expr, self.record)?,
+            Function::All => all_samples(expr, self.record)?,
+        };
+
+        Ok(Value::Bool(value))
+    }
+
+    fn compare_field(
+        &self,
+        field: &Self::Field,
+        index: Option<usize>,
+        operator: BinaryOperator,
+        s: &str,
+    ) -> Option<io::Result<bool>> {
+        let is_eq = operator == BinaryOperator::Eq;
+
+        let is_match = match field {
+            Field::ReferenceBases => match index {
+                None | Some(0) => bases_eq(self.record.reference_bases(), s) == is_eq,
+                Some(_) => false,
+            },
+            Field::AlternateBases => {
+                let alternate_bases = self.record.alternate_bases();
+
+                match index {
+                    Some(i) => match alternate_bases.get(i) {
+                        Some(allele) => allele_eq(allele, s)? == is_eq,
+                        None => false,
+                    },
+                    None => {
+                        let mut is_match = false;
+
+                        for allele in alternate_bases.iter() {
+                            if allele_eq(allele, s)? == is_eq {
+                                is_match = true;
+                                break;
+                            }
+                        }
+
+                        is_match
+                    }
+                }
+            }
+            _ => return None,
+        };
+
+        Some(Ok(is_match))
+    }
+}
+
+fn bases_eq(bases: &[Base], s: &str) -> bool {
+    bases.len() == s.len()
+        && bases
+            .iter()
+            .zip(s.bytes())
+            .all(|(&base, b)| char::from(base) as u8 == b)
+}
+
+// Returns `None` if the allele is not a list of bases, i.e., it is compared as a string.
+fn allele_eq(allele: &Allele, s: &str) -> Option<bool> {
+    match allele {
+        Allele::Bases(bases) => Some(bases_eq(bases, s)),
+        _ => None,
+    }
+}
+
+// Returns whether the expression uses `FORMAT` fields outside of `any` and `all` calls, which
+// evaluate samples themselves.
+fn uses_format_fields(expr: &Expr) -> bool {
+    match expr {
+        Expr::Literal(_) | Expr::Call(..) => false,
+        Expr::Field(field, _) => matches!(field, Field::Format(_)),
+        Expr::Unary(_, expr) | Expr::In(expr, _) => uses_format_fields(expr),
+        Expr::Binary(_, lhs, rhs) => uses_format_fields(lhs) || uses_format_fields(rhs),
+    }
+}
+
+fn evaluate_sample(expr: &Expr, record: &vcf::Record, i: usize) -> io::Result<bool> {
+    let evaluator = Evaluator {
+        record,
+        sample: Some(i),
+    };
+
+    expr.evaluate(&evaluator).map(|value| value.is_truthy())
+}
+
+fn any_sample(expr: &Expr, record: &vcf::Record) -> io::Result<bool> {
+    for i in 0..record.genotypes().len() {
+        if evaluate_sample(expr, record, i)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+fn all_samples(expr: &Expr, record: &vcf::Record) -> io::Result<bool> {
+    for i in 0..record.genotypes().len() {
+        if !evaluate_sample(expr, record, i)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn field_value<'r>(field: &Field, record: &'r vcf::Record, sample: Option<usize>) -> Value<'r> {
+    match field {
+        Field::Chromosome => match record.chromosome() {
+            Chromosome::Name(name) => Value::String(Cow::Borrowed(name)),
+            chromosome => Value::String(Cow::Owned(chromosome.to_string())),
+        },
+        Field::Position => Value::Number(usize::from(record.position()) as f64),
+        Field::Ids => Value::List(
+            record
+                .ids()
+                .iter()
+                .map(|id| Value::String(Cow::Borrowed(id)))
+                .collect(),
+        ),
+        Field::ReferenceBases => Value::String(Cow::Owned(record.reference_bases().to_string())),
+        Field::AlternateBases => Value::List(
+            record
+                .alternate_bases()
+                .iter()
+                .map(|allele| Value::String(Cow::Owned(allele.to_string())))
+                .collect(),
+        ),
+        Field::QualityScore => record
+            .quality_score()
+            .map(|score| Value::Number(f64::from(f32::from(score))))
+            .unwrap_or(Value::Missing),
+        Field::Filters => match record.filters() {
+            Some(Filters::Pass) => Value::List(vec![Value::String(Cow::Borrowed("PASS"))]),
+            Some(Filters::Fail(ids)) => Value::List(
+                ids.iter()
+                    .map(|id| Value::String(Cow::Borrowed(id)))
+                    .collect(),
+            ),
+            None => Value::Missing,
+        },
+        Field::Type => Value::List(variant_types(record)),
+        Field::Info(key) => match record.info().get(key) {
+            Some(Some(value)) => info_value(value),
+            _ => Value::Missing,
+        },
+        Field::Format(key) => match sample {
+            Some(i) => sample_value(record, i, key),
+            None => Value::List(
+                (0..record.genotypes().len())
+                    .map(|i| sample_value(record, i, key))
+                    .collect(),
+            ),
+        },
+    }
+}
+
+fn variant_types(record: &vcf::Record) -> Vec<Value<'static>> {
+    let reference_bases_len = record.reference_bases().len();
+    let mut types = Vec::new();
+
+    for allele in record.alternate_bases().iter() {
+        let ty = match allele {
+            Allele::Bases(bases) if bases.len() != reference_bases_len => "indel",
+            Allele::Bases(bases) if bases.len() == 1 => "snp",
+            Allele::Bases(_) => "mnp",
+            Allele::Symbol(Symbol::Unspecified) => continue,
+            Allele::Symbol(_) | Allele::Breakend(_) => "sv",
+            Allele::OverlappingDeletion => "other",
+        };
+
+        let value = Value::String(Cow::Borrowed(ty));
+
+        if !types.contains(&value) {
+            types.push(value);
+        }
+    }
+
+    if types.is_empty() {
+        types.push(Value::String(Cow::Borrowed("ref")));
+    }
+
+    types
+}
+
+fn info_value(value: &field::Value) -> Value<'_> {
+    match value {
+        field::Value::Integer(n) => Value::Number(f64::from(*n)),
+        field::Value::Float(n) => Value::Number(f64::from(*n)),
+        field::Value::Flag => Value::Bool(true),
+        field::Value::Character(c) => Value::String(Cow::Owned(c.to_string())),
+        field::Value::String(s) => Value::String(Cow::Borrowed(s)),
+        field::Value::IntegerArray(values) => list(values, |n| Value::Number(f64::from(*n))),
+        field::Value::FloatArray(values) => list(values, |n| Value::Number(f64::from(*n))),
+        field::Value::CharacterArray(values) => {
+            list(values, |c| Value::String(Cow::Owned(c.to_string())))
+        }
+        field::Value::StringArray(values) => list(values, |s| Value::String(Cow::Borrowed(s))),
+    }
+}
+
+fn sample_value<'r>(record: &'r vcf::Record, i: usize, key: &format::Key) -> Value<'r> {
+    let value = record
+        .genotypes()
+        .get(i)
+        .and_then(|sample| sample.get(key))
+        .and_then(|value| value.as_ref());
+
+    match value {
+        Some(genotype::field::Value::Integer(n)) => Value::Number(f64::from(*n)),
+        Some(genotype::field::Value::Float(n)) => Value::Number(f64::from(*n)),
+        Some(genotype::field::Value::Character(c)) => Value::String(Cow::Owned(c.to_string())),
+        Some(genotype::field::Value::String(s)) => Value::String(Cow::Borrowed(s)),
+        Some(genotype::field::Value::IntegerArray(values)) => {
+            list(values, |n| Value::Number(f64::from(*n)))
+        }
+        Some(genotype::field::Value::FloatArray(values)) => {
+            list(values, |n| Value::Number(f64::from(*n)))
+        }
+        Some(genotype::field::Value::CharacterArray(values)) => {
+            list(values, |c| Value::String(Cow::Owned(c.to_string())))
+        }
+        Some(genotype::field::Value::StringArray(values)) => {
+            list(values, |s| Value::String(Cow::Borrowed(s)))
+        }
+        None => Value::Missing,
+    }
+}
+
+fn list<'a, T, F>(values: &'a [Option<T>], f: F) -> Value<'a>
+where
+    F: Fn(&'a T) -> Value<'a>,
+{
+    Value::List(
+        values
+            .iter()
+            .map(|value| value.as_ref().map_or(Value::Missing, &f))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_header() -> Result<vcf::Header, Box<dyn std::error::Error>> {
+        let header = "##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Combined depth across samples\">
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##INFO=<ID=DB,Number=0,Type=Flag,Description=\"dbSNP membership\">
+##FILTER=<ID=q10,Description=\"Quality below 10\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=GQ,Number=1,Type=Integer,Description=\"Conditional genotype quality\">
+##contig=<ID=sq0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+";
+
+        Ok(header.parse()?)
+    }
+
+    fn eval(
+        header: &vcf::Header,
+        record: &vcf::Record,
+        s: &str,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let expression = Expression::parse(s, header)?;
+        Ok(expression.evaluate(record)?)
+    }
+
+    #[test]
+    fn test_evaluate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let record = vcf::Record::try_from_str(
+            "sq0\t8\trs13\tA\tG,AT\t25.5\tPASS\tDP=30;AC=3,1;DB\tGT:GQ\t0/1:40\t1/2:10",
+            &header,
+        )?;
+
+        assert!(eval(&header, &record, "CHROM == 'sq0' && POS == 8")?);
+        assert!(eval(&header, &record, "ID == 'rs13'")?);
+        assert!(eval(&header, &record, "REF = 'A' && ALT = 'AT'")?);
+        assert!(eval(&header, &record, "QUAL > 20 && QUAL < 30")?);
+        assert!(eval(&header, &record, "FILTER == 'PASS'")?);
+        assert!(eval(&header, &record, "FILTER in ('q10', 'PASS')")?);
+        assert!(eval(&header, &record, "TYPE == 'snp' && TYPE == 'indel'")?);
+        assert!(!eval(&header, &record, "TYPE == 'sv'")?);
+
+        assert!(eval(&header, &record, "INFO/DP > 10 && DP / 2 == 15")?);
+        assert!(eval(&header, &record, "INFO/AC > 2")?);
+        assert!(!eval(&header, &record, "INFO/AC[1] > 2")?);
+        assert!(eval(&header, &record, "INFO/AC[0] + AC[1] == 4")?);
+        assert!(eval(&header, &record, "INFO/DB && !!DB")?);
+        assert!(eval(&header, &record, "-DP < 0 && 1 + 2 * 3 == 7")?);
+
+        assert!(eval(&header, &record, "FMT/GQ > 30")?);
+        assert!(eval(&header, &record, "any(FMT/GQ > 30)")?);
+        assert!(!eval(&header, &record, "all(FMT/GQ > 30)")?);
+        assert!(eval(&header, &record, "all(FORMAT/GQ >= 10)")?);
+        assert!(eval(&header, &record, "FMT/GT == '1/2' && FMT/GQ < 20")?);
+        assert!(!eval(
+            &header,
+            &record,
+            "any(FMT/GT == '1/2' && FMT/GQ > 20)"
+        )?);
+        assert!(eval(&header, &record, "!(FMT/GQ > 30)")?);
+        assert!(!eval(&header, &record, "!any(FMT/GQ > 30)")?);
+        assert!(!eval(&header, &record, "FMT/GQ > 30 && FMT/GT == '1/2'")?);
+        assert!(eval(&header, &record, "QUAL > 20 && all(FMT/GQ >= 10)")?);
+
+        assert!(eval(&header, &record, "QUAL > 'a'").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluate_with_bases() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let record = vcf::Record::try_from_str("sq0	8	.	AC	A,<DEL>,<*>	.	PASS	.", &header)?;
+
+        assert!(eval(
+            &header,
+            &record,
+            "REF == 'AC' && 'AC' == REF && REF != 'A'"
+        )?);
+        assert!(!eval(
+            &header,
+            &record,
+            "REF == 'A' || REF[1] == 'AC' || REF[1] != 'AC'"
+        )?);
+        assert!(eval(&header, &record, "REF in ('A', 'AC') && REF > 'A'")?);
+
+        assert!(eval(
+            &header,
+            &record,
+            "ALT == 'A' && ALT != 'A' && ALT[0] == 'A'"
+        )?);
+        assert!(eval(
+            &header,
+            &record,
+            "ALT == '<DEL>' && ALT[1] == '<DEL>'"
+        )?);
+        assert!(!eval(&header, &record, "ALT[0] != 'A' || ALT[3] != 'A'")?);
+        assert!(eval(&header, &record, "ALT in ('C', '<*>')")?);
+
+        assert!(eval(&header, &record, "TYPE == 'indel' && TYPE == 'sv'")?);
+
+        let record = vcf::Record::try_from_str("sq0	8	.	A	<*>	.	PASS	.", &header)?;
+        assert!(eval(&header, &record, "TYPE == 'ref'")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluate_with_missing_values() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let record =
+            vcf::Record::try_from_str("sq0\t8\t.\tA\t.\t.\tq10\t.\tGT:GQ\t0/0:.\t./.:.", &header)?;
+
+        assert!(eval(&header, &record, "TYPE == 'ref'")?);
+        assert!(!eval(&header, &record, "QUAL < 10 || QUAL >= 10")?);
+        assert!(eval(&header, &record, "!QUAL")?);
+        assert!(!eval(&header, &record, "FILTER == 'PASS'")?);
+        assert!(eval(&header, &record, "FILTER == 'q10'")?);
+        assert!(!eval(&header, &record, "INFO/DP != 0 || DB")?);
+        assert!(!eval(&header, &record, "FMT/GQ > 0 || FMT/GQ <= 0")?);
+        assert!(!eval(&header, &record, "ID")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluate_with_samples_passing_one_side() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let record = vcf::Record::try_from_str(
+            "sq0\t8\t.\tA\tG\t.\tPASS\tDP=30\tGT:GQ\t0/1:40\t1/1:5",
+            &header,
+        )?;
+
+        assert!(!eval(&header, &record, "FMT/GQ > 20 && FMT/GT == '1/1'")?);
+        assert!(eval(&header, &record, "FMT/GQ > 20 || FMT/GT == '1/1'")?);
+        assert!(eval(&header, &record, "FMT/GQ > 20 && FMT/GT == '0/1'")?);
+        assert!(eval(
+            &header,
+            &record,
+            "any(FMT/GQ > 20) && any(FMT/GT == '1/1')"
+        )?);
+        assert!(!eval(&header, &record, "INFO/DP > 10 && FMT/GQ < 5")?);
+
+        let record = vcf::Record::try_from_str("sq0\t8\t.\tA\tG\t.\tPASS\tDP=30", &header)?;
+        assert!(eval(&header, &record, "INFO/DP > 10 || FMT/GQ > 20")?);
+        assert!(!eval(&header, &record, "INFO/DP > 10 && FMT/GQ > 20")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        assert!(Expression::parse("INFO/DP > 1 && FMT/GQ > 1 && DB", &header).is_ok());
+
+        assert_eq!(
+            Expression::parse("INFO/GQ > 1", &header),
+            Err(ParseError::InvalidField(String::from("INFO/GQ")))
+        );
+        assert_eq!(
+            Expression::parse("FMT/DP > 1", &header),
+            Err(ParseError::InvalidField(String::from("FMT/DP")))
+        );
+        assert_eq!(
+            Expression::parse("qual > 1", &header),
+            Err(ParseError::InvalidField(String::from("qual")))
+        );
+        assert_eq!(
+            Expression::parse("none(FMT/GQ > 1)", &header),
+            Err(ParseError::InvalidFunction(String::from("none")))
+        );
+
+        Ok(())
+    }
+}
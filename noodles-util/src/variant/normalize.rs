@@ -0,0 +1,441 @@
+//! Variant normalization.
+//!
+//! A variant is normalized when it is left-aligned and parsimonious, i.e., its alleles are shifted
+//! as far left as possible through repeats and share no unnecessary leading or trailing bases.
+//! Equivalent variants then have the same representation, which is required to compare call
+//! sets.
+//!
+//! Records that have a symbolic, breakend, or overlapping deletion (`*`) alternate allele are
+//! left as-is.
+
+use std::io;
+
+use noodles_bcf as bcf;
+use noodles_fasta as fasta;
+use noodles_vcf::{
+    self as vcf,
+    header::info::key,
+    record::{
+        alternate_bases::Allele, info::field::Value, reference_bases::Base, AlternateBases,
+        Chromosome, Position, ReferenceBases,
+    },
+};
+
+/// Normalizes a VCF record against a reference sequence repository.
+///
+/// Common trailing bases of all alleles are trimmed, and the alleles are extended to the left
+/// with reference bases, shifting indels through repeats, until the alleles no longer share a
+/// trailing base. Common leading bases are then trimmed while every allele has more than one base.
+/// The position and, if present, the `INFO` end position (`END`) are updated.
+///
+/// This returns whether the record changed.
+///
+/// An error is returned if the reference sequence is missing or if the reference bases of the
+/// record do not match the reference sequence.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fasta as fasta;
+/// use noodles_util::variant::normalize;
+/// use noodles_vcf::{self as vcf, record::Position};
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     fasta::record::Definition::new("sq0", None),
+///     fasta::record::Sequence::from(b"GGCACACAGG".to_vec()),
+/// )]);
+///
+/// let mut record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse()?)
+///     .set_position(Position::from(7))
+///     .set_reference_bases("CAG".parse()?)
+///     .set_alternate_bases("G".parse()?)
+///     .build()?;
+///
+/// assert!(normalize::vcf_record(&repository, &mut record)?);
+///
+/// assert_eq!(record.position(), Position::from(2));
+/// assert_eq!(record.reference_bases().to_string(), "GCA");
+/// assert_eq!(record.alternate_bases().to_string(), "G");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn vcf_record(repository: &fasta::Repository, record: &mut vcf::Record) -> io::Result<bool> {
+    let mut alleles = Vec::with_capacity(record.alternate_bases().len() + 1);
+    alleles.push(bases_to_vec(record.reference_bases()));
+
+    for allele in record.alternate_bases().iter() {
+        match allele {
+            Allele::Bases(bases) => alleles.push(bases_to_vec(bases)),
+            _ => return Ok(false),
+        }
+    }
+
+    let position = usize::from(record.position());
+
+    if alleles.len() < 2 || position == 0 {
+        return Ok(false);
+    }
+
+    let name = match record.chromosome() {
+        Chromosome::Name(name) => name,
+        Chromosome::Symbol(symbol) => symbol,
+    };
+
+    let sequence = repository.get(name).transpose()?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("missing reference sequence: {name}"),
+        )
+    })?;
+
+    let reference_sequence = sequence.as_ref();
+
+    let original_start = position - 1;
+    let reference_bases = &alleles[0];
+
+    let is_reference_match = reference_sequence
+        .get(original_start..original_start + reference_bases.len())
+        .map(|expected| expected.eq_ignore_ascii_case(reference_bases))
+        .unwrap_or(false);
+
+    if !is_reference_match {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "reference bases mismatch at {}:{}: got {}",
+                record.chromosome(),
+                position,
+                record.reference_bases()
+            ),
+        ));
+    }
+
+    let original_alleles = alleles.clone();
+    let mut start = original_start;
+
+    normalize(reference_sequence, &mut start, &mut alleles);
+
+    if start == original_start && alleles == original_alleles {
+        return Ok(false);
+    }
+
+    let mut alleles = alleles.into_iter().map(vec_to_bases);
+
+    let reference_bases = alleles.next().expect("missing reference bases");
+    let end = start + reference_bases.len();
+
+    *record.position_mut() = Position::from(start + 1);
+
+    *record.reference_bases_mut() = ReferenceBases::try_from(reference_bases)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    *record.alternate_bases_mut() =
+        AlternateBases::from(alleles.map(Allele::Bases).collect::<Vec<_>>());
+
+    if let Some(value) = record.info_mut().get_mut(&key::END_POSITION) {
+        let n = i32::try_from(end).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        *value = Some(Value::Integer(n));
+    }
+
+    Ok(true)
+}
+
+/// Converts a BCF record to a VCF record normalized against a reference sequence repository.
+///
+/// The record is converted to a VCF record using the given header and string maps and then
+/// normalized (see [`vcf_record`]). The normalized VCF record and whether normalization changed
+/// it are returned. The VCF record can be written using [`bcf::Writer::write_vcf_record`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles_bcf as bcf;
+/// use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+/// use noodles_util::variant::normalize;
+/// use noodles_vcf as vcf;
+///
+/// let repository = fasta::indexed_reader::Builder::default()
+///     .build_from_path("reference.fa")
+///     .map(IndexedReader::new)
+///     .map(fasta::Repository::new)?;
+///
+/// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+/// reader.read_file_format()?;
+///
+/// let raw_header = reader.read_header()?;
+/// let header: vcf::Header = raw_header.parse()?;
+/// let string_maps: bcf::header::StringMaps = raw_header.parse()?;
+///
+/// for result in reader.records() {
+///     let record = result?;
+///     let (record, _) =
+///         normalize::bcf_record_to_vcf_record(&repository, &header, &string_maps, &record)?;
+///     println!("{record}");
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn bcf_record_to_vcf_record(
+    repository: &fasta::Repository,
+    header: &vcf::Header,
+    string_maps: &bcf::header::StringMaps,
+    record: &bcf::Record,
+) -> io::Result<(vcf::Record, bool)> {
+    let mut record = record.try_into_vcf_record(header, string_maps)?;
+    let is_changed = vcf_record(repository, &mut record)?;
+    Ok((record, is_changed))
+}
+
+// Tan A, Abecasis GR, Kang HM. Unified representation of genetic variants. Bioinformatics.
+// 2015;31(13):2202-2204.
+fn normalize(reference_sequence: &[u8], start: &mut usize, alleles: &mut [Vec<u8>]) {
+    while can_trim_end(alleles, *start) {
+        for allele in alleles.iter_mut() {
+            allele.pop();
+        }
+
+        if alleles.iter().any(|allele| allele.is_empty()) {
+            *start -= 1;
+
+            let base = normalize_base(reference_sequence[*start]);
+
+            for allele in alleles.iter_mut() {
+                allele.insert(0, base);
+            }
+        }
+    }
+
+    while can_trim_start(alleles) {
+        for allele in alleles.iter_mut() {
+            allele.remove(0);
+        }
+
+        *start += 1;
+    }
+}
+
+fn can_trim_end(alleles: &[Vec<u8>], start: usize) -> bool {
+    let last_bases: Option<Vec<_>> = alleles.iter().map(|allele| allele.last()).collect();
+
+    match last_bases {
+        Some(bases) => {
+            let is_common = bases.windows(2).all(|w| w[0] == w[1]);
+            let can_extend = start > 0 || alleles.iter().all(|allele| allele.len() > 1);
+            is_common && can_extend
+        }
+        None => false,
+    }
+}
+
+fn can_trim_start(alleles: &[Vec<u8>]) -> bool {
+    alleles.iter().all(|allele| allele.len() > 1) && alleles.windows(2).all(|w| w[0][0] == w[1][0])
+}
+
+fn normalize_base(b: u8) -> u8 {
+    match b.to_ascii_uppercase() {
+        b @ (b'A' | b'C' | b'G' | b'T') => b,
+        _ => b'N',
+    }
+}
+
+fn bases_to_vec(bases: &[Base]) -> Vec<u8> {
+    bases.iter().map(|&base| char::from(base) as u8).collect()
+}
+
+fn vec_to_bases(bases: Vec<u8>) -> Vec<Base> {
+    bases
+        .into_iter()
+        .map(|b| match b {
+            b'A' => Base::A,
+            b'C' => Base::C,
+            b'G' => Base::G,
+            b'T' => Base::T,
+            _ => Base::N,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_repository() -> fasta::Repository {
+        fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"GGCACACAGG".to_vec()),
+        )])
+    }
+
+    fn build_record(position: usize, reference_bases: &str, alternate_bases: &str) -> vcf::Record {
+        vcf::Record::builder()
+            .set_chromosome("sq0".parse().unwrap())
+            .set_position(Position::from(position))
+            .set_reference_bases(reference_bases.parse().unwrap())
+            .set_alternate_bases(alternate_bases.parse().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn normalize_vcf_record(
+        position: usize,
+        reference_bases: &str,
+        alternate_bases: &str,
+    ) -> io::Result<(bool, usize, String, String)> {
+        let repository = build_repository();
+        let mut record = build_record(position, reference_bases, alternate_bases);
+        let is_changed = vcf_record(&repository, &mut record)?;
+
+        Ok((
+            is_changed,
+            usize::from(record.position()),
+            record.reference_bases().to_string(),
+            record.alternate_bases().to_string(),
+        ))
+    }
+
+    #[test]
+    fn test_vcf_record() -> io::Result<()> {
+        // deletion in a repeat
+        assert_eq!(
+            normalize_vcf_record(7, "CAG", "G")?,
+            (true, 2, String::from("GCA"), String::from("G"))
+        );
+
+        // insertion in a repeat
+        assert_eq!(
+            normalize_vcf_record(8, "A", "ACA")?,
+            (true, 2, String::from("G"), String::from("GCA"))
+        );
+
+        // SNV with a common trailing base
+        assert_eq!(
+            normalize_vcf_record(3, "CA", "TA")?,
+            (true, 3, String::from("C"), String::from("T"))
+        );
+
+        // SNV with common leading bases
+        assert_eq!(
+            normalize_vcf_record(1, "GGC", "GGT")?,
+            (true, 3, String::from("C"), String::from("T"))
+        );
+
+        // multiallelic
+        assert_eq!(
+            normalize_vcf_record(7, "CAG", "G,CTG")?,
+            (true, 6, String::from("ACA"), String::from("A,ACT"))
+        );
+
+        // already normalized
+        assert_eq!(
+            normalize_vcf_record(2, "GCA", "G")?,
+            (false, 2, String::from("GCA"), String::from("G"))
+        );
+
+        // symbolic allele
+        assert_eq!(
+            normalize_vcf_record(3, "CA", "<DEL>")?,
+            (false, 3, String::from("CA"), String::from("<DEL>"))
+        );
+
+        // deletion at the start of the reference sequence
+        assert_eq!(
+            normalize_vcf_record(1, "GG", "G")?,
+            (false, 1, String::from("GG"), String::from("G"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vcf_record_with_end_position() -> io::Result<()> {
+        let repository = build_repository();
+
+        let mut record = build_record(7, "CAG", "G");
+        record
+            .info_mut()
+            .insert(key::END_POSITION, Some(Value::Integer(9)));
+
+        vcf_record(&repository, &mut record)?;
+
+        assert_eq!(
+            record.info().get(&key::END_POSITION),
+            Some(Some(&Value::Integer(4)))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vcf_record_with_reference_bases_mismatch() {
+        let repository = build_repository();
+
+        let mut record = build_record(1, "T", "G");
+        assert!(matches!(
+            vcf_record(&repository, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let mut record = build_record(10, "GT", "G");
+        assert!(matches!(
+            vcf_record(&repository, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let mut record = build_record(1, "G", "T");
+        *record.chromosome_mut() = "sq1".parse().unwrap();
+        assert!(matches!(
+            vcf_record(&repository, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_bcf_record_to_vcf_record() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_vcf::header::record::value::{map::Contig, Map};
+
+        fn read_bcf_record(
+            header: &vcf::Header,
+            string_maps: &bcf::header::StringMaps,
+            record: &vcf::Record,
+        ) -> io::Result<bcf::Record> {
+            let mut writer = bcf::Writer::from(Vec::new());
+            writer.write_file_format()?;
+            writer.write_header(header)?;
+            writer.write_vcf_record(header, string_maps, record)?;
+
+            let src = writer.into_inner();
+            let mut reader = bcf::Reader::from(&src[..]);
+            reader.read_file_format()?;
+            reader.read_header()?;
+
+            let mut record = bcf::Record::default();
+            reader.read_record(&mut record)?;
+
+            Ok(record)
+        }
+
+        let header = vcf::Header::builder()
+            .add_contig("sq0".parse()?, Map::<Contig>::new())
+            .build();
+        let string_maps = bcf::header::StringMaps::from(&header);
+        let repository = build_repository();
+
+        let record = read_bcf_record(&header, &string_maps, &build_record(7, "CAG", "G"))?;
+        let (actual, is_changed) =
+            bcf_record_to_vcf_record(&repository, &header, &string_maps, &record)?;
+
+        let mut expected = build_record(2, "GCA", "G");
+        *expected.quality_score_mut() = actual.quality_score();
+        *expected.filters_mut() = actual.filters().cloned();
+        assert_eq!(actual, expected);
+        assert!(is_changed);
+
+        let record = read_bcf_record(&header, &string_maps, &expected)?;
+        let (actual, is_changed) =
+            bcf_record_to_vcf_record(&repository, &header, &string_maps, &record)?;
+        assert_eq!(actual, expected);
+        assert!(!is_changed);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,739 @@
+//! Allele reconciliation and remapping of allele-dependent fields.
+
+use std::io;
+
+use crate::{
+    header::{
+        format, info,
+        record::value::{map, Map},
+        Number,
+    },
+    record::{
+        alternate_bases::{allele::Symbol, Allele},
+        genotypes::{
+            genotype::field::{
+                self,
+                value::genotype::{allele::Phasing, Allele as GenotypeAllele},
+            },
+            Genotype,
+        },
+        info::field::Value as InfoValue,
+        reference_bases::Base,
+        Filters, Ids, Info, QualityScore, ReferenceBases,
+    },
+    Header, Record,
+};
+
+pub(crate) const DEFAULT_PLOIDY: usize = 2;
+
+/// A mapping of the alleles of a source record to the alleles of a target record.
+///
+/// Allele 0 is the reference allele, and allele `i` (`i > 0`) is alternate allele `i - 1`.
+pub(crate) struct AlleleMap {
+    // The target allele of each source allele.
+    targets: Vec<usize>,
+    // The source allele of each target allele, if any.
+    sources: Vec<Option<usize>>,
+}
+
+impl AlleleMap {
+    pub fn new(targets: Vec<usize>, sources: Vec<Option<usize>>) -> Self {
+        Self { targets, sources }
+    }
+
+    /// Creates an allele map where each target allele has at most one source allele.
+    pub fn from_targets(targets: Vec<usize>, target_allele_count: usize) -> Self {
+        let mut sources = vec![None; target_allele_count];
+
+        for (i, &j) in targets.iter().enumerate() {
+            sources[j] = Some(i);
+        }
+
+        Self::new(targets, sources)
+    }
+
+    fn is_identity(&self) -> bool {
+        self.targets.len() == self.sources.len()
+            && self.targets.iter().enumerate().all(|(i, &j)| i == j)
+    }
+
+    pub fn remap_info_value(
+        &self,
+        header: &Header,
+        key: &info::Key,
+        value: &InfoValue,
+        ploidy: usize,
+    ) -> io::Result<InfoValue> {
+        let number = info_number(header, key);
+
+        match self.indices(number, ploidy, info_value_len(value))? {
+            Some(indices) => Ok(remap_info_value(value, &indices)),
+            None => Ok(value.clone()),
+        }
+    }
+
+    /// Remaps a sample value, where `ploidy` is the ploidy of the sample.
+    pub fn remap_format_value(
+        &self,
+        header: &Header,
+        key: &format::Key,
+        value: &field::Value,
+        ploidy: usize,
+    ) -> io::Result<field::Value> {
+        if key == &format::key::GENOTYPE {
+            return self.remap_genotype_value(value);
+        }
+
+        let number = format_number(header, key);
+
+        match self.indices(number, ploidy, format_value_len(value))? {
+            Some(indices) => Ok(remap_format_value(value, &indices)),
+            None => Ok(value.clone()),
+        }
+    }
+
+    fn remap_genotype_value(&self, value: &field::Value) -> io::Result<field::Value> {
+        let mut genotype = self.remap_genotype(value)?;
+        sort_unphased_alleles(&mut genotype);
+        Ok(field::Value::String(genotype.to_string()))
+    }
+
+    /// Remaps the allele positions of a genotype, keeping the order of the alleles.
+    pub fn remap_genotype(&self, value: &field::Value) -> io::Result<field::value::Genotype> {
+        let mut genotype = parse_genotype_value(value)?;
+
+        for allele in genotype.iter_mut() {
+            if let Some(position) = allele.position() {
+                let new_position = self.targets.get(position).copied().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid genotype allele position: {position}"),
+                    )
+                })?;
+
+                *allele.position_mut() = Some(new_position);
+            }
+        }
+
+        Ok(genotype)
+    }
+
+    // Builds the list of source indices for each index of an allele-dependent value in the target
+    // allele order. `None` is returned when the field does not depend on alleles or when the order
+    // is unchanged.
+    //
+    // `Number=G` values have one value per genotype of the given ploidy.
+    fn indices(
+        &self,
+        number: Number,
+        ploidy: usize,
+        value_len: usize,
+    ) -> io::Result<Option<Vec<Option<usize>>>> {
+        if self.is_identity() {
+            return Ok(None);
+        }
+
+        let source_allele_count = self.targets.len();
+        let target_allele_count = self.sources.len();
+
+        match number {
+            Number::A => Ok(Some(
+                self.sources[1..]
+                    .iter()
+                    .map(|i| i.and_then(|j| j.checked_sub(1)))
+                    .collect(),
+            )),
+            Number::R => Ok(Some(self.sources.clone())),
+            Number::G => {
+                let expected_len = genotype_count(source_allele_count, ploidy);
+
+                if value_len != expected_len {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "invalid Number=G value count: expected {expected_len}, got {value_len}"
+                        ),
+                    ));
+                }
+
+                let indices = genotypes(target_allele_count, ploidy)
+                    .into_iter()
+                    .map(|genotype| {
+                        genotype
+                            .into_iter()
+                            .map(|allele| self.sources[allele])
+                            .collect::<Option<Vec<_>>>()
+                            .map(|mut source_genotype| {
+                                source_genotype.sort_unstable();
+                                genotype_index(&source_genotype)
+                            })
+                    })
+                    .collect();
+
+                Ok(Some(indices))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Remaps and combines the `INFO` fields of records to a single list of fields.
+///
+/// Each record is paired with the allele map to the combined allele order. `Number=A`, `R`, and
+/// `G` values are combined, taking the first nonmissing value for each allele. Other fields take
+/// the first nonmissing value. `Number=G` values are read as genotypes of the given ploidy.
+pub(crate) fn combine_info<'r, I>(header: &Header, records: I, ploidy: usize) -> io::Result<Info>
+where
+    I: IntoIterator<Item = (&'r Record, &'r AlleleMap)>,
+{
+    let mut info = Info::default();
+
+    for (record, allele_map) in records {
+        for (key, value) in record.info().as_ref() {
+            let value = value
+                .as_ref()
+                .map(|v| allele_map.remap_info_value(header, key, v, ploidy))
+                .transpose()?;
+
+            match info.get_mut(key) {
+                Some(Some(combined_value)) => {
+                    if is_allele_dependent(info_number(header, key)) {
+                        if let Some(value) = value {
+                            combine_info_values(combined_value, value);
+                        }
+                    }
+                }
+                Some(combined_value) => *combined_value = value,
+                None => {
+                    info.insert(key.clone(), value);
+                }
+            }
+        }
+    }
+
+    Ok(info)
+}
+
+pub(crate) fn is_allele_dependent(number: Number) -> bool {
+    matches!(number, Number::A | Number::R | Number::G)
+}
+
+pub(crate) fn fill<T>(dst: &mut [Option<T>], src: Vec<Option<T>>) {
+    for (a, b) in dst.iter_mut().zip(src) {
+        if a.is_none() {
+            *a = b;
+        }
+    }
+}
+
+fn combine_info_values(dst: &mut InfoValue, src: InfoValue) {
+    match (dst, src) {
+        (InfoValue::IntegerArray(a), InfoValue::IntegerArray(b)) => fill(a, b),
+        (InfoValue::FloatArray(a), InfoValue::FloatArray(b)) => fill(a, b),
+        (InfoValue::CharacterArray(a), InfoValue::CharacterArray(b)) => fill(a, b),
+        (InfoValue::StringArray(a), InfoValue::StringArray(b)) => fill(a, b),
+        _ => {}
+    }
+}
+
+pub(crate) fn info_number(header: &Header, key: &info::Key) -> Number {
+    header
+        .infos()
+        .get(key)
+        .map(|info| info.number())
+        .unwrap_or_else(|| Map::<map::Info>::from(key).number())
+}
+
+pub(crate) fn format_number(header: &Header, key: &format::Key) -> Number {
+    header
+        .formats()
+        .get(key)
+        .map(|format| format.number())
+        .unwrap_or_else(|| Map::<map::Format>::from(key).number())
+}
+
+/// Reconciles the reference bases and unifies the alternate alleles of records at the same site.
+///
+/// The reference bases are the longest of all records; shorter reference bases must be a prefix.
+/// The alternate bases of records with shorter reference bases are extended with the remaining
+/// suffix. Alternate alleles are listed in order of first appearance, except the unspecified
+/// allele (`<*>`), which is kept last, as in gVCFs.
+pub(crate) fn unify_alleles<'r, I>(
+    records: I,
+) -> io::Result<(ReferenceBases, Vec<Allele>, Vec<AlleleMap>)>
+where
+    I: IntoIterator<Item = &'r Record>,
+    I::IntoIter: Clone,
+{
+    let records = records.into_iter();
+
+    let reference_bases = records
+        .clone()
+        .map(|record| record.reference_bases())
+        .max_by_key(|reference_bases| reference_bases.len())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records"))?
+        .clone();
+
+    for record in records.clone() {
+        if !reference_bases.starts_with(record.reference_bases()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "incompatible reference bases at {}:{}: {} and {}",
+                    record.chromosome(),
+                    record.position(),
+                    reference_bases,
+                    record.reference_bases(),
+                ),
+            ));
+        }
+    }
+
+    let mut alternate_bases = Vec::new();
+    let mut targets = Vec::new();
+
+    for record in records {
+        let suffix = &reference_bases[record.reference_bases().len()..];
+        let mut record_targets = vec![0];
+
+        for allele in record.alternate_bases().iter() {
+            let allele = extend_allele(allele, suffix);
+
+            let i = match alternate_bases.iter().position(|a| a == &allele) {
+                Some(i) => i,
+                None => {
+                    alternate_bases.push(allele);
+                    alternate_bases.len() - 1
+                }
+            };
+
+            record_targets.push(i + 1);
+        }
+
+        targets.push(record_targets);
+    }
+
+    move_unspecified_allele_last(&mut alternate_bases, &mut targets);
+
+    let allele_count = alternate_bases.len() + 1;
+
+    let allele_maps = targets
+        .into_iter()
+        .map(|record_targets| AlleleMap::from_targets(record_targets, allele_count))
+        .collect();
+
+    Ok((reference_bases, alternate_bases, allele_maps))
+}
+
+fn move_unspecified_allele_last(alternate_bases: &mut Vec<Allele>, targets: &mut [Vec<usize>]) {
+    let i = match alternate_bases
+        .iter()
+        .position(|allele| matches!(allele, Allele::Symbol(Symbol::Unspecified)))
+    {
+        Some(i) if i + 1 < alternate_bases.len() => i,
+        _ => return,
+    };
+
+    let allele = alternate_bases.remove(i);
+    alternate_bases.push(allele);
+
+    // Allele positions are offset by the reference allele.
+    let (old_position, new_position) = (i + 1, alternate_bases.len());
+
+    for position in targets.iter_mut().flatten() {
+        if *position == old_position {
+            *position = new_position;
+        } else if *position > old_position {
+            *position -= 1;
+        }
+    }
+}
+
+fn extend_allele(allele: &Allele, suffix: &[Base]) -> Allele {
+    match allele {
+        Allele::Bases(bases) if !suffix.is_empty() => {
+            let mut bases = bases.clone();
+            bases.extend_from_slice(suffix);
+            Allele::Bases(bases)
+        }
+        _ => allele.clone(),
+    }
+}
+
+/// Returns the union of the IDs of the given records.
+pub(crate) fn merge_ids<'r, I>(records: I) -> Ids
+where
+    I: IntoIterator<Item = &'r Record>,
+{
+    let mut ids = Ids::default();
+
+    for record in records {
+        ids.extend(record.ids().iter().cloned());
+    }
+
+    ids
+}
+
+/// Returns the max quality score of the given records.
+pub(crate) fn max_quality_score<'r, I>(records: I) -> Option<QualityScore>
+where
+    I: IntoIterator<Item = &'r Record>,
+{
+    records
+        .into_iter()
+        .filter_map(|record| record.quality_score())
+        .max_by(|a, b| f32::from(*a).total_cmp(&f32::from(*b)))
+}
+
+/// Returns the union of the filters of the given records.
+///
+/// `PASS` is only kept if no record failed a filter.
+pub(crate) fn merge_filters<'r, I>(records: I) -> Option<Filters>
+where
+    I: IntoIterator<Item = &'r Record>,
+{
+    let mut merged = None;
+
+    for record in records {
+        match (record.filters(), &mut merged) {
+            (None, _) => {}
+            (Some(Filters::Fail(ids)), Some(Filters::Fail(merged_ids))) => {
+                merged_ids.extend(ids.iter().cloned());
+            }
+            (Some(Filters::Fail(ids)), _) => merged = Some(Filters::Fail(ids.clone())),
+            (Some(Filters::Pass), None) => merged = Some(Filters::Pass),
+            (Some(Filters::Pass), Some(_)) => {}
+        }
+    }
+
+    merged
+}
+
+/// Returns the ploidy of the first called genotype of the given records.
+pub(crate) fn ploidy<'r, I>(records: I) -> usize
+where
+    I: IntoIterator<Item = &'r Record>,
+{
+    records
+        .into_iter()
+        .flat_map(|record| record.genotypes().iter())
+        .find_map(|genotype| genotype.genotype().and_then(|r| r.ok()))
+        .map(|genotype| genotype.len())
+        .unwrap_or(DEFAULT_PLOIDY)
+}
+
+/// Returns the ploidy of a sample from its `GT` field, or `LGT` field if `GT` is not set.
+pub(crate) fn sample_ploidy(genotype: &Genotype) -> Option<usize> {
+    [format::key::GENOTYPE, format::key::LOCAL_GENOTYPE]
+        .iter()
+        .find_map(|key| match genotype.get(key) {
+            Some(Some(value)) => parse_genotype_value(value).ok().map(|g| g.len()),
+            _ => None,
+        })
+}
+
+pub(crate) fn missing_genotype_value(ploidy: usize) -> field::Value {
+    let alleles = (0..ploidy.max(1))
+        .map(|_| GenotypeAllele::new(None, Phasing::Unphased))
+        .collect::<Vec<_>>();
+
+    let genotype = field::value::Genotype::try_from(alleles).expect("alleles are never empty");
+
+    field::Value::String(genotype.to_string())
+}
+
+pub(crate) fn parse_genotype_value(value: &field::Value) -> io::Result<field::value::Genotype> {
+    match value {
+        field::Value::String(s) => s
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid genotype value type",
+        )),
+    }
+}
+
+/// Sorts the allele positions of an unphased genotype.
+///
+/// Unphased genotypes are unordered and are written in canonical (ascending) order. Phased
+/// genotypes are left unchanged.
+pub(crate) fn sort_unphased_alleles(genotype: &mut field::value::Genotype) {
+    let is_unphased = genotype
+        .iter()
+        .skip(1)
+        .all(|allele| allele.phasing() == Phasing::Unphased);
+
+    if genotype.len() > 1 && is_unphased {
+        let mut positions: Vec<_> = genotype.iter().map(|allele| allele.position()).collect();
+        positions.sort_unstable();
+
+        for (allele, position) in genotype.iter_mut().zip(positions) {
+            *allele.position_mut() = position;
+        }
+    }
+}
+
+// Returns the number of unordered genotypes of the given ploidy, i.e., the number of multisets of
+// size `ploidy` from `allele_count` alleles.
+fn genotype_count(allele_count: usize, ploidy: usize) -> usize {
+    match allele_count {
+        0 => 0,
+        n => binomial(n + ploidy - 1, ploidy),
+    }
+}
+
+// Returns all unordered genotypes of the given ploidy in index order. The alleles of each
+// genotype are sorted.
+fn genotypes(allele_count: usize, ploidy: usize) -> Vec<Vec<usize>> {
+    if ploidy == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut genotypes = Vec::with_capacity(genotype_count(allele_count, ploidy));
+
+    for last_allele in 0..allele_count {
+        for mut genotype in self::genotypes(last_allele + 1, ploidy - 1) {
+            genotype.push(last_allele);
+            genotypes.push(genotype);
+        }
+    }
+
+    genotypes
+}
+
+// § 1.6.2 Genotype fields (2023-08-23): "...the ordering of genotypes for the likelihoods is
+// given by: F(j/k) = (k*(k+1)/2)+j. In the triploid case, if A is the allele, the ordering is
+// given by: F(j/k/l) = (l*(l+1)*(l+2)/6)+(k*(k+1)/2)+j." The general form for sorted alleles
+// a_1 <= ... <= a_P is the sum of C(a_m + m - 1, m).
+//
+// `alleles` must be sorted.
+fn genotype_index(alleles: &[usize]) -> usize {
+    alleles
+        .iter()
+        .enumerate()
+        .map(|(m, &a)| binomial(a + m, m + 1))
+        .sum()
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    let k = k.min(n - k);
+
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+fn select<T: Clone>(values: &[Option<T>], indices: &[Option<usize>]) -> Vec<Option<T>> {
+    indices
+        .iter()
+        .map(|i| i.and_then(|j| values.get(j).cloned().flatten()))
+        .collect()
+}
+
+fn info_value_len(value: &InfoValue) -> usize {
+    match value {
+        InfoValue::IntegerArray(values) => values.len(),
+        InfoValue::FloatArray(values) => values.len(),
+        InfoValue::CharacterArray(values) => values.len(),
+        InfoValue::StringArray(values) => values.len(),
+        _ => 1,
+    }
+}
+
+fn remap_info_value(value: &InfoValue, indices: &[Option<usize>]) -> InfoValue {
+    match value {
+        InfoValue::Integer(n) => InfoValue::IntegerArray(select(&[Some(*n)], indices)),
+        InfoValue::Float(n) => InfoValue::FloatArray(select(&[Some(*n)], indices)),
+        InfoValue::Flag => InfoValue::Flag,
+        InfoValue::Character(c) => InfoValue::CharacterArray(select(&[Some(*c)], indices)),
+        InfoValue::String(s) => InfoValue::StringArray(select(&[Some(s.clone())], indices)),
+        InfoValue::IntegerArray(values) => InfoValue::IntegerArray(select(values, indices)),
+        InfoValue::FloatArray(values) => InfoValue::FloatArray(select(values, indices)),
+        InfoValue::CharacterArray(values) => InfoValue::CharacterArray(select(values, indices)),
+        InfoValue::StringArray(values) => InfoValue::StringArray(select(values, indices)),
+    }
+}
+
+fn format_value_len(value: &field::Value) -> usize {
+    use field::Value;
+
+    match value {
+        Value::IntegerArray(values) => values.len(),
+        Value::FloatArray(values) => values.len(),
+        Value::CharacterArray(values) => values.len(),
+        Value::StringArray(values) => values.len(),
+        _ => 1,
+    }
+}
+
+fn remap_format_value(value: &field::Value, indices: &[Option<usize>]) -> field::Value {
+    use field::Value;
+
+    match value {
+        Value::Integer(n) => Value::IntegerArray(select(&[Some(*n)], indices)),
+        Value::Float(n) => Value::FloatArray(select(&[Some(*n)], indices)),
+        Value::Character(c) => Value::CharacterArray(select(&[Some(*c)], indices)),
+        Value::String(s) => Value::StringArray(select(&[Some(s.clone())], indices)),
+        Value::IntegerArray(values) => Value::IntegerArray(select(values, indices)),
+        Value::FloatArray(values) => Value::FloatArray(select(values, indices)),
+        Value::CharacterArray(values) => Value::CharacterArray(select(values, indices)),
+        Value::StringArray(values) => Value::StringArray(select(values, indices)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_genotype_index() {
+        assert_eq!(genotype_index(&[1]), 1);
+
+        assert_eq!(genotype_index(&[0, 0]), 0);
+        assert_eq!(genotype_index(&[0, 1]), 1);
+        assert_eq!(genotype_index(&[1, 1]), 2);
+        assert_eq!(genotype_index(&[0, 2]), 3);
+        assert_eq!(genotype_index(&[1, 2]), 4);
+        assert_eq!(genotype_index(&[2, 2]), 5);
+
+        assert_eq!(genotype_index(&[0, 0, 0]), 0);
+        assert_eq!(genotype_index(&[0, 0, 1]), 1);
+        assert_eq!(genotype_index(&[0, 1, 1]), 2);
+        assert_eq!(genotype_index(&[1, 1, 1]), 3);
+        assert_eq!(genotype_index(&[0, 0, 2]), 4);
+        assert_eq!(genotype_index(&[1, 2, 2]), 8);
+        assert_eq!(genotype_index(&[2, 2, 2]), 9);
+    }
+
+    #[test]
+    fn test_genotypes() {
+        assert_eq!(genotypes(2, 1), [[0], [1]]);
+        assert_eq!(
+            genotypes(3, 2),
+            [[0, 0], [0, 1], [1, 1], [0, 2], [1, 2], [2, 2]]
+        );
+
+        for (allele_count, ploidy) in [(1, 1), (1, 2), (3, 2), (3, 3), (4, 4)] {
+            let genotypes = genotypes(allele_count, ploidy);
+            assert_eq!(genotypes.len(), genotype_count(allele_count, ploidy));
+
+            for (i, genotype) in genotypes.iter().enumerate() {
+                assert_eq!(genotype_index(genotype), i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_remap_format_value_with_number_g() -> Result<(), Box<dyn std::error::Error>> {
+        use field::Value;
+
+        let header = Header::default();
+        let key = format::key::ROUNDED_GENOTYPE_LIKELIHOODS;
+
+        // A,C -> A,G,C
+        let allele_map = AlleleMap::from_targets(vec![0, 2], 3);
+
+        // haploid
+        let value = Value::IntegerArray(vec![Some(0), Some(10)]);
+        assert_eq!(
+            allele_map.remap_format_value(&header, &key, &value, 1)?,
+            Value::IntegerArray(vec![Some(0), None, Some(10)])
+        );
+
+        // diploid
+        let value = Value::IntegerArray(vec![Some(0), Some(10), Some(20)]);
+        assert_eq!(
+            allele_map.remap_format_value(&header, &key, &value, 2)?,
+            Value::IntegerArray(vec![Some(0), None, None, Some(10), None, Some(20)])
+        );
+
+        // triploid: A/A/A, A/A/C, A/C/C, C/C/C
+        let value = Value::IntegerArray(vec![Some(0), Some(10), Some(20), Some(30)]);
+        assert_eq!(
+            allele_map.remap_format_value(&header, &key, &value, 3)?,
+            Value::IntegerArray(vec![
+                Some(0),
+                None,
+                None,
+                None,
+                Some(10),
+                None,
+                None,
+                Some(20),
+                None,
+                Some(30),
+            ])
+        );
+
+        assert!(matches!(
+            allele_map.remap_format_value(&header, &key, &value, 2),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remap_format_value_with_number_g_and_ref_only_source(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use field::Value;
+
+        let header = Header::default();
+        let key = format::key::ROUNDED_GENOTYPE_LIKELIHOODS;
+
+        // A -> A,C
+        let allele_map = AlleleMap::from_targets(vec![0], 2);
+        let value = Value::IntegerArray(vec![Some(0)]);
+
+        assert_eq!(
+            allele_map.remap_format_value(&header, &key, &value, 1)?,
+            Value::IntegerArray(vec![Some(0), None])
+        );
+
+        assert_eq!(
+            allele_map.remap_format_value(&header, &key, &value, 2)?,
+            Value::IntegerArray(vec![Some(0), None, None])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sample_ploidy() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::default();
+        let record =
+            Record::try_from_str("sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/1/1\t1\t./.", &header)?;
+
+        let genotypes = record.genotypes();
+        assert_eq!(sample_ploidy(&genotypes[0]), Some(3));
+        assert_eq!(sample_ploidy(&genotypes[1]), Some(1));
+        assert_eq!(sample_ploidy(&genotypes[2]), Some(2));
+
+        assert!(sample_ploidy(&Genotype::default()).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unify_alleles() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::default();
+        let records = [
+            Record::try_from_str("sq0\t1\t.\tA\tC,<*>\t.\tPASS\t.", &header)?,
+            Record::try_from_str("sq0\t1\t.\tA\tG,<*>\t.\tPASS\t.", &header)?,
+        ];
+
+        let (_, alternate_bases, allele_maps) = unify_alleles(&records)?;
+
+        let expected: Vec<Allele> = vec!["C".parse()?, "G".parse()?, "<*>".parse()?];
+        assert_eq!(alternate_bases, expected);
+
+        assert_eq!(allele_maps[0].targets, [0, 1, 3]);
+        assert_eq!(allele_maps[1].targets, [0, 2, 3]);
+        assert_eq!(allele_maps[1].sources, [Some(0), None, Some(1), Some(2)]);
+
+        Ok(())
+    }
+}
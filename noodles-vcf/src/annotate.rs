@@ -0,0 +1,541 @@
+//! Annotation of VCF records from indexed sources.
+//!
+//! An [`Annotator`] copies fields from an indexed source into the `INFO` fields of the records of
+//! a stream. Each record is looked up in the source using its index.
+//!
+//! The source can be either
+//!
+//!   * a bgzipped, tabix-indexed VCF, where source records are matched by position, reference
+//!     bases, and alternate alleles and `INFO` fields are copied; or
+//!   * a bgzipped, tabix-indexed tab-delimited file, e.g., BED, where lines are matched by
+//!     overlap and columns are copied.
+//!
+//! Copied fields can be given new keys, and [`Annotator::update_header`] adds their header
+//! definitions.
+
+mod builder;
+mod lines;
+
+pub use self::builder::Builder;
+
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::{Position, Region};
+use noodles_tabix as tabix;
+
+use crate::{
+    alleles::{self, AlleleMap},
+    header::{
+        info,
+        record::value::{
+            map::{info::Type, Info},
+            Map,
+        },
+        Number,
+    },
+    record::{info::field, Chromosome},
+    Header, Reader, Record,
+};
+
+/// A VCF record annotator.
+///
+/// This is created by [`Builder::build_from_vcf`] or [`Builder::build_from_tabix`].
+pub struct Annotator<R> {
+    source: Source<R>,
+    fields: Vec<Field>,
+}
+
+enum Source<R> {
+    Vcf {
+        reader: Reader<bgzf::Reader<R>>,
+        header: Box<Header>,
+        index: tabix::Index,
+    },
+    Tabix {
+        reader: bgzf::Reader<R>,
+        index: tabix::Index,
+    },
+}
+
+// An annotation field. The source is an INFO key for VCF sources and a 1-based column index for
+// tabix sources.
+struct Field {
+    source: FieldSource,
+    key: info::Key,
+    info: Map<Info>,
+}
+
+enum FieldSource {
+    Info(info::Key),
+    Column(usize),
+}
+
+impl<R> Annotator<R>
+where
+    R: Read + Seek,
+{
+    /// Returns the header definitions of the annotation fields.
+    pub fn infos(&self) -> impl Iterator<Item = (&info::Key, &Map<Info>)> {
+        self.fields.iter().map(|field| (&field.key, &field.info))
+    }
+
+    /// Adds the header info definitions of the annotation fields.
+    ///
+    /// An existing definition is kept if its number and type match.
+    pub fn update_header(&self, header: &mut Header) {
+        let infos = header.infos_mut();
+
+        for field in &self.fields {
+            let is_compatible = infos
+                .get(&field.key)
+                .map(|m| m.number() == field.info.number() && m.ty() == field.info.ty())
+                .unwrap_or(false);
+
+            if !is_compatible {
+                infos.insert(field.key.clone(), field.info.clone());
+            }
+        }
+    }
+
+    /// Annotates a record.
+    ///
+    /// The source is queried with the region of the record, and the fields of the matching
+    /// source entries are set in the record's `INFO`. Fields that are missing from the matching
+    /// entries are left unchanged.
+    ///
+    /// For VCF sources, a source record matches if it has the same position and reference bases
+    /// and shares at least one alternate allele. Allele-dependent fields (`Number=A`, `R`, or
+    /// `G`) are remapped to the alleles of the record, combining values from all matching source
+    /// records. Other fields are copied from the first matching source record.
+    ///
+    /// For tabix sources, a line matches if its interval overlaps the record. Column values are
+    /// parsed using the field definition. Fields with an unknown number (`Number=.`) combine the
+    /// values of all matching lines; other fields use the first matching line. Flags are set if
+    /// any line matches.
+    ///
+    /// This returns whether any source entry matched.
+    pub fn annotate(&mut self, record: &mut Record) -> io::Result<bool> {
+        let reference_sequence_name = match record.chromosome() {
+            Chromosome::Name(name) => name.clone(),
+            Chromosome::Symbol(_) => return Ok(false),
+        };
+
+        match &mut self.source {
+            Source::Vcf {
+                reader,
+                header,
+                index,
+            } => annotate_from_vcf(
+                reader,
+                header,
+                index,
+                &self.fields,
+                &reference_sequence_name,
+                record,
+            ),
+            Source::Tabix { reader, index } => annotate_from_tabix(
+                reader,
+                index,
+                &self.fields,
+                &reference_sequence_name,
+                record,
+            ),
+        }
+    }
+}
+
+fn annotate_from_vcf<R>(
+    reader: &mut Reader<bgzf::Reader<R>>,
+    header: &Header,
+    index: &tabix::Index,
+    fields: &[Field],
+    reference_sequence_name: &str,
+    record: &mut Record,
+) -> io::Result<bool>
+where
+    R: Read + Seek,
+{
+    if !index
+        .header()
+        .reference_sequence_names()
+        .contains(reference_sequence_name)
+    {
+        return Ok(false);
+    }
+
+    let start = usize::from(record.position());
+    let position =
+        Position::try_from(start).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let region = Region::new(reference_sequence_name, position..=position);
+
+    let mut matches = Vec::new();
+
+    for result in reader.query(header, index, &region)? {
+        let source_record = result?;
+
+        if usize::from(source_record.position()) != start
+            || source_record.reference_bases() != record.reference_bases()
+        {
+            continue;
+        }
+
+        if let Some(allele_map) = build_allele_map(&source_record, record) {
+            matches.push((source_record, allele_map));
+        }
+    }
+
+    if matches.is_empty() {
+        return Ok(false);
+    }
+
+    for field in fields {
+        let source_key = match &field.source {
+            FieldSource::Info(key) => key,
+            FieldSource::Column(_) => continue,
+        };
+
+        let number = alleles::info_number(header, source_key);
+        let mut value: Option<field::Value> = None;
+
+        for (source_record, allele_map) in &matches {
+            let ploidy = alleles::ploidy([source_record]);
+
+            let source_value = match source_record.info().get(source_key) {
+                Some(Some(v)) => v,
+                Some(None) => continue,
+                None => continue,
+            };
+
+            match number {
+                Number::A | Number::R | Number::G => {
+                    let remapped =
+                        allele_map.remap_info_value(header, source_key, source_value, ploidy)?;
+
+                    match value.as_mut() {
+                        Some(v) => fill_missing(v, &remapped),
+                        None => value = Some(remapped),
+                    }
+                }
+                _ => {
+                    value = Some(source_value.clone());
+                    break;
+                }
+            }
+        }
+
+        if let Some(v) = value {
+            if !is_missing(&v) {
+                record.info_mut().insert(field.key.clone(), Some(v));
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+// Maps the alleles of a source record to the alleles of a target record with the same reference
+// bases. `None` is returned if they do not share an alternate allele.
+fn build_allele_map(source_record: &Record, record: &Record) -> Option<AlleleMap> {
+    let source_alleles = source_record.alternate_bases();
+    let target_alleles = record.alternate_bases();
+    let target_allele_count = target_alleles.len() + 1;
+
+    let mut sources = Vec::with_capacity(target_allele_count);
+    sources.push(Some(0));
+
+    for allele in target_alleles.iter() {
+        let i = source_alleles.iter().position(|a| a == allele);
+        sources.push(i.map(|i| i + 1));
+    }
+
+    if sources[1..].iter().all(|i| i.is_none()) {
+        return None;
+    }
+
+    // Source alleles without a target allele are mapped past the end of the target alleles.
+    let mut targets = Vec::with_capacity(source_alleles.len() + 1);
+    targets.push(0);
+
+    for allele in source_alleles.iter() {
+        let j = target_alleles.iter().position(|a| a == allele);
+        targets.push(j.map(|j| j + 1).unwrap_or(target_allele_count));
+    }
+
+    Some(AlleleMap::new(targets, sources))
+}
+
+fn annotate_from_tabix<R>(
+    reader: &mut bgzf::Reader<R>,
+    index: &tabix::Index,
+    fields: &[Field],
+    reference_sequence_name: &str,
+    record: &mut Record,
+) -> io::Result<bool>
+where
+    R: Read + Seek,
+{
+    let start = Position::try_from(usize::from(record.position()))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let end = record
+        .end()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        .map(usize::from)
+        .and_then(|n| {
+            Position::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })?;
+
+    let lines = lines::query(reader, index, reference_sequence_name, start, end)?;
+
+    if lines.is_empty() {
+        return Ok(false);
+    }
+
+    for field in fields {
+        let column = match field.source {
+            FieldSource::Column(column) => column,
+            FieldSource::Info(_) => continue,
+        };
+
+        if field.info.ty() == Type::Flag {
+            record
+                .info_mut()
+                .insert(field.key.clone(), Some(field::Value::Flag));
+            continue;
+        }
+
+        let mut value: Option<field::Value> = None;
+
+        for line in &lines {
+            let raw_value = match line.split('\t').nth(column - 1) {
+                Some(s) if !s.is_empty() && s != "." => s,
+                _ => continue,
+            };
+
+            let v = field::Value::from_str_info(raw_value, &field.info)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            match value.as_mut() {
+                Some(value) if field.info.number() == Number::Unknown => append(value, v),
+                Some(_) => break,
+                None => value = Some(v),
+            }
+        }
+
+        if let Some(v) = value {
+            record.info_mut().insert(field.key.clone(), Some(v));
+        }
+    }
+
+    Ok(true)
+}
+
+// Fills the missing elements of an array value with the elements of another array value of the
+// same type.
+fn fill_missing(dst: &mut field::Value, src: &field::Value) {
+    fn fill<T: Clone>(dst: &mut [Option<T>], src: &[Option<T>]) {
+        for (a, b) in dst.iter_mut().zip(src) {
+            if a.is_none() {
+                *a = b.clone();
+            }
+        }
+    }
+
+    match (dst, src) {
+        (field::Value::IntegerArray(dst), field::Value::IntegerArray(src)) => fill(dst, src),
+        (field::Value::FloatArray(dst), field::Value::FloatArray(src)) => fill(dst, src),
+        (field::Value::CharacterArray(dst), field::Value::CharacterArray(src)) => fill(dst, src),
+        (field::Value::StringArray(dst), field::Value::StringArray(src)) => fill(dst, src),
+        _ => {}
+    }
+}
+
+fn is_missing(value: &field::Value) -> bool {
+    match value {
+        field::Value::IntegerArray(values) => values.iter().all(|v| v.is_none()),
+        field::Value::FloatArray(values) => values.iter().all(|v| v.is_none()),
+        field::Value::CharacterArray(values) => values.iter().all(|v| v.is_none()),
+        field::Value::StringArray(values) => values.iter().all(|v| v.is_none()),
+        _ => false,
+    }
+}
+
+fn append(dst: &mut field::Value, src: field::Value) {
+    match (dst, src) {
+        (field::Value::IntegerArray(dst), field::Value::IntegerArray(src)) => dst.extend(src),
+        (field::Value::FloatArray(dst), field::Value::FloatArray(src)) => dst.extend(src),
+        (field::Value::CharacterArray(dst), field::Value::CharacterArray(src)) => dst.extend(src),
+        (field::Value::StringArray(dst), field::Value::StringArray(src)) => dst.extend(src),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn build_bgzf<F>(data: &[u8], f: F) -> io::Result<(Vec<u8>, tabix::Index)>
+    where
+        F: FnOnce(&[u8]) -> io::Result<tabix::Index>,
+    {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        io::Write::write_all(&mut writer, data)?;
+        let buf = writer.finish()?;
+        let index = f(&buf)?;
+        Ok((buf, index))
+    }
+
+    #[test]
+    fn test_annotate_from_vcf() -> Result<(), Box<dyn std::error::Error>> {
+        let source_data = b"##fileformat=VCFv4.3
+##INFO=<ID=AF,Number=A,Type=Float,Description=\"Allele frequency\">
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Combined depth across samples\">
+##contig=<ID=sq0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t8\t.\tA\tC\t.\t.\tAF=0.25;DP=13
+sq0\t8\t.\tA\tT\t.\t.\tAF=0.5;DP=21
+sq0\t13\t.\tG\tA\t.\t.\tAF=0.75
+";
+
+        let (buf, index) = build_bgzf(source_data, |src| {
+            lines::tests::index(src, tabix::index::header::Builder::vcf().build())
+        })?;
+
+        let mut source_reader = Reader::new(bgzf::Reader::new(Cursor::new(buf)));
+        let source_header: Header = source_reader.read_header()?.parse()?;
+
+        let mut annotator = Builder::default()
+            .add_info_field("AF".parse()?, "SRC_AF".parse()?)
+            .add_info_field("DP".parse()?, "SRC_DP".parse()?)
+            .build_from_vcf(source_reader, source_header, index)?;
+
+        let mut header: Header = "##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+"
+        .parse()?;
+
+        annotator.update_header(&mut header);
+        let src_af: info::Key = "SRC_AF".parse()?;
+        let src_dp: info::Key = "SRC_DP".parse()?;
+        assert_eq!(
+            header.infos().get(&src_af).map(|m| m.number()),
+            Some(Number::A)
+        );
+        assert_eq!(
+            header.infos().get(&src_dp).map(|m| m.ty()),
+            Some(Type::Integer)
+        );
+
+        let mut record = Record::try_from_str("sq0\t8\t.\tA\tT,G,C\t.\t.\t.", &header)?;
+        assert!(annotator.annotate(&mut record)?);
+        assert_eq!(
+            record.info().get(&src_af),
+            Some(Some(&field::Value::FloatArray(vec![
+                Some(0.5),
+                None,
+                Some(0.25)
+            ])))
+        );
+        assert_eq!(
+            record.info().get(&src_dp),
+            Some(Some(&field::Value::Integer(13)))
+        );
+
+        let mut record = Record::try_from_str("sq0\t13\t.\tG\tA\t.\t.\t.", &header)?;
+        assert!(annotator.annotate(&mut record)?);
+        assert_eq!(
+            record.info().get(&src_af),
+            Some(Some(&field::Value::FloatArray(vec![Some(0.75)])))
+        );
+        assert!(record.info().get(&src_dp).is_none());
+
+        let mut record = Record::try_from_str("sq0\t13\t.\tG\tC\t.\t.\t.", &header)?;
+        assert!(!annotator.annotate(&mut record)?);
+        assert!(record.info().is_empty());
+
+        let mut record = Record::try_from_str("sq1\t8\t.\tA\tT\t.\t.\t.", &header)?;
+        assert!(!annotator.annotate(&mut record)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_annotate_from_tabix() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b"#chrom\tstart\tend\tname\tscore
+sq0\t4\t10\tr0\t5
+sq0\t7\t9\tr1\t.
+sq0\t20\t30\tr2\t8
+";
+
+        let (buf, index) = build_bgzf(data, |src| {
+            lines::tests::index(src, tabix::index::header::Builder::bed().build())
+        })?;
+
+        let mut annotator = Builder::default()
+            .add_column(
+                4,
+                "REGION".parse()?,
+                Map::<Info>::new(Number::Unknown, Type::String, "Region names"),
+            )
+            .add_column(
+                5,
+                "SCORE".parse()?,
+                Map::<Info>::new(Number::Count(1), Type::Integer, "Region score"),
+            )
+            .add_column(
+                4,
+                "IN_REGION".parse()?,
+                Map::<Info>::new(Number::Count(0), Type::Flag, "In a region"),
+            )
+            .build_from_tabix(bgzf::Reader::new(Cursor::new(buf)), index)?;
+
+        let header = Header::default();
+        let region: info::Key = "REGION".parse()?;
+        let score: info::Key = "SCORE".parse()?;
+        let in_region: info::Key = "IN_REGION".parse()?;
+
+        let mut record = Record::try_from_str("sq0\t8\t.\tA\tT\t.\t.\t.", &header)?;
+        assert!(annotator.annotate(&mut record)?);
+        assert_eq!(
+            record.info().get(&region),
+            Some(Some(&field::Value::StringArray(vec![
+                Some(String::from("r0")),
+                Some(String::from("r1")),
+            ])))
+        );
+        assert_eq!(
+            record.info().get(&score),
+            Some(Some(&field::Value::Integer(5)))
+        );
+        assert_eq!(
+            record.info().get(&in_region),
+            Some(Some(&field::Value::Flag))
+        );
+
+        // BED end positions are exclusive.
+        let mut record = Record::try_from_str("sq0\t10\t.\tA\tT\t.\t.\t.", &header)?;
+        assert!(annotator.annotate(&mut record)?);
+        assert_eq!(
+            record.info().get(&region),
+            Some(Some(&field::Value::StringArray(vec![Some(String::from(
+                "r0"
+            ))])))
+        );
+
+        let mut record = Record::try_from_str("sq0\t11\t.\tA\tT\t.\t.\t.", &header)?;
+        assert!(!annotator.annotate(&mut record)?);
+        assert!(record.info().is_empty());
+
+        // The deletion spans into the next region.
+        let mut record = Record::try_from_str("sq0\t20\t.\tAC\tA\t.\t.\t.", &header)?;
+        assert!(annotator.annotate(&mut record)?);
+        assert_eq!(
+            record.info().get(&score),
+            Some(Some(&field::Value::Integer(8)))
+        );
+
+        Ok(())
+    }
+}
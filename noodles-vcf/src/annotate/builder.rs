@@ -0,0 +1,169 @@
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_tabix as tabix;
+
+use super::{Annotator, Field, FieldSource, Source};
+use crate::{
+    header::{
+        info,
+        record::value::{map::Info, Map},
+    },
+    Header, Reader,
+};
+
+/// A VCF record annotator builder.
+#[derive(Default)]
+pub struct Builder {
+    info_fields: Vec<(info::Key, info::Key)>,
+    columns: Vec<(usize, info::Key, Map<Info>)>,
+}
+
+impl Builder {
+    /// Adds an `INFO` field to copy from a VCF source.
+    ///
+    /// The source field is copied to the field with the given key. Its header definition is
+    /// taken from the source header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::annotate;
+    ///
+    /// let builder = annotate::Builder::default()
+    ///     .add_info_field("AF".parse()?, "SRC_AF".parse()?);
+    /// # Ok::<_, noodles_vcf::header::info::key::ParseError>(())
+    /// ```
+    pub fn add_info_field(mut self, source_key: info::Key, key: info::Key) -> Self {
+        self.info_fields.push((source_key, key));
+        self
+    }
+
+    /// Adds a column to copy from a tabix source.
+    ///
+    /// The column index is 1-based, as in the tabix index header. Column values are parsed using
+    /// the given header definition and set in the field with the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     annotate,
+    ///     header::{
+    ///         record::value::{
+    ///             map::{info::Type, Info},
+    ///             Map,
+    ///         },
+    ///         Number,
+    ///     },
+    /// };
+    ///
+    /// let builder = annotate::Builder::default().add_column(
+    ///     4,
+    ///     "REGION".parse()?,
+    ///     Map::<Info>::new(Number::Unknown, Type::String, "Overlapping region names"),
+    /// );
+    /// # Ok::<_, noodles_vcf::header::info::key::ParseError>(())
+    /// ```
+    pub fn add_column(mut self, column: usize, key: info::Key, info: Map<Info>) -> Self {
+        self.columns.push((column, key, info));
+        self
+    }
+
+    /// Builds an annotator with a bgzipped, tabix-indexed VCF source.
+    ///
+    /// The reader must be positioned after the header.
+    ///
+    /// An error is returned if columns were added or if a source field is not defined in the
+    /// source header.
+    pub fn build_from_vcf<R>(
+        self,
+        reader: Reader<bgzf::Reader<R>>,
+        header: Header,
+        index: tabix::Index,
+    ) -> io::Result<Annotator<R>>
+    where
+        R: Read + Seek,
+    {
+        if !self.columns.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "columns cannot be copied from a VCF source",
+            ));
+        }
+
+        let fields = self
+            .info_fields
+            .into_iter()
+            .map(|(source_key, key)| {
+                let info = header.infos().get(&source_key).cloned().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("missing source header info definition: {source_key}"),
+                    )
+                })?;
+
+                Ok(Field {
+                    source: FieldSource::Info(source_key),
+                    key,
+                    info,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Annotator {
+            source: Source::Vcf {
+                reader,
+                header: Box::new(header),
+                index,
+            },
+            fields,
+        })
+    }
+
+    /// Builds an annotator with a bgzipped, tabix-indexed tab-delimited source, e.g., BED.
+    ///
+    /// The index header describes the columns of the reference sequence name, start position,
+    /// and end position and the coordinate system.
+    ///
+    /// An error is returned if `INFO` fields were added or if a column index is 0.
+    pub fn build_from_tabix<R>(
+        self,
+        reader: bgzf::Reader<R>,
+        index: tabix::Index,
+    ) -> io::Result<Annotator<R>>
+    where
+        R: Read + Seek,
+    {
+        if !self.info_fields.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "INFO fields can only be copied from a VCF source",
+            ));
+        }
+
+        let fields = self
+            .columns
+            .into_iter()
+            .map(|(column, key, info)| {
+                if column == 0 {
+                    Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid column index: 0",
+                    ))
+                } else {
+                    Ok(Field {
+                        source: FieldSource::Column(column),
+                        key,
+                        info,
+                    })
+                }
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Annotator {
+            source: Source::Tabix { reader, index },
+            fields,
+        })
+    }
+}
@@ -0,0 +1,194 @@
+//! Queries of tabix-indexed tab-delimited files.
+
+use std::io::{self, BufRead, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::{region::Interval, Position};
+use noodles_csi::BinningIndex;
+use noodles_tabix::{
+    self as tabix,
+    index::header::format::{CoordinateSystem, Format},
+};
+
+/// Returns the lines that intersect the given interval.
+///
+/// Line endings are removed.
+pub(super) fn query<R>(
+    reader: &mut bgzf::Reader<R>,
+    index: &tabix::Index,
+    reference_sequence_name: &str,
+    start: Position,
+    end: Position,
+) -> io::Result<Vec<String>>
+where
+    R: Read + Seek,
+{
+    let header = index.header();
+
+    let reference_sequence_id = match header
+        .reference_sequence_names()
+        .get_index_of(reference_sequence_name)
+    {
+        Some(i) => i,
+        None => return Ok(Vec::new()),
+    };
+
+    let interval = Interval::from(start..=end);
+    let chunks = index.query(reference_sequence_id, interval)?;
+
+    let mut lines = Vec::new();
+    let mut buf = String::new();
+
+    for chunk in chunks {
+        reader.seek(chunk.start())?;
+
+        while reader.virtual_position() < chunk.end() {
+            buf.clear();
+
+            if reader.read_line(&mut buf)? == 0 {
+                break;
+            }
+
+            let line = buf.trim_end_matches(['\n', '\r']);
+
+            if let Some((name, line_start, line_end)) = parse_interval(header, line)? {
+                let line_interval = Interval::from(line_start..=line_end);
+
+                if name == reference_sequence_name && line_interval.intersects(interval) {
+                    lines.push(line.into());
+                }
+            }
+        }
+    }
+
+    Ok(lines)
+}
+
+/// Parses the reference sequence name and 1-based start and end positions of a line.
+///
+/// `None` is returned for comment lines.
+pub(super) fn parse_interval<'l>(
+    header: &tabix::index::Header,
+    line: &'l str,
+) -> io::Result<Option<(&'l str, Position, Position)>> {
+    if line.is_empty() || line.as_bytes()[0] == header.line_comment_prefix() {
+        return Ok(None);
+    }
+
+    let fields: Vec<_> = line.split('\t').collect();
+
+    let get = |i: usize| {
+        i.checked_sub(1)
+            .and_then(|j| fields.get(j))
+            .copied()
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("missing column {i}"))
+            })
+    };
+
+    let parse_position = |i: usize| {
+        get(i).and_then(|s| {
+            s.parse::<usize>()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+    };
+
+    let name = get(header.reference_sequence_name_index())?;
+    let raw_start = parse_position(header.start_position_index())?;
+
+    let (start, end) = match header.format() {
+        Format::Generic(CoordinateSystem::Bed) => {
+            let end = match header.end_position_index() {
+                Some(i) => parse_position(i)?,
+                None => raw_start + 1,
+            };
+
+            // BED intervals are 0-based and half-open.
+            (raw_start + 1, end)
+        }
+        Format::Vcf => {
+            const REFERENCE_BASES_INDEX: usize = 4;
+            let len = get(REFERENCE_BASES_INDEX)?.len();
+            (raw_start, raw_start + len.max(1) - 1)
+        }
+        _ => {
+            let end = match header.end_position_index() {
+                Some(i) => parse_position(i)?,
+                None => raw_start,
+            };
+
+            (raw_start, end)
+        }
+    };
+
+    let start =
+        Position::try_from(start).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let end = Position::try_from(end.max(usize::from(start)))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(Some((name, start, end)))
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use noodles_csi::index::reference_sequence::bin::Chunk;
+
+    use super::*;
+
+    /// Builds a tabix index of bgzipped tab-delimited data.
+    pub fn index(src: &[u8], header: tabix::index::Header) -> io::Result<tabix::Index> {
+        let mut reader = bgzf::Reader::new(src);
+        let mut indexer = tabix::Index::indexer();
+        let mut buf = String::new();
+
+        loop {
+            let start = reader.virtual_position();
+
+            buf.clear();
+
+            if reader.read_line(&mut buf)? == 0 {
+                break;
+            }
+
+            let end = reader.virtual_position();
+            let line = buf.trim_end_matches(['\n', '\r']);
+
+            if let Some((name, start_position, end_position)) = parse_interval(&header, line)? {
+                indexer.add_record(name, start_position, end_position, Chunk::new(start, end));
+            }
+        }
+
+        indexer.set_header(header);
+
+        Ok(indexer.build())
+    }
+
+    #[test]
+    fn test_parse_interval() -> io::Result<()> {
+        let header = tabix::index::Header::builder().build();
+        assert!(parse_interval(&header, "#sq0\t1\t2")?.is_none());
+
+        let expected = (
+            "sq0",
+            Position::try_from(5).unwrap(),
+            Position::try_from(8).unwrap(),
+        );
+
+        let header = tabix::index::header::Builder::bed().build();
+        assert_eq!(parse_interval(&header, "sq0\t4\t8\tr0")?, Some(expected));
+
+        let header = tabix::index::header::Builder::gff().build();
+        assert_eq!(parse_interval(&header, "sq0\t.\t.\t5\t8")?, Some(expected));
+
+        let header = tabix::index::header::Builder::vcf().build();
+        assert_eq!(
+            parse_interval(&header, "sq0\t5\t.\tACGT\tA")?,
+            Some(expected)
+        );
+
+        assert!(parse_interval(&header, "sq0").is_err());
+        assert!(parse_interval(&header, "sq0\tx\t.\tA").is_err());
+
+        Ok(())
+    }
+}
@@ -17,6 +17,7 @@ pub use self::{
 use std::{hash::Hash, str::FromStr};
 
 use indexmap::{IndexMap, IndexSet};
+use noodles_core::region_list::Dictionary;
 
 use self::record::value::{
     map::{contig, AlternativeAllele, Contig, Filter, Format, Info, Meta},
@@ -733,6 +734,41 @@ impl std::fmt::Display for Header {
     }
 }
 
+impl From<&Header> for Dictionary {
+    /// Creates a reference sequence dictionary from the contigs in a VCF header.
+    ///
+    /// Contigs without a length have an unknown length in the dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_list::Dictionary;
+    /// use noodles_vcf::{self as vcf, header::record::value::{map::Contig, Map}};
+    ///
+    /// let mut contig = Map::<Contig>::new();
+    /// *contig.length_mut() = Some(8);
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig("sq0".parse()?, contig)
+    ///     .add_contig("sq1".parse()?, Map::<Contig>::new())
+    ///     .build();
+    ///
+    /// let dictionary = Dictionary::from(&header);
+    /// assert_eq!(dictionary.get("sq0"), Some(Some(8)));
+    /// assert_eq!(dictionary.get("sq1"), Some(None));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn from(header: &Header) -> Self {
+        let mut dictionary = Self::new();
+
+        for (name, contig) in header.contigs() {
+            dictionary.insert(name.as_ref(), contig.length());
+        }
+
+        dictionary
+    }
+}
+
 impl FromStr for Header {
     type Err = ParseError;
 
@@ -2,6 +2,7 @@
 
 mod v4_3;
 mod v4_4;
+mod v4_5;
 
 use std::{borrow::Borrow, error, fmt, str::FromStr};
 
@@ -102,6 +103,62 @@ pub const HAPLOTYPE_ID: Key = Key::Standard(Standard::HaplotypeId);
 /// Unique identifier of ancestral haplotype (`AHAP`).
 pub const ANCESTRAL_HAPLOTYPE_ID: Key = Key::Standard(Standard::AncestralHaplotypeId);
 
+/// Local alternate alleles (`LAA`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_ALLELES: Key = Key::Standard(Standard::LocalAlleles);
+
+/// Read depth for each local allele (`LAD`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_READ_DEPTHS: Key = Key::Standard(Standard::LocalReadDepths);
+
+/// Read depth for each local allele on the forward strand (`LADF`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_FORWARD_STRAND_READ_DEPTHS: Key =
+    Key::Standard(Standard::LocalForwardStrandReadDepths);
+
+/// Read depth for each local allele on the reverse strand (`LADR`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_REVERSE_STRAND_READ_DEPTHS: Key =
+    Key::Standard(Standard::LocalReverseStrandReadDepths);
+
+/// Expected alternate allele counts for each local alternate allele (`LEC`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_EXPECTED_ALTERNATE_ALLELE_COUNTS: Key =
+    Key::Standard(Standard::LocalExpectedAlternateAlleleCounts);
+
+/// Genotype likelihoods for local alleles (`LGL`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_GENOTYPE_LIKELIHOODS: Key = Key::Standard(Standard::LocalGenotypeLikelihoods);
+
+/// Genotype posterior probabilities for local alleles (`LGP`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_GENOTYPE_POSTERIOR_PROBABILITIES: Key =
+    Key::Standard(Standard::LocalGenotypePosteriorProbabilities);
+
+/// Genotype against the local alleles (`LGT`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_GENOTYPE: Key = Key::Standard(Standard::LocalGenotype);
+
+/// Phred-scaled genotype likelihoods rounded to the closest integer for local alleles (`LPL`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_ROUNDED_GENOTYPE_LIKELIHOODS: Key =
+    Key::Standard(Standard::LocalRoundedGenotypeLikelihoods);
+
+/// Phred-scaled genotype posterior probabilities rounded to the closest integer for local alleles (`LPP`).
+///
+/// Added in VCF 4.5.
+pub const LOCAL_ROUNDED_GENOTYPE_POSTERIOR_PROBABILITIES: Key =
+    Key::Standard(Standard::LocalRoundedGenotypePosteriorProbabilities);
+
 /// A reserved VCF header format key.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Standard {
@@ -168,6 +225,47 @@ pub enum Standard {
     HaplotypeId,
     /// Unique identifier of ancestral haplotype (`AHAP`).
     AncestralHaplotypeId,
+
+    /// Local alternate alleles (`LAA`).
+    ///
+    /// Added in VCF 4.5.
+    LocalAlleles,
+    /// Read depth for each local allele (`LAD`).
+    ///
+    /// Added in VCF 4.5.
+    LocalReadDepths,
+    /// Read depth for each local allele on the forward strand (`LADF`).
+    ///
+    /// Added in VCF 4.5.
+    LocalForwardStrandReadDepths,
+    /// Read depth for each local allele on the reverse strand (`LADR`).
+    ///
+    /// Added in VCF 4.5.
+    LocalReverseStrandReadDepths,
+    /// Expected alternate allele counts for each local alternate allele (`LEC`).
+    ///
+    /// Added in VCF 4.5.
+    LocalExpectedAlternateAlleleCounts,
+    /// Genotype likelihoods for local alleles (`LGL`).
+    ///
+    /// Added in VCF 4.5.
+    LocalGenotypeLikelihoods,
+    /// Genotype posterior probabilities for local alleles (`LGP`).
+    ///
+    /// Added in VCF 4.5.
+    LocalGenotypePosteriorProbabilities,
+    /// Genotype against the local alleles (`LGT`).
+    ///
+    /// Added in VCF 4.5.
+    LocalGenotype,
+    /// Phred-scaled genotype likelihoods rounded to the closest integer for local alleles (`LPL`).
+    ///
+    /// Added in VCF 4.5.
+    LocalRoundedGenotypeLikelihoods,
+    /// Phred-scaled genotype posterior probabilities rounded to the closest integer for local alleles (`LPP`).
+    ///
+    /// Added in VCF 4.5.
+    LocalRoundedGenotypePosteriorProbabilities,
 }
 
 impl AsRef<str> for Standard {
@@ -201,6 +299,17 @@ impl AsRef<str> for Standard {
             Self::NovelVariantQualityScore => "NQ",
             Self::HaplotypeId => "HAP",
             Self::AncestralHaplotypeId => "AHAP",
+
+            Self::LocalAlleles => "LAA",
+            Self::LocalReadDepths => "LAD",
+            Self::LocalForwardStrandReadDepths => "LADF",
+            Self::LocalReverseStrandReadDepths => "LADR",
+            Self::LocalExpectedAlternateAlleleCounts => "LEC",
+            Self::LocalGenotypeLikelihoods => "LGL",
+            Self::LocalGenotypePosteriorProbabilities => "LGP",
+            Self::LocalGenotype => "LGT",
+            Self::LocalRoundedGenotypeLikelihoods => "LPL",
+            Self::LocalRoundedGenotypePosteriorProbabilities => "LPP",
         }
     }
 }
@@ -259,6 +368,17 @@ impl FromStr for Standard {
             "HAP" => Ok(Self::HaplotypeId),
             "AHAP" => Ok(Self::AncestralHaplotypeId),
 
+            "LAA" => Ok(Self::LocalAlleles),
+            "LAD" => Ok(Self::LocalReadDepths),
+            "LADF" => Ok(Self::LocalForwardStrandReadDepths),
+            "LADR" => Ok(Self::LocalReverseStrandReadDepths),
+            "LEC" => Ok(Self::LocalExpectedAlternateAlleleCounts),
+            "LGL" => Ok(Self::LocalGenotypeLikelihoods),
+            "LGP" => Ok(Self::LocalGenotypePosteriorProbabilities),
+            "LGT" => Ok(Self::LocalGenotype),
+            "LPL" => Ok(Self::LocalRoundedGenotypeLikelihoods),
+            "LPP" => Ok(Self::LocalRoundedGenotypePosteriorProbabilities),
+
             _ => Err(ParseError::Invalid),
         }
     }
@@ -359,6 +479,7 @@ pub(crate) fn definition(
 ) -> Option<(Number, Type, &'static str)> {
     match key {
         Key::Standard(k) => match (file_format.major(), file_format.minor()) {
+            (4, 5) => v4_5::definition(*k),
             (4, 4) => v4_4::definition(*k),
             (4, 3) => v4_3::definition(*k),
             _ => None,
@@ -367,23 +488,28 @@ pub(crate) fn definition(
     }
 }
 
+// The VCF 4.3 definition or, for keys added later, the latest definition.
+fn reserved_definition(key: Standard) -> Option<(Number, Type, &'static str)> {
+    v4_3::definition(key).or_else(|| v4_5::definition(key))
+}
+
 pub(crate) fn number(key: &Key) -> Option<Number> {
     match key {
-        Key::Standard(k) => v4_3::definition(*k).map(|(number, _, _)| number),
+        Key::Standard(k) => reserved_definition(*k).map(|(number, _, _)| number),
         Key::Other(_) => None,
     }
 }
 
 pub(crate) fn ty(key: &Key) -> Option<Type> {
     match key {
-        Key::Standard(k) => v4_3::definition(*k).map(|(_, ty, _)| ty),
+        Key::Standard(k) => reserved_definition(*k).map(|(_, ty, _)| ty),
         Key::Other(_) => None,
     }
 }
 
 pub(crate) fn description(key: &Key) -> Option<&str> {
     match key {
-        Key::Standard(k) => v4_3::definition(*k).map(|(_, _, description)| description),
+        Key::Standard(k) => reserved_definition(*k).map(|(_, _, description)| description),
         Key::Other(_) => None,
     }
 }
@@ -426,6 +552,20 @@ mod tests {
         assert_eq!(HAPLOTYPE_ID.to_string(), "HAP");
         assert_eq!(ANCESTRAL_HAPLOTYPE_ID.to_string(), "AHAP");
 
+        assert_eq!(LOCAL_ALLELES.to_string(), "LAA");
+        assert_eq!(LOCAL_READ_DEPTHS.to_string(), "LAD");
+        assert_eq!(LOCAL_FORWARD_STRAND_READ_DEPTHS.to_string(), "LADF");
+        assert_eq!(LOCAL_REVERSE_STRAND_READ_DEPTHS.to_string(), "LADR");
+        assert_eq!(LOCAL_EXPECTED_ALTERNATE_ALLELE_COUNTS.to_string(), "LEC");
+        assert_eq!(LOCAL_GENOTYPE_LIKELIHOODS.to_string(), "LGL");
+        assert_eq!(LOCAL_GENOTYPE_POSTERIOR_PROBABILITIES.to_string(), "LGP");
+        assert_eq!(LOCAL_GENOTYPE.to_string(), "LGT");
+        assert_eq!(LOCAL_ROUNDED_GENOTYPE_LIKELIHOODS.to_string(), "LPL");
+        assert_eq!(
+            LOCAL_ROUNDED_GENOTYPE_POSTERIOR_PROBABILITIES.to_string(),
+            "LPP"
+        );
+
         assert_eq!(Key::Other(Other(String::from("NDLS"))).to_string(), "NDLS");
     }
 
@@ -463,6 +603,20 @@ mod tests {
         assert_eq!("HAP".parse(), Ok(HAPLOTYPE_ID));
         assert_eq!("AHAP".parse(), Ok(ANCESTRAL_HAPLOTYPE_ID));
 
+        assert_eq!("LAA".parse(), Ok(LOCAL_ALLELES));
+        assert_eq!("LAD".parse(), Ok(LOCAL_READ_DEPTHS));
+        assert_eq!("LADF".parse(), Ok(LOCAL_FORWARD_STRAND_READ_DEPTHS));
+        assert_eq!("LADR".parse(), Ok(LOCAL_REVERSE_STRAND_READ_DEPTHS));
+        assert_eq!("LEC".parse(), Ok(LOCAL_EXPECTED_ALTERNATE_ALLELE_COUNTS));
+        assert_eq!("LGL".parse(), Ok(LOCAL_GENOTYPE_LIKELIHOODS));
+        assert_eq!("LGP".parse(), Ok(LOCAL_GENOTYPE_POSTERIOR_PROBABILITIES));
+        assert_eq!("LGT".parse(), Ok(LOCAL_GENOTYPE));
+        assert_eq!("LPL".parse(), Ok(LOCAL_ROUNDED_GENOTYPE_LIKELIHOODS));
+        assert_eq!(
+            "LPP".parse(),
+            Ok(LOCAL_ROUNDED_GENOTYPE_POSTERIOR_PROBABILITIES)
+        );
+
         assert_eq!("NDLS".parse(), Ok(Key::Other(Other(String::from("NDLS")))));
 
         assert_eq!("".parse::<Key>(), Err(ParseError::Empty));
@@ -510,6 +664,9 @@ mod tests {
         assert_eq!(number(&HAPLOTYPE_ID), Some(Number::Count(1)));
         assert_eq!(number(&ANCESTRAL_HAPLOTYPE_ID), Some(Number::Count(1)));
 
+        assert_eq!(number(&LOCAL_ALLELES), Some(Number::Unknown));
+        assert_eq!(number(&LOCAL_GENOTYPE), Some(Number::Count(1)));
+
         assert!(number(&Key::Other(Other(String::from("NDLS")))).is_none());
     }
 
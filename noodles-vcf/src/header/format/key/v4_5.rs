@@ -0,0 +1,59 @@
+use super::{v4_4, Standard};
+use crate::header::{record::value::map::format::Type, Number};
+
+pub(super) fn definition(key: Standard) -> Option<(Number, Type, &'static str)> {
+    match key {
+        Standard::LocalAlleles => Some((
+            Number::Unknown,
+            Type::Integer,
+            "Strictly increasing, 1-based indices into ALT, indicating which alternate alleles are relevant (local) for the current sample",
+        )),
+        Standard::LocalReadDepths => Some((
+            Number::Unknown,
+            Type::Integer,
+            "Read depth for each local allele",
+        )),
+        Standard::LocalForwardStrandReadDepths => Some((
+            Number::Unknown,
+            Type::Integer,
+            "Read depth for each local allele on the forward strand",
+        )),
+        Standard::LocalReverseStrandReadDepths => Some((
+            Number::Unknown,
+            Type::Integer,
+            "Read depth for each local allele on the reverse strand",
+        )),
+        Standard::LocalExpectedAlternateAlleleCounts => Some((
+            Number::Unknown,
+            Type::Integer,
+            "Expected alternate allele counts for each local alternate allele",
+        )),
+        Standard::LocalGenotypeLikelihoods => Some((
+            Number::Unknown,
+            Type::Float,
+            "Genotype likelihoods for local alleles",
+        )),
+        Standard::LocalGenotypePosteriorProbabilities => Some((
+            Number::Unknown,
+            Type::Float,
+            "Genotype posterior probabilities for local alleles",
+        )),
+        Standard::LocalGenotype => Some((
+            Number::Count(1),
+            Type::String,
+            "Genotype against the local alleles",
+        )),
+        Standard::LocalRoundedGenotypeLikelihoods => Some((
+            Number::Unknown,
+            Type::Integer,
+            "Phred-scaled genotype likelihoods rounded to the closest integer for local alleles",
+        )),
+        Standard::LocalRoundedGenotypePosteriorProbabilities => Some((
+            Number::Unknown,
+            Type::Integer,
+            "Phred-scaled genotype posterior probabilities rounded to the closest integer for local alleles",
+        )),
+
+        _ => v4_4::definition(key),
+    }
+}
@@ -595,7 +595,7 @@ pub(crate) fn definition(
 ) -> Option<(Number, Type, &'static str)> {
     match key {
         Key::Standard(k) => match (file_format.major(), file_format.minor()) {
-            (4, 4) => v4_4::definition(*k),
+            (4, 4) | (4, 5) => v4_4::definition(*k),
             (4, 3) => v4_3::definition(*k),
             _ => None,
         },
@@ -603,23 +603,28 @@ pub(crate) fn definition(
     }
 }
 
+// The VCF 4.3 definition or, for keys added later, the latest definition.
+fn reserved_definition(key: Standard) -> Option<(Number, Type, &'static str)> {
+    v4_3::definition(key).or_else(|| v4_4::definition(key))
+}
+
 pub(crate) fn number(key: &Key) -> Option<Number> {
     match key {
-        Key::Standard(k) => v4_3::definition(*k).map(|(number, _, _)| number),
+        Key::Standard(k) => reserved_definition(*k).map(|(number, _, _)| number),
         Key::Other(_) => None,
     }
 }
 
 pub(crate) fn ty(key: &Key) -> Option<Type> {
     match key {
-        Key::Standard(k) => v4_3::definition(*k).map(|(_, ty, _)| ty),
+        Key::Standard(k) => reserved_definition(*k).map(|(_, ty, _)| ty),
         Key::Other(_) => None,
     }
 }
 
 pub(crate) fn description(key: &Key) -> Option<&str> {
     match key {
-        Key::Standard(k) => v4_3::definition(*k).map(|(_, _, description)| description),
+        Key::Standard(k) => reserved_definition(*k).map(|(_, _, description)| description),
         Key::Other(_) => None,
     }
 }
@@ -0,0 +1,525 @@
+use std::{
+    fmt, io,
+    ops::{Range, RangeFrom},
+};
+
+use memchr::memchr_iter;
+
+use crate::{
+    record::{
+        genotypes::{Genotype, Keys},
+        AlternateBases, Chromosome, Field, Filters, Genotypes, Ids, Info, Position, QualityScore,
+        ReferenceBases, FIELD_DELIMITER, MISSING_FIELD,
+    },
+    Header,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Bounds {
+    pub(crate) chromosome_end: usize,
+    pub(crate) position_end: usize,
+    pub(crate) ids_end: usize,
+    pub(crate) reference_bases_end: usize,
+    pub(crate) alternate_bases_end: usize,
+    pub(crate) quality_score_end: usize,
+    pub(crate) filters_end: usize,
+    pub(crate) info_end: usize,
+    pub(crate) format_end: usize,
+}
+
+impl Bounds {
+    // Indexes the field delimiters of a raw record.
+    //
+    // If there are no genotypes, the info and format ends are the end of the line.
+    pub(crate) fn index(&mut self, s: &str) -> io::Result<()> {
+        let mut ends = memchr_iter(FIELD_DELIMITER as u8, s.as_bytes()).chain(Some(s.len()));
+
+        let mut next_end = |field: Field| {
+            ends.next().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("missing field: {field}"),
+                )
+            })
+        };
+
+        // Fields are only assigned once the entire line is indexed so that a malformed line does
+        // not leave partially updated bounds.
+        *self = Self {
+            chromosome_end: next_end(Field::Chromosome)?,
+            position_end: next_end(Field::Position)?,
+            ids_end: next_end(Field::Ids)?,
+            reference_bases_end: next_end(Field::ReferenceBases)?,
+            alternate_bases_end: next_end(Field::AlternateBases)?,
+            quality_score_end: next_end(Field::QualityScore)?,
+            filters_end: next_end(Field::Filters)?,
+            info_end: next_end(Field::Info)?,
+            format_end: next_end(Field::Format).unwrap_or(s.len()),
+        };
+
+        Ok(())
+    }
+
+    fn chromosome_range(&self) -> Range<usize> {
+        0..self.chromosome_end
+    }
+
+    fn position_range(&self) -> Range<usize> {
+        self.chromosome_end + 1..self.position_end
+    }
+
+    fn ids_range(&self) -> Range<usize> {
+        self.position_end + 1..self.ids_end
+    }
+
+    fn reference_bases_range(&self) -> Range<usize> {
+        self.ids_end + 1..self.reference_bases_end
+    }
+
+    fn alternate_bases_range(&self) -> Range<usize> {
+        self.reference_bases_end + 1..self.alternate_bases_end
+    }
+
+    fn quality_score_range(&self) -> Range<usize> {
+        self.alternate_bases_end + 1..self.quality_score_end
+    }
+
+    fn filters_range(&self) -> Range<usize> {
+        self.quality_score_end + 1..self.filters_end
+    }
+
+    fn info_range(&self) -> Range<usize> {
+        self.filters_end + 1..self.info_end
+    }
+
+    fn format_range(&self) -> Range<usize> {
+        self.info_end + 1..self.format_end
+    }
+
+    fn samples_range(&self) -> RangeFrom<usize> {
+        self.format_end + 1..
+    }
+}
+
+/// An immutable, lazily-evalulated VCF record.
+///
+/// The record holds the raw line and the bounds of each field. Fields are parsed on access and
+/// are _not_ memoized. Sample columns are split only when requested, so position-only scans skip
+/// parsing the genotypes entirely.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Record {
+    pub(crate) buf: String,
+    pub(crate) bounds: Bounds,
+}
+
+impl Record {
+    /// Returns the chromosome.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::Chromosome};
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.chromosome()?, Chromosome::Name(String::from("sq0")));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn chromosome(&self) -> io::Result<Chromosome> {
+        self.buf[self.bounds.chromosome_range()]
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::Position};
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.position()?, Position::from(1));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn position(&self) -> io::Result<Position> {
+        self.buf[self.bounds.position_range()]
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.ids()?.is_empty());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn ids(&self) -> io::Result<Ids> {
+        match &self.buf[self.bounds.ids_range()] {
+            MISSING_FIELD => Ok(Ids::default()),
+            s => s
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
+
+    /// Returns the reference bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::reference_bases::Base};
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(&record.reference_bases()?[..], [Base::A]);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn reference_bases(&self) -> io::Result<ReferenceBases> {
+        self.buf[self.bounds.reference_bases_range()]
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the alternate bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.alternate_bases()?.is_empty());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn alternate_bases(&self) -> io::Result<AlternateBases> {
+        self.buf[self.bounds.alternate_bases_range()]
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the quality score.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.quality_score()?.is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn quality_score(&self) -> io::Result<Option<QualityScore>> {
+        match &self.buf[self.bounds.quality_score_range()] {
+            MISSING_FIELD => Ok(None),
+            s => s
+                .parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
+
+    /// Returns the filters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.filters()?.is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn filters(&self) -> io::Result<Option<Filters>> {
+        match &self.buf[self.bounds.filters_range()] {
+            MISSING_FIELD => Ok(None),
+            s => s
+                .parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
+
+    /// Returns the info.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let header = vcf::Header::default();
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.info(&header)?.is_empty());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn info(&self, header: &Header) -> io::Result<Info> {
+        let s = &self.buf[self.bounds.info_range()];
+        Info::try_from_str(s, header.infos())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the genotypes of all samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let header = vcf::Header::default();
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.genotypes(&header)?.is_empty());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn genotypes(&self, header: &Header) -> io::Result<Genotypes> {
+        if !self.has_samples() {
+            return Ok(Genotypes::default());
+        }
+
+        let s = &self.buf[self.bounds.format_range().start..];
+        Genotypes::parse(s, header).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the number of sample columns.
+    ///
+    /// This only counts the columns. No sample is parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.sample_count(), 0);
+    /// ```
+    pub fn sample_count(&self) -> usize {
+        if self.has_samples() {
+            let s = &self.buf[self.bounds.samples_range()];
+            memchr_iter(FIELD_DELIMITER as u8, s.as_bytes()).count() + 1
+        } else {
+            0
+        }
+    }
+
+    /// Returns the genotype of the sample at the given index.
+    ///
+    /// Only the requested sample column and the format are parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+    /// sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:GQ\t0/0:13\t0/1:21
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// let header = reader.read_header()?.parse()?;
+    ///
+    /// let mut record = vcf::lazy::Record::default();
+    /// reader.read_lazy_record(&mut record)?;
+    ///
+    /// let genotype = record.sample(&header, 1).transpose()?;
+    /// assert_eq!(genotype.and_then(|g| g.genotype()).transpose()?, Some("0/1".parse()?));
+    ///
+    /// assert!(record.sample(&header, 2).is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn sample(&self, header: &Header, i: usize) -> Option<io::Result<Genotype>> {
+        if !self.has_samples() {
+            return None;
+        }
+
+        let s = self.buf[self.bounds.samples_range()]
+            .split(FIELD_DELIMITER)
+            .nth(i)?;
+
+        Some(
+            self.keys(header)
+                .and_then(|keys| parse_sample(s, header, &keys)),
+        )
+    }
+
+    /// Returns an iterator over the genotypes of all samples.
+    ///
+    /// The format is parsed once, and each sample column is parsed as the iterator advances.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let header = vcf::Header::default();
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.samples(&header)?.next().is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn samples<'a>(
+        &'a self,
+        header: &'a Header,
+    ) -> io::Result<impl Iterator<Item = io::Result<Genotype>> + 'a> {
+        let (keys, s) = if self.has_samples() {
+            let keys = self.keys(header)?;
+            (keys, &self.buf[self.bounds.samples_range()])
+        } else {
+            (Keys::default(), "")
+        };
+
+        let mut columns = s.split(FIELD_DELIMITER);
+
+        if s.is_empty() {
+            columns.next();
+        }
+
+        Ok(columns.map(move |t| parse_sample(t, header, &keys)))
+    }
+
+    /// Parses all fields into a [`crate::Record`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let header = vcf::Header::default();
+    /// let record = vcf::lazy::Record::default();
+    /// let record = record.try_into_record(&header)?;
+    /// assert_eq!(record.to_string(), "sq0\t1\t.\tA\t.\t.\t.\t.");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn try_into_record(&self, header: &Header) -> io::Result<crate::Record> {
+        crate::Record::try_from_str(&self.buf, header)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn has_samples(&self) -> bool {
+        self.bounds.format_end < self.buf.len()
+    }
+
+    fn keys(&self, header: &Header) -> io::Result<Keys> {
+        let s = &self.buf[self.bounds.format_range()];
+        Keys::try_from_str(s, header.formats())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl AsRef<str> for Record {
+    fn as_ref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Record")
+            .field("chromosome", &self.chromosome())
+            .field("position", &self.position())
+            .field("ids", &self.ids())
+            .field("reference_bases", &self.reference_bases())
+            .field("alternate_bases", &self.alternate_bases())
+            .field("quality_score", &self.quality_score())
+            .field("filters", &self.filters())
+            .finish()
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        let buf = String::from("sq0\t1\t.\tA\t.\t.\t.\t.");
+
+        let bounds = Bounds {
+            chromosome_end: 3,
+            position_end: 5,
+            ids_end: 7,
+            reference_bases_end: 9,
+            alternate_bases_end: 11,
+            quality_score_end: 13,
+            filters_end: 15,
+            info_end: 17,
+            format_end: 17,
+        };
+
+        Self { buf, bounds }
+    }
+}
+
+fn parse_sample(s: &str, header: &Header, keys: &Keys) -> io::Result<Genotype> {
+    Genotype::parse(s, header.formats(), keys)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_record(s: &str) -> io::Result<Record> {
+        let mut record = Record::default();
+        record.buf.clear();
+        record.buf.push_str(s);
+        record.bounds.index(&record.buf)?;
+        Ok(record)
+    }
+
+    #[test]
+    fn test_default() -> io::Result<()> {
+        let expected = Record::default();
+        let actual = build_record(&expected.buf)?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header = "##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+"
+        .parse()?;
+
+        let s = "sq0\t8\tnd0\tA\tC,G\t13\tPASS\tNS=2\tGT:GQ\t0/1:7\t1/2:.";
+        let record = build_record(s)?;
+
+        assert_eq!(record.chromosome()?, "sq0".parse()?);
+        assert_eq!(record.position()?, Position::from(8));
+        assert_eq!(record.ids()?, "nd0".parse()?);
+        assert_eq!(record.reference_bases()?, "A".parse()?);
+        assert_eq!(record.alternate_bases()?, "C,G".parse()?);
+        assert_eq!(record.quality_score()?, Some(QualityScore::try_from(13.0)?));
+        assert_eq!(record.filters()?, Some(Filters::Pass));
+        assert_eq!(record.info(&header)?, "NS=2".parse()?);
+
+        assert_eq!(record.sample_count(), 2);
+
+        let samples: Vec<_> = record.samples(&header)?.collect::<io::Result<_>>()?;
+        let genotypes = record.genotypes(&header)?;
+        assert_eq!(&samples[..], &genotypes[..]);
+
+        assert_eq!(
+            record.sample(&header, 1).transpose()?,
+            Some(genotypes[1].clone())
+        );
+        assert!(record.sample(&header, 2).is_none());
+
+        assert_eq!(record.try_into_record(&header)?.to_string(), s);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fields_without_samples() -> io::Result<()> {
+        let header = Header::default();
+
+        let record = build_record("sq0\t8\t.\tA\t.\t.\t.\t.\tGT")?;
+        assert_eq!(record.sample_count(), 0);
+        assert!(record.sample(&header, 0).is_none());
+        assert!(record.samples(&header)?.next().is_none());
+        assert!(record.genotypes(&header)?.is_empty());
+
+        let record = build_record("sq0\t8\t.\tA\t.\t.\t.\t.")?;
+        assert_eq!(record.sample_count(), 0);
+        assert!(record.samples(&header)?.next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_with_missing_fields() {
+        let mut bounds = Record::default().bounds;
+
+        assert!(matches!(
+            bounds.index("sq0\t8\t.\tA\t.\t.\t."),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
@@ -23,9 +23,18 @@
 #[cfg(feature = "async")]
 mod r#async;
 
+mod alleles;
+pub mod annotate;
 pub mod header;
+pub mod lazy;
+pub mod local_alleles;
+pub mod merge;
+pub mod multiallelic;
 pub mod reader;
+pub mod recompute;
 pub mod record;
+pub mod structural_variant;
+pub mod subset;
 mod writer;
 
 pub use self::{header::Header, reader::Reader, record::Record, writer::Writer};
@@ -0,0 +1,519 @@
+//! VCF local allele transformation.
+//!
+//! VCF 4.5 adds local-allele `FORMAT` fields, which describe each sample against a subset of the
+//! alternate alleles of a record rather than all of them. The local alleles of a sample are given
+//! by `LAA`, a strictly increasing list of 1-based indices into the alternate alleles. Fields such
+//! as `LGT`, `LAD`, and `LPL` then index into the reference allele and the local alleles. This
+//! keeps `Number=R` and `Number=G` fields small in records with many alternate alleles, e.g., in
+//! gVCFs.
+//!
+//! [`localize`] converts global fields (e.g., `GT`, `AD`, `PL`) to their local counterparts, and
+//! [`globalize`] converts local fields back to global fields.
+
+use std::io;
+
+use crate::{
+    alleles::{self, AlleleMap},
+    header::{
+        format::{key, Key},
+        record::value::{map::Format, Map},
+    },
+    record::{
+        alternate_bases::allele::{Allele, Symbol},
+        genotypes::{self, genotype::field::Value, Genotype},
+        Genotypes,
+    },
+    Header, Record,
+};
+
+// Global fields and their local counterparts.
+const FIELDS: [(Key, Key); 9] = [
+    (key::GENOTYPE, key::LOCAL_GENOTYPE),
+    (key::READ_DEPTHS, key::LOCAL_READ_DEPTHS),
+    (
+        key::FORWARD_STRAND_READ_DEPTHS,
+        key::LOCAL_FORWARD_STRAND_READ_DEPTHS,
+    ),
+    (
+        key::REVERSE_STRAND_READ_DEPTHS,
+        key::LOCAL_REVERSE_STRAND_READ_DEPTHS,
+    ),
+    (
+        key::EXPECTED_ALTERNATE_ALLELE_COUNTS,
+        key::LOCAL_EXPECTED_ALTERNATE_ALLELE_COUNTS,
+    ),
+    (key::GENOTYPE_LIKELIHOODS, key::LOCAL_GENOTYPE_LIKELIHOODS),
+    (
+        key::GENOTYPE_POSTERIOR_PROBABILITIES,
+        key::LOCAL_GENOTYPE_POSTERIOR_PROBABILITIES,
+    ),
+    (
+        key::ROUNDED_GENOTYPE_LIKELIHOODS,
+        key::LOCAL_ROUNDED_GENOTYPE_LIKELIHOODS,
+    ),
+    (
+        key::ROUNDED_GENOTYPE_POSTERIOR_PROBABILITIES,
+        key::LOCAL_ROUNDED_GENOTYPE_POSTERIOR_PROBABILITIES,
+    ),
+];
+
+fn local_key(key: &Key) -> Option<&'static Key> {
+    FIELDS
+        .iter()
+        .find(|(global_key, _)| global_key == key)
+        .map(|(_, local_key)| local_key)
+}
+
+fn global_key(key: &Key) -> Option<&'static Key> {
+    FIELDS
+        .iter()
+        .find(|(_, local_key)| local_key == key)
+        .map(|(global_key, _)| global_key)
+}
+
+/// Adds the header definitions of the local fields that correspond to the global fields defined
+/// in the header.
+///
+/// This includes `LAA`. Existing definitions are kept.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, header::format::key, local_alleles};
+///
+/// let mut header = vcf::Header::builder()
+///     .add_format(key::GENOTYPE, vcf::header::record::value::Map::from(&key::GENOTYPE))
+///     .build();
+///
+/// local_alleles::localize_header(&mut header);
+///
+/// assert!(header.formats().contains_key(&key::LOCAL_ALLELES));
+/// assert!(header.formats().contains_key(&key::LOCAL_GENOTYPE));
+/// ```
+pub fn localize_header(header: &mut Header) {
+    let keys: Vec<_> = header
+        .formats()
+        .keys()
+        .filter_map(local_key)
+        .cloned()
+        .collect();
+
+    let formats = header.formats_mut();
+
+    for key in std::iter::once(key::LOCAL_ALLELES).chain(keys) {
+        if !formats.contains_key(&key) {
+            let format = Map::<Format>::from(&key);
+            formats.insert(key, format);
+        }
+    }
+}
+
+/// Adds the header definitions of the global fields that correspond to the local fields defined
+/// in the header.
+///
+/// Existing definitions are kept.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, header::format::key, local_alleles};
+///
+/// let mut header = vcf::Header::builder()
+///     .add_format(
+///         key::LOCAL_GENOTYPE,
+///         vcf::header::record::value::Map::from(&key::LOCAL_GENOTYPE),
+///     )
+///     .build();
+///
+/// local_alleles::globalize_header(&mut header);
+///
+/// assert!(header.formats().contains_key(&key::GENOTYPE));
+/// ```
+pub fn globalize_header(header: &mut Header) {
+    let keys: Vec<_> = header
+        .formats()
+        .keys()
+        .filter_map(global_key)
+        .cloned()
+        .collect();
+
+    let formats = header.formats_mut();
+
+    for key in keys {
+        if !formats.contains_key(&key) {
+            let format = Map::<Format>::from(&key);
+            formats.insert(key, format);
+        }
+    }
+}
+
+/// Converts the global allele-dependent fields of a record to local fields.
+///
+/// The local alleles of each sample are the alternate alleles called in its `GT`. If the record
+/// has an unspecified allele (`<*>`), it is always a local allele, which keeps the likelihoods of
+/// unobserved alleles, as is common in gVCFs.
+///
+/// `LAA` is added as the first field, and `GT`, `AD`, `ADF`, `ADR`, `EC`, `GL`, `GP`, `PL`, and
+/// `PP` are replaced with `LGT`, `LAD`, `LADF`, `LADR`, `LEC`, `LGL`, `LGP`, `LPL`, and `LPP`,
+/// respectively. Values are subset using the `Number` of the global field in the header. All
+/// other fields are copied.
+///
+/// A record that already has `LAA` is returned as-is. [`localize_header`] adds the header
+/// definitions of the local fields.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, local_alleles};
+///
+/// let data = b"##fileformat=VCFv4.3
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+/// sq0\t1\t.\tA\tC,G,T\t.\tPASS\t.\tGT:AD\t0/2:5,0,8,0
+/// ";
+///
+/// let mut reader = vcf::Reader::new(&data[..]);
+/// let header = reader.read_header()?.parse()?;
+/// let record = reader.records(&header).next().transpose()?.unwrap();
+///
+/// let record = local_alleles::localize(&header, &record)?;
+/// assert_eq!(record.genotypes().to_string(), "LAA:LGT:LAD\t2:0/1:5,8");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn localize(header: &Header, record: &Record) -> io::Result<Record> {
+    let format = record.format();
+
+    if format.contains(&key::LOCAL_ALLELES) || !format.iter().any(|k| local_key(k).is_some()) {
+        return Ok(record.clone());
+    }
+
+    let allele_count = record.alternate_bases().len() + 1;
+
+    let unspecified_allele = record
+        .alternate_bases()
+        .iter()
+        .position(|allele| matches!(allele, Allele::Symbol(Symbol::Unspecified)))
+        .map(|i| i + 1);
+
+    let mut keys = vec![key::LOCAL_ALLELES];
+
+    for key in format.iter() {
+        let key = local_key(key).unwrap_or(key);
+
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+
+    let default_ploidy = alleles::ploidy([record]);
+    let mut genotypes = Vec::with_capacity(record.genotypes().len());
+
+    for genotype in record.genotypes().iter() {
+        let mut local_alleles: Vec<usize> = match genotype.genotype() {
+            Some(result) => result
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+                .iter()
+                .filter_map(|allele| allele.position())
+                .filter(|&i| i > 0)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        local_alleles.extend(unspecified_allele);
+        local_alleles.sort_unstable();
+        local_alleles.dedup();
+
+        let allele_map = build_local_allele_map(&local_alleles, allele_count)?;
+
+        let mut local_genotype: Genotype = keys.iter().map(|key| (key.clone(), None)).collect();
+        let ploidy = alleles::sample_ploidy(genotype).unwrap_or(default_ploidy);
+
+        let local_alleles_value = if local_alleles.is_empty() {
+            None
+        } else {
+            local_alleles
+                .iter()
+                .map(|&i| {
+                    i32::try_from(i)
+                        .map(Some)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                })
+                .collect::<io::Result<_>>()
+                .map(|values| Some(Value::IntegerArray(values)))?
+        };
+
+        local_genotype.insert(key::LOCAL_ALLELES, local_alleles_value);
+
+        for (key, value) in genotype.iter() {
+            match local_key(key) {
+                Some(local_key) => {
+                    let value = value
+                        .as_ref()
+                        .map(|v| allele_map.remap_format_value(header, key, v, ploidy))
+                        .transpose()?;
+
+                    local_genotype.insert(local_key.clone(), value);
+                }
+                None => {
+                    local_genotype.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        genotypes.push(local_genotype);
+    }
+
+    let keys = genotypes::Keys::try_from(keys)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let mut local_record = record.clone();
+    *local_record.genotypes_mut() = Genotypes::new(keys, genotypes);
+
+    Ok(local_record)
+}
+
+/// Converts the local allele-dependent fields of a record to global fields.
+///
+/// This is the inverse of [`localize`]. `LAA` is removed, and each local field is replaced with
+/// its global counterpart, e.g., `LGT` with `GT` and `LPL` with `PL`. Values of alleles that are not
+/// local to a sample are missing. A missing `LAA` means that only the reference allele is local.
+///
+/// If a record has both a local field and its global counterpart, the global field is kept.
+///
+/// A record without `LAA` is returned as-is. [`globalize_header`] adds the header definitions of
+/// the global fields.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, local_alleles};
+///
+/// let data = b"##fileformat=VCFv4.5
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+/// sq0\t1\t.\tA\tC,G,T\t.\tPASS\t.\tLAA:LGT:LAD\t2:0/1:5,8
+/// ";
+///
+/// let mut reader = vcf::Reader::new(&data[..]);
+/// let header = reader.read_header()?.parse()?;
+/// let record = reader.records(&header).next().transpose()?.unwrap();
+///
+/// let record = local_alleles::globalize(&header, &record)?;
+/// assert_eq!(record.genotypes().to_string(), "GT:AD\t0/2:5,.,8,.");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn globalize(header: &Header, record: &Record) -> io::Result<Record> {
+    let format = record.format();
+
+    if !format.contains(&key::LOCAL_ALLELES) {
+        return Ok(record.clone());
+    }
+
+    let allele_count = record.alternate_bases().len() + 1;
+
+    let mut keys = Vec::with_capacity(format.len() - 1);
+
+    for key in format.iter() {
+        if key == &key::LOCAL_ALLELES {
+            continue;
+        }
+
+        let key = match global_key(key) {
+            Some(global_key) if !format.contains(global_key) => global_key,
+            _ => key,
+        };
+
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+
+    // The genotype must be the first field.
+    if let Some(i) = keys.iter().position(|key| key == &key::GENOTYPE) {
+        let key = keys.remove(i);
+        keys.insert(0, key);
+    }
+
+    let default_ploidy = alleles::ploidy([record]);
+    let mut genotypes = Vec::with_capacity(record.genotypes().len());
+
+    for genotype in record.genotypes().iter() {
+        let local_alleles = parse_local_alleles(genotype, allele_count)?;
+
+        let mut targets = Vec::with_capacity(local_alleles.len() + 1);
+        targets.push(0);
+        targets.extend(local_alleles);
+        let allele_map = AlleleMap::from_targets(targets, allele_count);
+
+        let mut global_genotype: Genotype = keys.iter().map(|key| (key.clone(), None)).collect();
+        let ploidy = alleles::sample_ploidy(genotype).unwrap_or(default_ploidy);
+
+        for (key, value) in genotype.iter() {
+            if key == &key::LOCAL_ALLELES {
+                continue;
+            }
+
+            match global_key(key) {
+                Some(global_key) if !format.contains(global_key) => {
+                    let value = value
+                        .as_ref()
+                        .map(|v| allele_map.remap_format_value(header, global_key, v, ploidy))
+                        .transpose()?;
+
+                    global_genotype.insert(global_key.clone(), value);
+                }
+                _ => {
+                    global_genotype.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        genotypes.push(global_genotype);
+    }
+
+    let keys = genotypes::Keys::try_from(keys)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let mut global_record = record.clone();
+    *global_record.genotypes_mut() = Genotypes::new(keys, genotypes);
+
+    Ok(global_record)
+}
+
+// Maps all alleles to the reference allele and the given local alternate alleles. Alleles that
+// are not local are mapped past the end of the local alleles.
+fn build_local_allele_map(local_alleles: &[usize], allele_count: usize) -> io::Result<AlleleMap> {
+    let local_allele_count = local_alleles.len() + 1;
+
+    let mut targets = vec![local_allele_count; allele_count];
+    targets[0] = 0;
+
+    let mut sources = Vec::with_capacity(local_allele_count);
+    sources.push(Some(0));
+
+    for (i, &j) in local_alleles.iter().enumerate() {
+        let target = targets.get_mut(j).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid allele position: {j}"),
+            )
+        })?;
+
+        *target = i + 1;
+        sources.push(Some(j));
+    }
+
+    Ok(AlleleMap::new(targets, sources))
+}
+
+fn parse_local_alleles(genotype: &Genotype, allele_count: usize) -> io::Result<Vec<usize>> {
+    let values = match genotype.get(&key::LOCAL_ALLELES) {
+        Some(Some(Value::IntegerArray(values))) => values.clone(),
+        Some(Some(Value::Integer(n))) => vec![Some(*n)],
+        Some(None) | None => return Ok(Vec::new()),
+        Some(Some(_)) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid LAA value type",
+            ))
+        }
+    };
+
+    let mut local_alleles = Vec::with_capacity(values.len());
+
+    for value in values.into_iter().flatten() {
+        let i = usize::try_from(value)
+            .ok()
+            .filter(|&i| i > 0 && i < allele_count)
+            .filter(|&i| local_alleles.last().map(|&j| i > j).unwrap_or(true))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid LAA value: {value}"),
+                )
+            })?;
+
+        local_alleles.push(i);
+    }
+
+    Ok(local_alleles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "##fileformat=VCFv4.5
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description=\"Read depth for each allele\">
+##FORMAT=<ID=DP,Number=1,Type=Integer,Description=\"Read depth\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods rounded to the closest integer\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1\tsample2
+";
+
+    #[test]
+    fn test_localize() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header: Header = HEADER.parse()?;
+
+        let record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC,G,<*>\t.\tPASS\t.\tGT:AD:DP:PL\t0/2:5,0,8,1:14:10,20,30,0,40,50,60,70,80,90\t0/0:9,0,0,1:10:0,.,.,.,.,.,.,.,.,.\t./.:.:.:.",
+            &header,
+        )?;
+
+        let local_record = localize(&header, &record)?;
+
+        assert_eq!(
+            local_record.genotypes().to_string(),
+            "LAA:LGT:LAD:DP:LPL\t2,3:0/1:5,8,1:14:10,0,50,60,80,90\t3:0/0:9,1:10:0,.,.\t3:./.:.:.:."
+        );
+
+        localize_header(&mut header);
+        assert!(header.formats().contains_key(&key::LOCAL_ALLELES));
+        assert!(header.formats().contains_key(&key::LOCAL_READ_DEPTHS));
+        assert!(!header
+            .formats()
+            .contains_key(&key::LOCAL_GENOTYPE_LIKELIHOODS));
+
+        assert_eq!(globalize(&header, &local_record)?.genotypes().to_string(),
+            "GT:AD:DP:PL\t0/2:5,.,8,1:14:10,.,.,0,.,50,60,.,80,90\t0/0:9,.,.,1:10:0,.,.,.,.,.,.,.,.,.\t./.:.:.:.");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_localize_with_local_alleles() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header = HEADER.parse()?;
+        let record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC\t.\tPASS\t.\tLAA:LGT\t1:0/1\t.:0/0\t.:./.",
+            &header,
+        )?;
+        assert_eq!(localize(&header, &record)?, record);
+        Ok(())
+    }
+
+    #[test]
+    fn test_globalize() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header = HEADER.parse()?;
+
+        let record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\t.\tLGT:LAA:LAD\t0/1:2:5,8\t0/0:.:9\t./.:3:.",
+            &header,
+        )?;
+
+        assert!(globalize(&header, &record).is_err());
+
+        let record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\t.\tGT:LGT:LAA:LAD\t./.:0/1:2:5,8\t./.:0/0:.:9\t./.:./.:.:.",
+            &header,
+        )?;
+
+        assert_eq!(
+            globalize(&header, &record)?.genotypes().to_string(),
+            "GT:LGT:AD\t./.:0/1:5,.,8\t./.:0/0:9,.,.\t./.:./.:."
+        );
+
+        let record =
+            Record::try_from_str("sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/1\t0/0\t./.", &header)?;
+        assert_eq!(globalize(&header, &record)?, record);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,770 @@
+//! VCF multi-sample merging.
+//!
+//! A [`Merger`] combines several coordinate-sorted VCF inputs, each with its own set of samples,
+//! into a single stream of records that spans the union of all samples. Records at the same
+//! position are combined into one record: their reference bases are reconciled, their alternate
+//! alleles are unified, and allele-dependent fields (`GT` and fields with `Number=A`, `R`, or
+//! `G`) are remapped to the merged allele order. Unphased genotypes are written with their alleles
+//! sorted. Samples that have no record at a position are filled with missing values.
+//!
+//! The allele count fields `AC` and `AN` are recomputed from the merged `GT` values and are
+//! removed if the merged record has no `GT` field. Other `INFO` fields with `Number=A`, `R`, or
+//! `G` take the first nonmissing value for each allele in input order, and other `INFO` fields
+//! take the value of the first record.
+//!
+//! BCF inputs can be merged by converting each record to a VCF record, e.g., using
+//! `noodles_bcf::Record::try_into_vcf_record`.
+
+use std::io;
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    alleles::{self, AlleleMap},
+    header::{format, info::key},
+    recompute::Counts,
+    record::{
+        genotypes::{self, genotype::field, Genotype},
+        info::field::Value as InfoValue,
+        AlternateBases, Chromosome, Genotypes, Position,
+    },
+    Header, Record,
+};
+
+/// Merges a list of VCF headers.
+///
+/// The merged header contains the union of the contigs, filters, `INFO` and `FORMAT`
+/// definitions, alternative alleles, and other records of all inputs. Samples are concatenated
+/// in input order. The file format is the latest of all inputs.
+///
+/// An error is returned if an `INFO` or `FORMAT` field is defined with conflicting numbers or
+/// types, if a contig is defined with conflicting lengths, or if a sample name is not unique
+/// across the inputs.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, merge};
+///
+/// let header_a = vcf::Header::builder().add_sample_name("sample0").build();
+/// let header_b = vcf::Header::builder().add_sample_name("sample1").build();
+///
+/// let header = merge::merge_headers(&[header_a, header_b])?;
+///
+/// let sample_names: Vec<_> = header.sample_names().iter().collect();
+/// assert_eq!(sample_names, ["sample0", "sample1"]);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn merge_headers(headers: &[Header]) -> io::Result<Header> {
+    let (first, rest) = headers
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no headers to merge"))?;
+
+    let mut merged = first.clone();
+
+    for header in rest {
+        if header.file_format() > merged.file_format() {
+            *merged.file_format_mut() = header.file_format();
+        }
+
+        for (key, info) in header.infos() {
+            if let Some(merged_info) = merged.infos().get(key) {
+                if merged_info.number() != info.number() || merged_info.ty() != info.ty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("conflicting INFO definitions: {key}"),
+                    ));
+                }
+            } else {
+                merged.infos_mut().insert(key.clone(), info.clone());
+            }
+        }
+
+        for (id, filter) in header.filters() {
+            if !merged.filters().contains_key(id) {
+                merged.filters_mut().insert(id.clone(), filter.clone());
+            }
+        }
+
+        for (key, format) in header.formats() {
+            if let Some(merged_format) = merged.formats().get(key) {
+                if merged_format.number() != format.number() || merged_format.ty() != format.ty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("conflicting FORMAT definitions: {key}"),
+                    ));
+                }
+            } else {
+                merged.formats_mut().insert(key.clone(), format.clone());
+            }
+        }
+
+        for (id, alternative_allele) in header.alternative_alleles() {
+            if !merged.alternative_alleles().contains_key(id) {
+                merged
+                    .alternative_alleles_mut()
+                    .insert(id.clone(), alternative_allele.clone());
+            }
+        }
+
+        if merged.assembly().is_none() {
+            *merged.assembly_mut() = header.assembly().map(|s| s.into());
+        }
+
+        for (name, contig) in header.contigs() {
+            if let Some(merged_contig) = merged.contigs_mut().get_mut(name) {
+                match (merged_contig.length(), contig.length()) {
+                    (Some(a), Some(b)) if a != b => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("conflicting contig lengths: {name}"),
+                        ));
+                    }
+                    (None, Some(b)) => *merged_contig.length_mut() = Some(b),
+                    _ => {}
+                }
+            } else {
+                merged.contigs_mut().insert(name.clone(), contig.clone());
+            }
+        }
+
+        for (id, meta) in header.meta() {
+            if !merged.meta().contains_key(id) {
+                merged.meta_mut().insert(id.clone(), meta.clone());
+            }
+        }
+
+        if merged.pedigree_db().is_none() {
+            *merged.pedigree_db_mut() = header.pedigree_db().map(|s| s.into());
+        }
+
+        for sample_name in header.sample_names() {
+            if !merged.sample_names_mut().insert(sample_name.clone()) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("duplicate sample name: {sample_name}"),
+                ));
+            }
+        }
+
+        for (key, values) in header.other_records() {
+            let merged_values = merged.other_records_mut().entry(key.clone()).or_default();
+
+            for value in values {
+                if !merged_values.contains(value) {
+                    merged_values.push(value.clone());
+                }
+            }
+        }
+    }
+
+    Ok(merged)
+}
+
+/// A merger of coordinate-sorted VCF records from multiple inputs.
+///
+/// Each input must be sorted by contig, in the order the contigs are defined in the header, and
+/// position. Records of contigs that are not defined in any header are ordered by first
+/// appearance.
+pub struct Merger<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    header: Header,
+    sample_counts: Vec<usize>,
+    inputs: Vec<Option<I>>,
+    heads: Vec<Option<Record>>,
+    reference_sequence_names: IndexSet<String>,
+}
+
+impl<I> Merger<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    /// Creates a merger from a list of headers and their corresponding record iterators.
+    ///
+    /// The headers are merged using [`merge_headers`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, merge::Merger};
+    ///
+    /// let src_a = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+    /// sq0\t1\t.\tA\tC\t.\t.\t.\tGT\t0/1
+    /// ";
+    ///
+    /// let src_b = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+    /// sq0\t1\t.\tA\tG\t.\t.\t.\tGT\t1/1
+    /// ";
+    ///
+    /// let mut reader_a = vcf::Reader::new(&src_a[..]);
+    /// let header_a = reader_a.read_header()?.parse()?;
+    /// let records_a: Vec<_> = reader_a.records(&header_a).collect();
+    ///
+    /// let mut reader_b = vcf::Reader::new(&src_b[..]);
+    /// let header_b = reader_b.read_header()?.parse()?;
+    /// let records_b: Vec<_> = reader_b.records(&header_b).collect();
+    ///
+    /// let merger = Merger::new(
+    ///     &[header_a, header_b],
+    ///     vec![records_a.into_iter(), records_b.into_iter()],
+    /// )?;
+    ///
+    /// let header = merger.header().clone();
+    /// let records: Vec<_> = merger.collect::<std::io::Result<_>>()?;
+    ///
+    /// assert_eq!(records.len(), 1);
+    /// assert_eq!(records[0].alternate_bases().to_string(), "C,G");
+    /// assert_eq!(records[0].genotypes().to_string(), "GT\t0/1\t2/2");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(headers: &[Header], inputs: Vec<I>) -> io::Result<Self> {
+        if headers.len() != inputs.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "header and input counts mismatch",
+            ));
+        }
+
+        let header = merge_headers(headers)?;
+        let sample_counts = headers.iter().map(|h| h.sample_names().len()).collect();
+
+        let reference_sequence_names = header
+            .contigs()
+            .keys()
+            .map(|name| name.as_ref().into())
+            .collect();
+
+        let heads = inputs.iter().map(|_| None).collect();
+
+        Ok(Self {
+            header,
+            sample_counts,
+            inputs: inputs.into_iter().map(Some).collect(),
+            heads,
+            reference_sequence_names,
+        })
+    }
+
+    /// Returns the merged header.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    fn fill_heads(&mut self) -> io::Result<()> {
+        for (input, head) in self.inputs.iter_mut().zip(&mut self.heads) {
+            if head.is_some() {
+                continue;
+            }
+
+            if let Some(iter) = input {
+                match iter.next().transpose()? {
+                    Some(record) => *head = Some(record),
+                    None => *input = None,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn sort_key(&mut self, record: &Record) -> (usize, Position) {
+        let name = chromosome_name(record.chromosome());
+        let (i, _) = self.reference_sequence_names.insert_full(name.into());
+        (i, record.position())
+    }
+
+    fn next_group(&mut self) -> io::Result<Option<Vec<(usize, Record)>>> {
+        self.fill_heads()?;
+
+        let mut min_key = None;
+
+        for i in 0..self.heads.len() {
+            if let Some(record) = self.heads[i].take() {
+                let key = self.sort_key(&record);
+
+                if min_key.map(|k| key < k).unwrap_or(true) {
+                    min_key = Some(key);
+                }
+
+                self.heads[i] = Some(record);
+            }
+        }
+
+        let min_key = match min_key {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let mut group = Vec::new();
+
+        for i in 0..self.heads.len() {
+            if let Some(record) = self.heads[i].take() {
+                if self.sort_key(&record) == min_key {
+                    group.push((i, record));
+                } else {
+                    self.heads[i] = Some(record);
+                }
+            }
+        }
+
+        Ok(Some(group))
+    }
+}
+
+impl<I> Iterator for Merger<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_group() {
+            Ok(Some(group)) => Some(merge_records(&self.header, &self.sample_counts, group)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+fn chromosome_name(chromosome: &Chromosome) -> &str {
+    match chromosome {
+        Chromosome::Name(name) => name,
+        Chromosome::Symbol(symbol) => symbol,
+    }
+}
+
+fn merge_records(
+    header: &Header,
+    sample_counts: &[usize],
+    group: Vec<(usize, Record)>,
+) -> io::Result<Record> {
+    let records = group.iter().map(|(_, record)| record);
+
+    let (reference_bases, alternate_bases, allele_maps) = alleles::unify_alleles(records.clone())?;
+    let ploidy = alleles::ploidy(records.clone());
+
+    let first_record = &group[0].1;
+
+    let mut builder = Record::builder()
+        .set_chromosome(first_record.chromosome().clone())
+        .set_position(first_record.position())
+        .set_ids(alleles::merge_ids(records.clone()))
+        .set_reference_bases(reference_bases)
+        .set_alternate_bases(AlternateBases::from(alternate_bases));
+
+    if let Some(quality_score) = alleles::max_quality_score(records.clone()) {
+        builder = builder.set_quality_score(quality_score);
+    }
+
+    if let Some(filters) = alleles::merge_filters(records.clone()) {
+        builder = builder.set_filters(filters);
+    }
+
+    let info = alleles::combine_info(header, records.zip(allele_maps.iter()), ploidy)?;
+    let genotypes = merge_genotypes(header, sample_counts, &group, &allele_maps)?;
+
+    let mut record = builder
+        .set_info(info)
+        .set_genotypes(genotypes)
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    recompute_allele_counts(&mut record)?;
+
+    Ok(record)
+}
+
+// Allele counts of records with disjoint samples cannot be combined, so the `AC` and `AN` fields
+// of the merged record, if any, are recomputed from its genotypes.
+fn recompute_allele_counts(record: &mut Record) -> io::Result<()> {
+    let has_allele_count = record.info().get(&key::ALLELE_COUNT).is_some();
+    let has_total_allele_count = record.info().get(&key::TOTAL_ALLELE_COUNT).is_some();
+
+    if !has_allele_count && !has_total_allele_count {
+        return Ok(());
+    }
+
+    let counts = Counts::from_record(record)?;
+    let info = record.info_mut();
+
+    let counts = match counts {
+        Some(counts) => counts,
+        None => {
+            info.as_mut().shift_remove(&key::ALLELE_COUNT);
+            info.as_mut().shift_remove(&key::TOTAL_ALLELE_COUNT);
+            return Ok(());
+        }
+    };
+
+    if has_allele_count {
+        let allele_counts = counts.allele_counts()[1..]
+            .iter()
+            .map(|&n| try_into_i32(n).map(Some))
+            .collect::<io::Result<_>>()?;
+
+        info.insert(
+            key::ALLELE_COUNT,
+            Some(InfoValue::IntegerArray(allele_counts)),
+        );
+    }
+
+    if has_total_allele_count {
+        let allele_number = try_into_i32(counts.allele_number())?;
+        info.insert(
+            key::TOTAL_ALLELE_COUNT,
+            Some(InfoValue::Integer(allele_number)),
+        );
+    }
+
+    Ok(())
+}
+
+fn try_into_i32(n: usize) -> io::Result<i32> {
+    i32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn merge_genotypes(
+    header: &Header,
+    sample_counts: &[usize],
+    group: &[(usize, Record)],
+    allele_maps: &[AlleleMap],
+) -> io::Result<Genotypes> {
+    let mut has_genotype_key = false;
+    let mut other_keys = IndexSet::new();
+
+    for (_, record) in group {
+        for key in record.format().iter() {
+            if key == &format::key::GENOTYPE {
+                has_genotype_key = true;
+            } else {
+                other_keys.insert(key.clone());
+            }
+        }
+    }
+
+    if !has_genotype_key && other_keys.is_empty() {
+        return Ok(Genotypes::default());
+    }
+
+    let mut keys = Vec::with_capacity(other_keys.len() + 1);
+
+    if has_genotype_key {
+        keys.push(format::key::GENOTYPE);
+    }
+
+    keys.extend(other_keys);
+
+    let mut inputs: IndexMap<usize, (&Record, &AlleleMap)> = IndexMap::new();
+
+    for ((i, record), allele_map) in group.iter().zip(allele_maps) {
+        inputs.insert(*i, (record, allele_map));
+    }
+
+    let ploidy = alleles::ploidy(group.iter().map(|(_, record)| record));
+
+    let missing_genotype = if has_genotype_key {
+        Some(alleles::missing_genotype_value(ploidy))
+    } else {
+        None
+    };
+
+    let sample_count = sample_counts.iter().sum();
+    let mut genotypes = Vec::with_capacity(sample_count);
+
+    for (i, &input_sample_count) in sample_counts.iter().enumerate() {
+        if let Some((record, allele_map)) = inputs.get(&i) {
+            let record_genotypes = record.genotypes();
+
+            if record_genotypes.len() != input_sample_count {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "sample count mismatch at {}:{}: expected {}, got {}",
+                        record.chromosome(),
+                        record.position(),
+                        input_sample_count,
+                        record_genotypes.len()
+                    ),
+                ));
+            }
+
+            for genotype in record_genotypes.iter() {
+                let genotype = remap_genotype(
+                    header,
+                    &keys,
+                    genotype,
+                    allele_map,
+                    ploidy,
+                    missing_genotype.as_ref(),
+                )?;
+
+                genotypes.push(genotype);
+            }
+        } else {
+            for _ in 0..input_sample_count {
+                let genotype: Genotype = keys
+                    .iter()
+                    .map(|key| {
+                        if key == &format::key::GENOTYPE {
+                            (key.clone(), missing_genotype.clone())
+                        } else {
+                            (key.clone(), None)
+                        }
+                    })
+                    .collect();
+
+                genotypes.push(genotype);
+            }
+        }
+    }
+
+    let keys = genotypes::Keys::try_from(keys)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(Genotypes::new(keys, genotypes))
+}
+
+fn remap_genotype(
+    header: &Header,
+    keys: &[format::Key],
+    genotype: &Genotype,
+    allele_map: &AlleleMap,
+    default_ploidy: usize,
+    missing_genotype: Option<&field::Value>,
+) -> io::Result<Genotype> {
+    let ploidy = alleles::sample_ploidy(genotype).unwrap_or(default_ploidy);
+    let mut fields = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        let value = match genotype.get(key) {
+            Some(Some(value)) => Some(allele_map.remap_format_value(header, key, value, ploidy)?),
+            Some(None) => None,
+            None if key == &format::key::GENOTYPE => missing_genotype.cloned(),
+            None => None,
+        };
+
+        fields.push((key.clone(), value));
+    }
+
+    Ok(fields.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(src: &str) -> io::Result<(Header, Vec<io::Result<Record>>)> {
+        let mut reader = crate::Reader::new(src.as_bytes());
+        let header: Header = reader
+            .read_header()?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let records = reader.records(&header).collect();
+        Ok((header, records))
+    }
+
+    fn merge(srcs: &[&str]) -> io::Result<(Header, Vec<String>)> {
+        let mut headers = Vec::new();
+        let mut inputs = Vec::new();
+
+        for src in srcs {
+            let (header, records) = read(src)?;
+            headers.push(header);
+            inputs.push(records.into_iter());
+        }
+
+        let merger = Merger::new(&headers, inputs)?;
+        let header = merger.header().clone();
+        let records = merger
+            .map(|result| result.map(|record| record.to_string()))
+            .collect::<io::Result<_>>()?;
+
+        Ok((header, records))
+    }
+
+    #[test]
+    fn test_merge_headers() -> io::Result<()> {
+        let (header_a, _) = read(
+            "##fileformat=VCFv4.2
+##INFO=<ID=NDLS,Number=1,Type=Integer,Description=\"noodles\">
+##contig=<ID=sq0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+",
+        )?;
+
+        let (header_b, _) = read(
+            "##fileformat=VCFv4.3
+##INFO=<ID=NDLS,Number=1,Type=Integer,Description=\"noodles\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##contig=<ID=sq0,length=8>
+##contig=<ID=sq1,length=13>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+",
+        )?;
+
+        let header = merge_headers(&[header_a.clone(), header_b.clone()])?;
+
+        assert_eq!(header.file_format(), header_b.file_format());
+        assert_eq!(header.infos().len(), 1);
+        assert_eq!(header.formats().len(), 1);
+        assert_eq!(header.contigs().len(), 2);
+        assert_eq!(header.contigs()["sq0"].length(), Some(8));
+        assert_eq!(
+            header.sample_names().iter().collect::<Vec<_>>(),
+            ["sample0", "sample1"]
+        );
+
+        assert!(matches!(
+            merge_headers(&[header_a.clone(), header_a.clone()]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let (header_c, _) = read(
+            "##fileformat=VCFv4.3
+##INFO=<ID=NDLS,Number=A,Type=Integer,Description=\"noodles\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample2
+",
+        )?;
+
+        assert!(matches!(
+            merge_headers(&[header_a, header_c]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            merge_headers(&[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merger() -> io::Result<()> {
+        let src_a = "##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description=\"Read depth for each allele\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t1\tid0\tA\tC\t5\tPASS\tAC=1\tGT:AD:PL\t0/1:3,5:10,0,20
+sq0\t8\t.\tAT\tA\t.\tq10\t.\tGT\t0|1
+sq1\t5\t.\tG\tT\t.\t.\t.\tGT\t1/1
+";
+
+        let src_b = "##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description=\"Read depth for each allele\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+sq0\t1\tid1\tA\tG,C\t13\tPASS\tAC=1,2\tGT:AD:PL\t1/2:0,4,6:50,40,30,20,10,0
+sq0\t8\t.\tA\tG\t.\tPASS\t.\tGT\t1/1
+";
+
+        let (header, records) = merge(&[src_a, src_b])?;
+
+        assert_eq!(
+            header.sample_names().iter().collect::<Vec<_>>(),
+            ["sample0", "sample1"]
+        );
+
+        assert_eq!(
+            records,
+            [
+                "sq0\t1\tid0;id1\tA\tC,G\t13\tPASS\tAC=2,1\tGT:AD:PL\t0/1:3,5,.:10,0,20,.,.,.\t1/2:0,6,4:50,20,0,40,10,30",
+                "sq0\t8\t.\tAT\tA,GT\t.\tq10\t.\tGT\t0|1\t2/2",
+                "sq1\t5\t.\tG\tT\t.\t.\t.\tGT\t1/1\t./.",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merger_with_allele_counts() -> io::Result<()> {
+        let src_a = "##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##INFO=<ID=AN,Number=1,Type=Integer,Description=\"Total number of alleles in called genotypes\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t1\t.\tA\tC\t.\tPASS\tAC=1;AN=2\tGT\t0|1
+sq0\t5\t.\tA\tC\t.\tPASS\tAC=1;AN=2\tGT\t0/1
+";
+
+        let src_b = "##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##INFO=<ID=AN,Number=1,Type=Integer,Description=\"Total number of alleles in called genotypes\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t1\t.\tA\tG\t.\tPASS\tAC=3;AN=4
+sq0\t8\t.\tA\tG\t.\tPASS\tAC=3;AN=4
+";
+
+        let (_, records) = merge(&[src_a, src_b])?;
+
+        assert_eq!(
+            records,
+            [
+                "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,0;AN=2\tGT\t0|1",
+                "sq0\t5\t.\tA\tC\t.\tPASS\tAC=1;AN=2\tGT\t0/1",
+                "sq0\t8\t.\tA\tG\t.\tPASS\t.",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merger_with_reference_only_records() -> io::Result<()> {
+        let src_a = "##fileformat=VCFv4.3
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:PL\t0/0:0\t0:0
+";
+
+        let src_b = "##fileformat=VCFv4.3
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample2
+sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:PL\t0/1/1:30,20,0,10
+";
+
+        let (_, records) = merge(&[src_a, src_b])?;
+
+        assert_eq!(
+            records,
+            ["sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:PL\t0/0:0,.,.\t0:0,.\t0/1/1:30,20,0,10"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merger_with_incompatible_reference_bases() -> io::Result<()> {
+        let src_a = "##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t1\t.\tA\tC\t.\t.\t.\tGT\t0/1
+";
+
+        let src_b = "##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+sq0\t1\t.\tG\tC\t.\t.\t.\tGT\t0/1
+";
+
+        assert!(matches!(
+            merge(&[src_a, src_b]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,473 @@
+//! VCF multiallelic record decomposition and composition.
+//!
+//! [`split`] decomposes a record with several alternate alleles into biallelic records, one per
+//! alternate allele, and [`join`] composes biallelic (or multiallelic) records at the same site
+//! back into a single record. In both directions, `GT` allele indices and fields with `Number=A`,
+//! `R`, or `G` are remapped using the header definitions.
+
+use std::io;
+
+use crate::{
+    alleles::{self, AlleleMap},
+    header::format,
+    record::{
+        genotypes::{
+            self,
+            genotype::field::{self, value::genotype::Allele as GenotypeAllele},
+            Genotype,
+        },
+        AlternateBases, Genotypes, Info,
+    },
+    Header, Record,
+};
+
+/// Splits a multiallelic record into biallelic records.
+///
+/// One record is built for each alternate allele, in order. `INFO` and `FORMAT` fields with
+/// `Number=A`, `R`, or `G` are subset to the reference allele and the given alternate allele. In
+/// `GT` fields, the given alternate allele becomes allele 1, and other alternate alleles become the
+/// reference allele (0); unphased genotypes are then sorted. All other fields are copied.
+///
+/// A record with at most one alternate allele is returned as-is.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, multiallelic};
+///
+/// let data = b"##fileformat=VCFv4.3
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+/// sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,1\tGT\t1/2
+/// ";
+///
+/// let mut reader = vcf::Reader::new(&data[..]);
+/// let header = reader.read_header()?.parse()?;
+/// let record = reader.records(&header).next().transpose()?.unwrap();
+///
+/// let records = multiallelic::split(&header, &record)?;
+///
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[0].to_string(), "sq0\t1\t.\tA\tC\t.\tPASS\tAC=1\tGT\t0/1");
+/// assert_eq!(records[1].to_string(), "sq0\t1\t.\tA\tG\t.\tPASS\tAC=1\tGT\t0/1");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn split(header: &Header, record: &Record) -> io::Result<Vec<Record>> {
+    let allele_count = record.alternate_bases().len() + 1;
+
+    if allele_count <= 2 {
+        return Ok(vec![record.clone()]);
+    }
+
+    let mut records = Vec::with_capacity(allele_count - 1);
+    let ploidy = alleles::ploidy([record]);
+
+    for (i, allele) in record.alternate_bases().iter().enumerate() {
+        let k = i + 1;
+
+        let mut targets = vec![0; allele_count];
+        targets[k] = 1;
+        let allele_map = AlleleMap::new(targets, vec![Some(0), Some(k)]);
+
+        let mut split_record = record.clone();
+        *split_record.alternate_bases_mut() = AlternateBases::from(vec![allele.clone()]);
+
+        let mut info = Info::default();
+
+        for (key, value) in record.info().as_ref() {
+            let value = value
+                .as_ref()
+                .map(|v| allele_map.remap_info_value(header, key, v, ploidy))
+                .transpose()?;
+
+            info.insert(key.clone(), value);
+        }
+
+        *split_record.info_mut() = info;
+
+        let mut genotypes = Vec::with_capacity(record.genotypes().len());
+
+        for genotype in record.genotypes().iter() {
+            let mut split_genotype = Genotype::default();
+            let sample_ploidy = alleles::sample_ploidy(genotype).unwrap_or(ploidy);
+
+            for (key, value) in genotype.iter() {
+                let value = value
+                    .as_ref()
+                    .map(|v| allele_map.remap_format_value(header, key, v, sample_ploidy))
+                    .transpose()?;
+
+                split_genotype.insert(key.clone(), value);
+            }
+
+            genotypes.push(split_genotype);
+        }
+
+        *split_record.genotypes_mut() = Genotypes::new(record.format().clone(), genotypes);
+
+        records.push(split_record);
+    }
+
+    Ok(records)
+}
+
+/// Joins records at the same site into a single record.
+///
+/// This is the inverse of [`split`]. All records must have the same chromosome, position, and
+/// number of samples. The reference bases are reconciled to the longest of the records, and the
+/// alternate alleles are unified in order of first appearance.
+///
+/// `INFO` and `FORMAT` fields with `Number=A`, `R`, or `G` are remapped to the joined allele order
+/// and combined, taking the first nonmissing value for each allele. For each `GT` allele, a
+/// nonreference allele is preferred over a reference allele, which is preferred over a missing
+/// allele, and unphased genotypes are sorted after they are combined. Other fields take the first
+/// nonmissing value. IDs and filters are unioned, and the quality score is the max.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, multiallelic};
+///
+/// let data = b"##fileformat=VCFv4.3
+/// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+/// sq0\t1\t.\tA\tC\t.\tPASS\tAC=1\tGT\t1/0
+/// sq0\t1\t.\tA\tG\t.\tPASS\tAC=1\tGT\t0/1
+/// ";
+///
+/// let mut reader = vcf::Reader::new(&data[..]);
+/// let header = reader.read_header()?.parse()?;
+/// let records: Vec<_> = reader.records(&header).collect::<std::io::Result<_>>()?;
+///
+/// let record = multiallelic::join(&header, &records)?;
+/// assert_eq!(record.to_string(), "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,1\tGT\t1/2");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn join(header: &Header, records: &[Record]) -> io::Result<Record> {
+    let first_record = records
+        .first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records to join"))?;
+
+    let sample_count = first_record.genotypes().len();
+
+    for record in &records[1..] {
+        if record.chromosome() != first_record.chromosome()
+            || record.position() != first_record.position()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "records are not at the same site: {}:{} and {}:{}",
+                    first_record.chromosome(),
+                    first_record.position(),
+                    record.chromosome(),
+                    record.position()
+                ),
+            ));
+        }
+
+        if record.genotypes().len() != sample_count {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "sample count mismatch at {}:{}: expected {}, got {}",
+                    record.chromosome(),
+                    record.position(),
+                    sample_count,
+                    record.genotypes().len()
+                ),
+            ));
+        }
+    }
+
+    let (reference_bases, alternate_bases, allele_maps) = alleles::unify_alleles(records)?;
+
+    let mut builder = Record::builder()
+        .set_chromosome(first_record.chromosome().clone())
+        .set_position(first_record.position())
+        .set_ids(alleles::merge_ids(records))
+        .set_reference_bases(reference_bases)
+        .set_alternate_bases(AlternateBases::from(alternate_bases));
+
+    if let Some(quality_score) = alleles::max_quality_score(records) {
+        builder = builder.set_quality_score(quality_score);
+    }
+
+    if let Some(filters) = alleles::merge_filters(records) {
+        builder = builder.set_filters(filters);
+    }
+
+    let info = alleles::combine_info(
+        header,
+        records.iter().zip(&allele_maps),
+        alleles::ploidy(records),
+    )?;
+    let genotypes = join_genotypes(header, records, &allele_maps, sample_count)?;
+
+    builder
+        .set_info(info)
+        .set_genotypes(genotypes)
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn join_genotypes(
+    header: &Header,
+    records: &[Record],
+    allele_maps: &[AlleleMap],
+    sample_count: usize,
+) -> io::Result<Genotypes> {
+    let mut has_genotype_key = false;
+    let mut other_keys = Vec::new();
+
+    for record in records {
+        for key in record.format().iter() {
+            if key == &format::key::GENOTYPE {
+                has_genotype_key = true;
+            } else if !other_keys.contains(key) {
+                other_keys.push(key.clone());
+            }
+        }
+    }
+
+    if !has_genotype_key && other_keys.is_empty() {
+        return Ok(Genotypes::default());
+    }
+
+    let mut keys = Vec::with_capacity(other_keys.len() + 1);
+
+    if has_genotype_key {
+        keys.push(format::key::GENOTYPE);
+    }
+
+    keys.extend(other_keys);
+
+    let ploidy = alleles::ploidy(records);
+    let mut genotypes = Vec::with_capacity(sample_count);
+
+    for i in 0..sample_count {
+        let mut joined_genotype: Genotype = keys.iter().map(|key| (key.clone(), None)).collect();
+
+        for (record, allele_map) in records.iter().zip(allele_maps) {
+            let genotype = &record.genotypes()[i];
+            let sample_ploidy = alleles::sample_ploidy(genotype).unwrap_or(ploidy);
+
+            for (key, value) in genotype.iter() {
+                // Genotypes are combined by allele index, so their allele order is kept until all
+                // records are combined.
+                let value = match value {
+                    Some(v) if key == &format::key::GENOTYPE => {
+                        field::Value::String(allele_map.remap_genotype(v)?.to_string())
+                    }
+                    Some(v) => allele_map.remap_format_value(header, key, v, sample_ploidy)?,
+                    None => continue,
+                };
+
+                let joined_value = joined_genotype
+                    .get_mut(key)
+                    .expect("missing joined genotype key");
+
+                match joined_value {
+                    Some(v) if key == &format::key::GENOTYPE => {
+                        combine_genotype_values(v, &value)?;
+                    }
+                    Some(v) => {
+                        if alleles::is_allele_dependent(alleles::format_number(header, key)) {
+                            combine_format_values(v, value);
+                        }
+                    }
+                    None => *joined_value = Some(value),
+                }
+            }
+        }
+
+        if has_genotype_key {
+            match joined_genotype.get_mut(&format::key::GENOTYPE) {
+                Some(Some(value)) => {
+                    let mut genotype = alleles::parse_genotype_value(value)?;
+                    alleles::sort_unphased_alleles(&mut genotype);
+                    *value = field::Value::String(genotype.to_string());
+                }
+                Some(value @ None) => *value = Some(alleles::missing_genotype_value(ploidy)),
+                None => {}
+            }
+        }
+
+        genotypes.push(joined_genotype);
+    }
+
+    let keys = genotypes::Keys::try_from(keys)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(Genotypes::new(keys, genotypes))
+}
+
+fn combine_format_values(dst: &mut field::Value, src: field::Value) {
+    use field::Value;
+
+    match (dst, src) {
+        (Value::IntegerArray(a), Value::IntegerArray(b)) => alleles::fill(a, b),
+        (Value::FloatArray(a), Value::FloatArray(b)) => alleles::fill(a, b),
+        (Value::CharacterArray(a), Value::CharacterArray(b)) => alleles::fill(a, b),
+        (Value::StringArray(a), Value::StringArray(b)) => alleles::fill(a, b),
+        _ => {}
+    }
+}
+
+fn combine_genotype_values(dst: &mut field::Value, src: &field::Value) -> io::Result<()> {
+    let mut genotype = alleles::parse_genotype_value(dst)?;
+    let other_genotype = alleles::parse_genotype_value(src)?;
+
+    for (allele, other_allele) in genotype.iter_mut().zip(other_genotype.iter()) {
+        let position = match (allele.position(), other_allele.position()) {
+            (Some(a), _) if a != 0 => Some(a),
+            (_, Some(b)) if b != 0 => Some(b),
+            (Some(0), _) | (_, Some(0)) => Some(0),
+            _ => None,
+        };
+
+        *allele = GenotypeAllele::new(position, allele.phasing());
+    }
+
+    *dst = field::Value::String(genotype.to_string());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Combined depth across samples\">
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description=\"Read depth for each allele\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+";
+
+    fn read(records: &str) -> io::Result<(Header, Vec<Record>)> {
+        let src = format!("{HEADER}{records}");
+        let mut reader = crate::Reader::new(src.as_bytes());
+
+        let header: Header = reader
+            .read_header()?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let records = reader.records(&header).collect::<io::Result<_>>()?;
+
+        Ok((header, records))
+    }
+
+    #[test]
+    fn test_split() -> io::Result<()> {
+        let (header, records) = read(
+            "sq0\t1\tid0\tA\tC,G\t5\tPASS\tDP=8;AC=1,2\tGT:AD:PL\t1/2:1,2,3:10,20,30,40,50,60\t0|2:4,.,6:.\n",
+        )?;
+
+        let actual: Vec<_> = split(&header, &records[0])?
+            .iter()
+            .map(|record| record.to_string())
+            .collect();
+
+        let expected = [
+            "sq0\t1\tid0\tA\tC\t5\tPASS\tDP=8;AC=1\tGT:AD:PL\t0/1:1,2:10,20,30\t0|0:4,.:.",
+            "sq0\t1\tid0\tA\tG\t5\tPASS\tDP=8;AC=2\tGT:AD:PL\t0/1:1,3:10,40,60\t0|1:4,6:.",
+        ];
+
+        assert_eq!(actual, expected);
+
+        let (header, records) = read("sq0\t1\t.\tA\tC\t.\t.\t.\tGT\t0/1\t1/1\n")?;
+        assert_eq!(split(&header, &records[0])?, records);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_with_polyploid_and_haploid_samples() -> io::Result<()> {
+        let (header, records) = read(
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\t.\tGT:PL\t1/2/2:0,10,20,30,40,50,60,70,80,90\t1:0,10,20\n",
+        )?;
+
+        let actual: Vec<_> = split(&header, &records[0])?
+            .iter()
+            .map(|record| record.to_string())
+            .collect();
+
+        let expected = [
+            "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:PL\t0/0/1:0,10,20,30\t1:0,10",
+            "sq0\t1\t.\tA\tG\t.\tPASS\t.\tGT:PL\t0/1/1:0,40,70,90\t0:0,20",
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_with_reference_only_records() -> io::Result<()> {
+        let (header, records) = read(
+            "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:PL\t0/0:0\t0:0\nsq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:PL\t./.:.\t.:.\n",
+        )?;
+
+        let actual = join(&header, &records)?;
+
+        assert_eq!(
+            actual.to_string(),
+            "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:PL\t0/0:0,.,.\t0:0,."
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join() -> io::Result<()> {
+        let (header, records) = read(
+            "sq0\t1\tid0\tA\tC\t5\tPASS\tDP=8;AC=1\tGT:AD:PL\t1/0:1,2:10,20,30\t0|0:4,.:.
+sq0\t1\tid0\tA\tG\t13\tq10\tDP=8;AC=2\tGT:AD:PL\t0/1:1,3:10,40,60\t0|1:4,6:.
+",
+        )?;
+
+        let actual = join(&header, &records)?.to_string();
+        let expected = "sq0\t1\tid0\tA\tC,G\t13\tq10\tDP=8;AC=1,2\tGT:AD:PL\t1/2:1,2,3:10,20,30,40,.,60\t0|2:4,.,6:.";
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_with_different_reference_bases() -> io::Result<()> {
+        let (header, records) = read(
+            "sq0\t1\t.\tA\tC\t.\t.\t.\tGT\t0/1\t0/0
+sq0\t1\t.\tAT\tA\t.\t.\t.\tGT\t0/0\t1/1
+",
+        )?;
+
+        let actual = join(&header, &records)?.to_string();
+        let expected = "sq0\t1\t.\tAT\tCT,A\t.\t.\t.\tGT\t0/1\t2/2";
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_with_invalid_records() -> io::Result<()> {
+        let (header, records) = read(
+            "sq0\t1\t.\tA\tC\t.\t.\t.\tGT\t0/1\t0/0
+sq0\t2\t.\tA\tG\t.\t.\t.\tGT\t0/0\t1/1
+",
+        )?;
+
+        assert!(matches!(
+            join(&header, &records),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            join(&header, &[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -2,8 +2,13 @@
 
 pub(crate) mod query;
 mod records;
+mod regions_query;
 
-pub use self::{query::Query, records::Records};
+pub use self::{
+    query::Query,
+    records::Records,
+    regions_query::{RegionsQuery, WithRegionIndices},
+};
 
 use std::io::{self, BufRead, Read, Seek};
 
@@ -13,7 +18,7 @@ use noodles_core::Region;
 use noodles_csi::BinningIndex;
 use noodles_tabix as tabix;
 
-use super::Header;
+use super::{lazy, Header};
 
 /// A VCF reader.
 ///
@@ -207,6 +212,41 @@ where
     pub fn records<'r, 'h>(&'r mut self, header: &'h Header) -> Records<'r, 'h, R> {
         Records::new(self, header)
     }
+
+    /// Reads a single record without eagerly parsing its fields.
+    ///
+    /// This reads a line from the underlying stream into the given record's buffer and indexes the
+    /// field bounds. No fields are parsed, meaning the record is not necessarily valid. However,
+    /// the record is guaranteed to have at least the 8 fixed fields.
+    ///
+    /// The stream is expected to be directly after the header or at the start of another record.
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0, the
+    /// stream reached EOF. On EOF or error, the record is reset to its default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, record::Position};
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t8\t.\tA\t.\t.\tPASS\t.
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let mut record = vcf::lazy::Record::default();
+    /// reader.read_lazy_record(&mut record)?;
+    ///
+    /// assert_eq!(record.position()?, Position::from(8));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_lazy_record(&mut self, record: &mut lazy::Record) -> io::Result<usize> {
+        read_lazy_record(&mut self.inner, record)
+    }
 }
 
 impl<R> Reader<bgzf::Reader<R>>
@@ -305,6 +345,57 @@ where
             header,
         ))
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The index chunks of all regions are merged, so each compressed block is read at most once,
+    /// and each record is returned at most once, even if the regions overlap or are close
+    /// together. Use [`RegionsQuery::with_region_indices`] to also get the regions each record
+    /// intersects.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_core::Region;
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(vcf::Reader::new)?;
+    ///
+    /// let header = reader.read_header()?.parse()?;
+    ///
+    /// let index = tabix::read("sample.vcf.gz.tbi")?;
+    /// let regions: Vec<Region> = vec!["sq0:8-13".parse()?, "sq0:21-34".parse()?];
+    /// let query = reader.query_regions(&header, &index, &regions)?;
+    ///
+    /// for result in query.with_region_indices() {
+    ///     let (record, region_indices) = result?;
+    ///     println!("{:?} {:?}", record, region_indices);
+    /// }
+    /// Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r, 'h>(
+        &'r mut self,
+        header: &'h Header,
+        index: &tabix::Index,
+        regions: &[Region],
+    ) -> io::Result<RegionsQuery<'r, 'h, R>> {
+        let queries = regions
+            .iter()
+            .map(|region| {
+                resolve_region(index, region)
+                    .map(|(reference_sequence_id, _)| (reference_sequence_id, region.interval()))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let chunks = index.query_intervals(queries)?;
+
+        Ok(RegionsQuery::new(self, chunks, regions, header))
+    }
 }
 
 fn read_header<R>(reader: &mut R) -> io::Result<String>
@@ -346,6 +437,29 @@ where
     String::from_utf8(header_buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
+fn read_lazy_record<R>(reader: &mut R, record: &mut lazy::Record) -> io::Result<usize>
+where
+    R: BufRead,
+{
+    record.buf.clear();
+
+    let result = read_line(reader, &mut record.buf).and_then(|n| {
+        if n > 0 {
+            record.bounds.index(&record.buf)?;
+        }
+
+        Ok(n)
+    });
+
+    // On EOF or error, the buffer no longer matches the bounds. Reset the record so that it
+    // remains valid for reuse.
+    if !matches!(result, Ok(n) if n > 0) {
+        *record = lazy::Record::default();
+    }
+
+    result
+}
+
 // Reads all bytes until a line feed ('\n') or EOF is reached.
 //
 // The buffer will not include the trailing newline ('\n' or '\r\n').
@@ -491,6 +605,60 @@ sq0\t13
         Ok(())
     }
 
+    #[test]
+    fn test_read_lazy_record() -> io::Result<()> {
+        let data = b"sq0\t1\t.\tA\t.\t.\tPASS\t.\r\nsq0\t2\t.\tA\t.\t.\tPASS\t.\tGT\t0/0\n";
+        let mut reader = &data[..];
+
+        let mut record = lazy::Record::default();
+
+        assert_eq!(read_lazy_record(&mut reader, &mut record)?, 22);
+        assert_eq!(record.as_ref(), "sq0\t1\t.\tA\t.\t.\tPASS\t.");
+        assert_eq!(record.sample_count(), 0);
+
+        read_lazy_record(&mut reader, &mut record)?;
+        assert_eq!(record.as_ref(), "sq0\t2\t.\tA\t.\t.\tPASS\t.\tGT\t0/0");
+        assert_eq!(record.sample_count(), 1);
+
+        assert_eq!(read_lazy_record(&mut reader, &mut record)?, 0);
+
+        let data = b"sq0\t1\t.\tA\n";
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_lazy_record(&mut reader, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_lazy_record_after_malformed_line() -> io::Result<()> {
+        use crate::record::Position;
+
+        let data = b"sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0/0\nsq0\t2\nsq0\t3\t.\tA\t.\t.\tPASS\t.\n";
+        let mut reader = &data[..];
+
+        let mut record = lazy::Record::default();
+        read_lazy_record(&mut reader, &mut record)?;
+
+        assert!(matches!(
+            read_lazy_record(&mut reader, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert_eq!(record.as_ref(), lazy::Record::default().as_ref());
+        assert_eq!(record.position()?, Position::from(1));
+        assert_eq!(record.sample_count(), 0);
+
+        read_lazy_record(&mut reader, &mut record)?;
+        assert_eq!(record.as_ref(), "sq0\t3\t.\tA\t.\t.\tPASS\t.");
+        assert_eq!(record.position()?, Position::from(3));
+        assert_eq!(record.sample_count(), 0);
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         let mut buf = String::new();
@@ -515,4 +683,86 @@ sq0\t13
 
         Ok(())
     }
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use noodles_core::Position;
+        use noodles_csi::index::reference_sequence::bin::Chunk;
+
+        use crate::{
+            header::record::value::{map::Contig, Map},
+            record::Position as RecordPosition,
+            Record,
+        };
+
+        let header = Header::builder()
+            .add_contig("sq0".parse()?, Map::<Contig>::new())
+            .add_contig("sq1".parse()?, Map::<Contig>::new())
+            .build();
+
+        let build_record = |chromosome: &str, position, reference_bases: &str| {
+            Record::builder()
+                .set_chromosome(chromosome.parse().unwrap())
+                .set_position(RecordPosition::from(position))
+                .set_reference_bases(reference_bases.parse().unwrap())
+                .build()
+        };
+
+        let records = [
+            build_record("sq0", 1, "ACGTA")?,
+            build_record("sq0", 10, "ACGTA")?,
+            build_record("sq0", 30, "ACGTA")?,
+            build_record("sq1", 8, "A")?,
+        ];
+
+        let mut writer = crate::Writer::new(bgzf::Writer::new(Vec::new()));
+        writer.write_header(&header)?;
+
+        let mut indexer = tabix::Index::indexer();
+
+        for record in &records {
+            let start = writer.get_ref().virtual_position();
+            writer.write_record(record)?;
+            let end = writer.get_ref().virtual_position();
+
+            indexer.add_record(
+                &record.chromosome().to_string(),
+                Position::try_from(usize::from(record.position()))?,
+                Position::try_from(usize::from(record.end()?))?,
+                Chunk::new(start, end),
+            );
+        }
+
+        let index = indexer.build();
+
+        let data = writer.into_inner().finish()?;
+        let mut reader = Reader::new(bgzf::Reader::new(Cursor::new(data)));
+        reader.read_header()?;
+
+        let regions: Vec<Region> = vec![
+            "sq0:3-12".parse()?,
+            "sq0:11-20".parse()?,
+            "sq1:1-10".parse()?,
+        ];
+
+        let actual: Vec<_> = reader
+            .query_regions(&header, &index, &regions)?
+            .with_region_indices()
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (records[0].clone(), vec![0]),
+            (records[1].clone(), vec![0, 1]),
+            (records[3].clone(), vec![2]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let regions: Vec<Region> = vec!["sq2".parse()?];
+        assert!(reader.query_regions(&header, &index, &regions).is_err());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,166 @@
+use std::{
+    io::{self, Read, Seek},
+    vec,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::{interval_set::OverlapIndex, Feature, Region};
+use noodles_csi::index::reference_sequence::bin::Chunk;
+
+use super::Reader;
+use crate::{Header, Record};
+
+enum State {
+    Seek,
+    Read(bgzf::VirtualPosition),
+    Done,
+}
+
+/// An iterator over records of a VCF reader that intersect any of the given regions.
+///
+/// Each record is read and returned at most once, even if it intersects more than one region.
+///
+/// This is created by calling [`Reader::query_regions`].
+pub struct RegionsQuery<'r, 'h, R>
+where
+    R: Read + Seek + 'r,
+{
+    reader: &'r mut Reader<bgzf::Reader<R>>,
+
+    chunks: vec::IntoIter<Chunk>,
+
+    regions: OverlapIndex<usize>,
+
+    state: State,
+    header: &'h Header,
+    line_buf: String,
+}
+
+impl<'r, 'h, R> RegionsQuery<'r, 'h, R>
+where
+    R: Read + Seek,
+{
+    pub(super) fn new(
+        reader: &'r mut Reader<bgzf::Reader<R>>,
+        chunks: Vec<Chunk>,
+        regions: &[Region],
+        header: &'h Header,
+    ) -> Self {
+        Self {
+            reader,
+
+            chunks: chunks.into_iter(),
+
+            regions: regions.iter().cloned().zip(0..).collect(),
+
+            state: State::Seek,
+            header,
+            line_buf: String::new(),
+        }
+    }
+
+    /// Returns an iterator that also yields the indices of the regions each record intersects.
+    ///
+    /// The indices are positions in the list of regions given to [`Reader::query_regions`], in
+    /// ascending order.
+    pub fn with_region_indices(self) -> WithRegionIndices<'r, 'h, R> {
+        WithRegionIndices { query: self }
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        self.line_buf.clear();
+
+        self.reader
+            .read_record(&mut self.line_buf)
+            .and_then(|n| match n {
+                0 => Ok(None),
+                _ => Record::try_from_str(&self.line_buf, self.header)
+                    .map(Some)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            })
+    }
+
+    fn next_with_region_indices(&mut self) -> Option<io::Result<(Record, Vec<usize>)>> {
+        loop {
+            match self.state {
+                State::Seek => {
+                    self.state = match self.chunks.next() {
+                        Some(chunk) => {
+                            if let Err(e) = self.reader.seek(chunk.start()) {
+                                return Some(Err(e));
+                            }
+
+                            State::Read(chunk.end())
+                        }
+                        None => State::Done,
+                    }
+                }
+                State::Read(chunk_end) => match self.read_record() {
+                    Ok(Some(record)) => {
+                        if self.reader.virtual_position() >= chunk_end {
+                            self.state = State::Seek;
+                        }
+
+                        match intersecting_region_indices(&self.regions, &record) {
+                            Ok(region_indices) if !region_indices.is_empty() => {
+                                return Some(Ok((record, region_indices)))
+                            }
+                            Ok(_) => {}
+                            Err(e) => return Some(Err(e)),
+                        }
+                    }
+                    Ok(None) => self.state = State::Seek,
+                    Err(e) => return Some(Err(e)),
+                },
+                State::Done => return None,
+            }
+        }
+    }
+}
+
+impl<'r, 'h, R> Iterator for RegionsQuery<'r, 'h, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_region_indices()
+            .map(|result| result.map(|(record, _)| record))
+    }
+}
+
+/// An iterator over records of a VCF reader and the indices of the regions they intersect.
+///
+/// This is created by calling [`RegionsQuery::with_region_indices`].
+pub struct WithRegionIndices<'r, 'h, R>
+where
+    R: Read + Seek + 'r,
+{
+    query: RegionsQuery<'r, 'h, R>,
+}
+
+impl<'r, 'h, R> Iterator for WithRegionIndices<'r, 'h, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<(Record, Vec<usize>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.query.next_with_region_indices()
+    }
+}
+
+pub(crate) fn intersecting_region_indices(
+    regions: &OverlapIndex<usize>,
+    record: &Record,
+) -> io::Result<Vec<usize>> {
+    let region = record
+        .region(&())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let mut indices: Vec<_> = regions.overlapping(&region).map(|(_, &i)| i).collect();
+    indices.sort_unstable();
+
+    Ok(indices)
+}
@@ -0,0 +1,371 @@
+//! VCF genotype-derived info field recomputation.
+//!
+//! A [`Recomputer`] walks the `GT` values of a record and sets the standard allele count info
+//! fields: `AC`, `AN`, `AF`, and `NS`. It can optionally set the call rate (`CR`), observed
+//! heterozygosity (`HET`), and Hardy-Weinberg equilibrium exact test p-values (`HWE`).
+//!
+//! Haploid and polyploid calls contribute all their alleles, and missing alleles (`.`) are not
+//! counted. Phasing is ignored.
+
+mod builder;
+mod counts;
+
+pub use self::{builder::Builder, counts::Counts};
+
+use std::io;
+
+use self::counts::hardy_weinberg_p_value;
+use crate::{
+    header::{
+        info::{key, Key},
+        record::value::{
+            map::{self, info::Type},
+            Map,
+        },
+        Number,
+    },
+    record::{info::field::Value, Info},
+    Header, Record,
+};
+
+/// The call rate info key (`CR`).
+///
+/// This is the fraction of samples with all alleles called.
+pub const CALL_RATE: &str = "CR";
+
+/// The observed heterozygosity info key (`HET`).
+///
+/// This is the fraction of called samples with at least two distinct alleles.
+pub const HETEROZYGOSITY: &str = "HET";
+
+/// The Hardy-Weinberg equilibrium exact test p-value info key (`HWE`).
+///
+/// There is one value per alternate allele, testing the alternate allele against all other
+/// alleles in called diploid samples. It is undefined when there are no called diploid samples.
+pub const HARDY_WEINBERG: &str = "HWE";
+
+const STANDARD_KEYS: [Key; 4] = [
+    key::ALLELE_COUNT,
+    key::TOTAL_ALLELE_COUNT,
+    key::ALLELE_FREQUENCIES,
+    key::SAMPLES_WITH_DATA_COUNT,
+];
+
+/// A genotype-derived info field recomputer.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Recomputer {
+    call_rate: bool,
+    heterozygosity: bool,
+    hardy_weinberg: bool,
+}
+
+impl Recomputer {
+    /// Returns a builder to create a recomputer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::recompute::Recomputer;
+    /// let builder = Recomputer::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Adds or updates the header info definitions of the recomputed fields.
+    ///
+    /// Standard keys use their reserved definitions. An existing definition is kept if its number
+    /// and type match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, header::info::key, recompute::Recomputer};
+    ///
+    /// let mut header = vcf::Header::default();
+    /// Recomputer::default().update_header(&mut header);
+    ///
+    /// assert!(header.infos().contains_key(&key::ALLELE_COUNT));
+    /// assert!(header.infos().contains_key(&key::TOTAL_ALLELE_COUNT));
+    /// assert!(header.infos().contains_key(&key::ALLELE_FREQUENCIES));
+    /// assert!(header.infos().contains_key(&key::SAMPLES_WITH_DATA_COUNT));
+    /// ```
+    pub fn update_header(&self, header: &mut Header) {
+        let infos = header.infos_mut();
+
+        let mut definitions: Vec<_> = STANDARD_KEYS
+            .iter()
+            .map(|key| (key.clone(), Map::<map::Info>::from(key)))
+            .collect();
+
+        if self.call_rate {
+            definitions.push((
+                other_key(CALL_RATE),
+                Map::<map::Info>::new(
+                    Number::Count(1),
+                    Type::Float,
+                    "Fraction of samples with all alleles called",
+                ),
+            ));
+        }
+
+        if self.heterozygosity {
+            definitions.push((
+                other_key(HETEROZYGOSITY),
+                Map::<map::Info>::new(
+                    Number::Count(1),
+                    Type::Float,
+                    "Fraction of called samples that are heterozygous",
+                ),
+            ));
+        }
+
+        if self.hardy_weinberg {
+            definitions.push((
+                other_key(HARDY_WEINBERG),
+                Map::<map::Info>::new(
+                    Number::A,
+                    Type::Float,
+                    "Hardy-Weinberg equilibrium exact test p-value",
+                ),
+            ));
+        }
+
+        for (key, map) in definitions {
+            let is_compatible = infos
+                .get(&key)
+                .map(|m| m.number() == map.number() && m.ty() == map.ty())
+                .unwrap_or(false);
+
+            if !is_compatible {
+                infos.insert(key, map);
+            }
+        }
+    }
+
+    /// Recomputes the info fields of a record from its `GT` values.
+    ///
+    /// Records without a `GT` field are left unchanged. Fields that are undefined for a record,
+    /// e.g., `HET` when no sample is called, are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, recompute::Recomputer};
+    ///
+    /// let mut record: vcf::Record = "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/1\t1/1\t./.".parse()?;
+    /// Recomputer::default().apply(&mut record)?;
+    ///
+    /// assert_eq!(
+    ///     record.to_string(),
+    ///     "sq0\t1\t.\tA\tC\t.\tPASS\tAC=3;AN=4;AF=0.75;NS=2\tGT\t0/1\t1/1\t./."
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn apply(&self, record: &mut Record) -> io::Result<()> {
+        let counts = match Counts::from_record(record)? {
+            Some(counts) => counts,
+            None => return Ok(()),
+        };
+
+        let allele_number = try_into_i32(counts.allele_number)?;
+
+        let allele_counts = counts.allele_counts[1..]
+            .iter()
+            .map(|&n| try_into_i32(n).map(Some))
+            .collect::<io::Result<_>>()?;
+
+        let allele_frequencies = counts.allele_counts[1..]
+            .iter()
+            .map(|&n| ratio(n, counts.allele_number))
+            .collect();
+
+        let samples_with_data_count = try_into_i32(counts.samples_with_data_count)?;
+
+        let info = record.info_mut();
+
+        info.insert(key::ALLELE_COUNT, Some(Value::IntegerArray(allele_counts)));
+        info.insert(key::TOTAL_ALLELE_COUNT, Some(Value::Integer(allele_number)));
+        info.insert(
+            key::ALLELE_FREQUENCIES,
+            Some(Value::FloatArray(allele_frequencies)),
+        );
+        info.insert(
+            key::SAMPLES_WITH_DATA_COUNT,
+            Some(Value::Integer(samples_with_data_count)),
+        );
+
+        if self.call_rate {
+            let value = ratio(counts.called_sample_count, counts.sample_count);
+            set_or_remove(info, other_key(CALL_RATE), value.map(Value::Float));
+        }
+
+        if self.heterozygosity {
+            let value = ratio(counts.heterozygous_sample_count, counts.called_sample_count);
+            set_or_remove(info, other_key(HETEROZYGOSITY), value.map(Value::Float));
+        }
+
+        if self.hardy_weinberg {
+            // Every alternate allele has the same number of called diploid samples.
+            let diploid_sample_count = counts
+                .diploid_genotype_counts
+                .first()
+                .map(|genotype_counts| genotype_counts.iter().sum())
+                .unwrap_or(0);
+
+            let value = if diploid_sample_count > 0 {
+                let p_values = counts
+                    .diploid_genotype_counts
+                    .iter()
+                    .map(|&[hom_1, het, hom_2]| {
+                        Some(hardy_weinberg_p_value(hom_1, het, hom_2) as f32)
+                    })
+                    .collect();
+
+                Some(Value::FloatArray(p_values))
+            } else {
+                None
+            };
+
+            set_or_remove(info, other_key(HARDY_WEINBERG), value);
+        }
+
+        Ok(())
+    }
+}
+
+fn other_key(s: &str) -> Key {
+    s.parse().expect("invalid info key")
+}
+
+fn try_into_i32(n: usize) -> io::Result<i32> {
+    i32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn ratio(n: usize, d: usize) -> Option<f32> {
+    if d == 0 {
+        None
+    } else {
+        Some(n as f32 / d as f32)
+    }
+}
+
+fn set_or_remove(info: &mut Info, key: Key, value: Option<Value>) {
+    match value {
+        Some(value) => {
+            info.insert(key, Some(value));
+        }
+        None => {
+            info.as_mut().shift_remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_header() {
+        let mut header = Header::builder()
+            .add_info(
+                key::ALLELE_COUNT,
+                Map::<map::Info>::new(Number::A, Type::Integer, "Alternate allele count"),
+            )
+            .add_info(
+                key::TOTAL_ALLELE_COUNT,
+                Map::<map::Info>::new(Number::Count(1), Type::Float, "Allele number"),
+            )
+            .build();
+
+        let recomputer = Recomputer::builder()
+            .set_call_rate(true)
+            .set_heterozygosity(true)
+            .set_hardy_weinberg(true)
+            .build();
+
+        recomputer.update_header(&mut header);
+
+        let infos = header.infos();
+
+        assert_eq!(
+            infos.keys().map(|key| key.as_ref()).collect::<Vec<_>>(),
+            ["AC", "AN", "AF", "NS", "CR", "HET", "HWE"]
+        );
+
+        assert_eq!(
+            infos[&key::ALLELE_COUNT].description(),
+            "Alternate allele count"
+        );
+        assert_eq!(infos[&key::TOTAL_ALLELE_COUNT].ty(), Type::Integer);
+        assert_eq!(infos[&other_key(HARDY_WEINBERG)].number(), Number::A);
+    }
+
+    #[test]
+    fn test_apply() -> Result<(), Box<dyn std::error::Error>> {
+        let recomputer = Recomputer::builder()
+            .set_call_rate(true)
+            .set_heterozygosity(true)
+            .set_hardy_weinberg(true)
+            .build();
+
+        let mut record: Record =
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=8;HET=0.5\tGT\t0/1\t1|1\t./.\t2\t0/1".parse()?;
+        recomputer.apply(&mut record)?;
+
+        let info = record.info();
+        assert_eq!(
+            info.get(&key::ALLELE_COUNT),
+            Some(Some(&Value::IntegerArray(vec![Some(4), Some(1)])))
+        );
+        assert_eq!(
+            info.get(&key::TOTAL_ALLELE_COUNT),
+            Some(Some(&Value::Integer(7)))
+        );
+        assert_eq!(
+            info.get(&key::ALLELE_FREQUENCIES),
+            Some(Some(&Value::FloatArray(vec![
+                Some(4.0 / 7.0),
+                Some(1.0 / 7.0)
+            ])))
+        );
+        assert_eq!(
+            info.get(&key::SAMPLES_WITH_DATA_COUNT),
+            Some(Some(&Value::Integer(4)))
+        );
+        assert_eq!(
+            info.get(&other_key(CALL_RATE)),
+            Some(Some(&Value::Float(0.8)))
+        );
+        assert_eq!(
+            info.get(&other_key(HETEROZYGOSITY)),
+            Some(Some(&Value::Float(0.5)))
+        );
+
+        let p_value = hardy_weinberg_p_value(0, 2, 1) as f32;
+        assert_eq!(
+            info.get(&other_key(HARDY_WEINBERG)),
+            Some(Some(&Value::FloatArray(vec![Some(p_value), Some(1.0)])))
+        );
+
+        let mut record: Record = "sq0\t1\t.\tA\tC\t.\tPASS\tHET=0.5;HWE=0.5\tGT\t./.".parse()?;
+        recomputer.apply(&mut record)?;
+        assert_eq!(
+            record.to_string(),
+            "sq0\t1\t.\tA\tC\t.\tPASS\tAC=0;AN=0;AF=.;NS=0;CR=0\tGT\t./."
+        );
+
+        let mut record: Record = "sq0\t1\t.\tA\tC\t.\tPASS\tHWE=0.5\tGT\t1\t0".parse()?;
+        recomputer.apply(&mut record)?;
+        assert_eq!(
+            record.to_string(),
+            "sq0\t1\t.\tA\tC\t.\tPASS\tAC=1;AN=2;AF=0.5;NS=2;CR=1;HET=0\tGT\t1\t0"
+        );
+
+        let mut record: Record = "sq0\t1\t.\tA\tC\t.\tPASS\tAC=8".parse()?;
+        recomputer.apply(&mut record)?;
+        assert_eq!(record.to_string(), "sq0\t1\t.\tA\tC\t.\tPASS\tAC=8");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use super::Recomputer;
+
+/// A genotype-derived info field recomputer builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    call_rate: bool,
+    heterozygosity: bool,
+    hardy_weinberg: bool,
+}
+
+impl Builder {
+    /// Sets whether to compute the call rate (`CR`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::recompute::Recomputer;
+    /// let recomputer = Recomputer::builder().set_call_rate(true).build();
+    /// ```
+    pub fn set_call_rate(mut self, call_rate: bool) -> Self {
+        self.call_rate = call_rate;
+        self
+    }
+
+    /// Sets whether to compute the observed heterozygosity (`HET`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::recompute::Recomputer;
+    /// let recomputer = Recomputer::builder().set_heterozygosity(true).build();
+    /// ```
+    pub fn set_heterozygosity(mut self, heterozygosity: bool) -> Self {
+        self.heterozygosity = heterozygosity;
+        self
+    }
+
+    /// Sets whether to compute Hardy-Weinberg equilibrium exact test p-values (`HWE`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::recompute::Recomputer;
+    /// let recomputer = Recomputer::builder().set_hardy_weinberg(true).build();
+    /// ```
+    pub fn set_hardy_weinberg(mut self, hardy_weinberg: bool) -> Self {
+        self.hardy_weinberg = hardy_weinberg;
+        self
+    }
+
+    /// Builds a recomputer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::recompute::Recomputer;
+    /// let recomputer = Recomputer::builder().build();
+    /// ```
+    pub fn build(self) -> Recomputer {
+        Recomputer {
+            call_rate: self.call_rate,
+            heterozygosity: self.heterozygosity,
+            hardy_weinberg: self.hardy_weinberg,
+        }
+    }
+}
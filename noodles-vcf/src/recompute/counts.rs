@@ -0,0 +1,313 @@
+use std::io;
+
+use crate::{
+    header::{format::key, info::key as info_key},
+    record::{info::field::Value, Info},
+    Header, Record,
+};
+
+/// Genotype-derived allele and sample counts of a record.
+///
+/// Counts are accumulated one sample at a time from the allele positions of its genotype. This
+/// can be used to recompute allele counts from genotypes that are not stored in a VCF record,
+/// e.g., BCF encoded genotypes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Counts {
+    pub(crate) sample_count: usize,
+    /// Allele counts, indexed by allele position (0 is the reference allele).
+    pub(crate) allele_counts: Vec<usize>,
+    /// The total number of called alleles.
+    pub(crate) allele_number: usize,
+    /// The number of samples with at least one called allele.
+    pub(crate) samples_with_data_count: usize,
+    /// The number of samples with all alleles called.
+    pub(crate) called_sample_count: usize,
+    /// The number of called samples with at least two distinct alleles.
+    pub(crate) heterozygous_sample_count: usize,
+    /// For each alternate allele, the number of called diploid samples with 0, 1, or 2 copies.
+    pub(crate) diploid_genotype_counts: Vec<[usize; 3]>,
+}
+
+impl Counts {
+    /// Creates empty counts for a record with the given number of alternate alleles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::recompute::Counts;
+    /// let counts = Counts::new(2);
+    /// assert_eq!(counts.allele_counts(), [0, 0, 0]);
+    /// ```
+    pub fn new(alternate_allele_count: usize) -> Self {
+        Self {
+            allele_counts: vec![0; alternate_allele_count + 1],
+            diploid_genotype_counts: vec![[0; 3]; alternate_allele_count],
+            ..Default::default()
+        }
+    }
+
+    /// Counts the alleles of the record's `GT` values.
+    ///
+    /// This returns `None` if the record has no `GT` field.
+    pub(crate) fn from_record(record: &Record) -> io::Result<Option<Self>> {
+        if !record.genotypes().keys().contains(&key::GENOTYPE) {
+            return Ok(None);
+        }
+
+        let genotypes = record
+            .genotypes()
+            .genotypes()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut counts = Self::new(record.alternate_bases().len());
+        let mut positions = Vec::new();
+
+        for genotype in genotypes.iter() {
+            positions.clear();
+
+            if let Some(genotype) = genotype {
+                positions.extend(genotype.iter().map(|allele| allele.position()));
+            }
+
+            counts.add_sample(&positions)?;
+        }
+
+        Ok(Some(counts))
+    }
+
+    /// Adds a sample given the allele positions of its genotype.
+    ///
+    /// A missing allele is `None`. A sample with a missing genotype has no allele positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::recompute::Counts;
+    ///
+    /// let mut counts = Counts::new(1);
+    /// counts.add_sample(&[Some(0), Some(1)])?;
+    /// counts.add_sample(&[Some(1), None])?;
+    ///
+    /// assert_eq!(counts.allele_counts(), [1, 2]);
+    /// assert_eq!(counts.allele_number(), 3);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn add_sample(&mut self, positions: &[Option<usize>]) -> io::Result<()> {
+        self.sample_count += 1;
+
+        let mut has_data = false;
+        let mut is_called = !positions.is_empty();
+
+        for position in positions {
+            match position {
+                Some(i) => {
+                    let count = self.allele_counts.get_mut(*i).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("invalid allele index: {i}"),
+                        )
+                    })?;
+
+                    *count += 1;
+                    self.allele_number += 1;
+                    has_data = true;
+                }
+                None => is_called = false,
+            }
+        }
+
+        if has_data {
+            self.samples_with_data_count += 1;
+        }
+
+        if !is_called {
+            return Ok(());
+        }
+
+        self.called_sample_count += 1;
+
+        if positions.windows(2).any(|w| w[0] != w[1]) {
+            self.heterozygous_sample_count += 1;
+        }
+
+        if positions.len() == 2 {
+            for (j, genotype_counts) in self.diploid_genotype_counts.iter_mut().enumerate() {
+                let copies = positions.iter().filter(|p| **p == Some(j + 1)).count();
+                genotype_counts[copies] += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the allele counts, indexed by allele position.
+    ///
+    /// The first count is of the reference allele.
+    pub fn allele_counts(&self) -> &[usize] {
+        &self.allele_counts
+    }
+
+    /// Returns the total number of called alleles.
+    pub fn allele_number(&self) -> usize {
+        self.allele_number
+    }
+
+    /// Sets the `AC` and `AN` info fields when they are defined in the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{info::key, record::value::{map::Info, Map}},
+    ///     record::info::field::Value,
+    ///     recompute::Counts,
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_info(key::TOTAL_ALLELE_COUNT, Map::<Info>::from(&key::TOTAL_ALLELE_COUNT))
+    ///     .build();
+    ///
+    /// let mut counts = Counts::new(1);
+    /// counts.add_sample(&[Some(0), Some(1)])?;
+    ///
+    /// let mut info = vcf::record::Info::default();
+    /// counts.update_allele_count_fields(&header, &mut info)?;
+    ///
+    /// assert_eq!(info.get(&key::TOTAL_ALLELE_COUNT), Some(Some(&Value::Integer(2))));
+    /// assert!(info.get(&key::ALLELE_COUNT).is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn update_allele_count_fields(&self, header: &Header, info: &mut Info) -> io::Result<()> {
+        if header.infos().contains_key(&info_key::ALLELE_COUNT) {
+            let allele_counts = self.allele_counts[1..]
+                .iter()
+                .map(|&n| try_into_i32(n).map(Some))
+                .collect::<io::Result<_>>()?;
+
+            info.insert(
+                info_key::ALLELE_COUNT,
+                Some(Value::IntegerArray(allele_counts)),
+            );
+        }
+
+        if header.infos().contains_key(&info_key::TOTAL_ALLELE_COUNT) {
+            let allele_number = try_into_i32(self.allele_number)?;
+            info.insert(
+                info_key::TOTAL_ALLELE_COUNT,
+                Some(Value::Integer(allele_number)),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn try_into_i32(n: usize) -> io::Result<i32> {
+    i32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Calculates the Hardy-Weinberg equilibrium exact test p-value of a biallelic site.
+///
+/// This uses the algorithm described in Wigginton, J. E., Cutler, D. J., & Abecasis, G. R.
+/// (2005). A note on exact tests of Hardy-Weinberg equilibrium. _American Journal of Human
+/// Genetics_, 76(5), 887–893.
+pub(crate) fn hardy_weinberg_p_value(hom_1: usize, het: usize, hom_2: usize) -> f64 {
+    let n = hom_1 + het + hom_2;
+
+    if n == 0 {
+        return 1.0;
+    }
+
+    let hom_r = hom_1.min(hom_2);
+    let rare = 2 * hom_r + het;
+
+    let mut probs = vec![0.0; rare + 1];
+
+    let mut mid = rare * (2 * n - rare) / (2 * n);
+
+    if mid % 2 != rare % 2 {
+        mid += 1;
+    }
+
+    probs[mid] = 1.0;
+    let mut sum = 1.0;
+
+    let (mut curr_het, mut curr_hom_r, mut curr_hom_c) =
+        (mid, (rare - mid) / 2, n - mid - (rare - mid) / 2);
+
+    while curr_het >= 2 {
+        let p = probs[curr_het] * (curr_het * (curr_het - 1)) as f64
+            / (4 * (curr_hom_r + 1) * (curr_hom_c + 1)) as f64;
+
+        probs[curr_het - 2] = p;
+        sum += p;
+
+        curr_het -= 2;
+        curr_hom_r += 1;
+        curr_hom_c += 1;
+    }
+
+    let (mut curr_het, mut curr_hom_r, mut curr_hom_c) =
+        (mid, (rare - mid) / 2, n - mid - (rare - mid) / 2);
+
+    while curr_het + 2 <= rare {
+        let p = probs[curr_het] * (4 * curr_hom_r * curr_hom_c) as f64
+            / ((curr_het + 2) * (curr_het + 1)) as f64;
+
+        probs[curr_het + 2] = p;
+        sum += p;
+
+        curr_het += 2;
+        curr_hom_r -= 1;
+        curr_hom_c -= 1;
+    }
+
+    let observed_prob = probs[het];
+
+    let p_value: f64 = probs.iter().filter(|&&p| p <= observed_prob).sum::<f64>() / sum;
+
+    p_value.min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_record() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record =
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\t.\tGT\t0/1\t1|1\t./.\t2\t0/.\t1/1/2\t.".parse()?;
+
+        let actual = Counts::from_record(&record)?;
+        let expected = Counts {
+            sample_count: 7,
+            allele_counts: vec![2, 5, 2],
+            allele_number: 9,
+            samples_with_data_count: 5,
+            called_sample_count: 4,
+            heterozygous_sample_count: 2,
+            diploid_genotype_counts: vec![[0, 1, 1], [2, 0, 0]],
+        };
+
+        assert_eq!(actual, Some(expected));
+
+        let record: Record = "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGQ\t13".parse()?;
+        assert!(Counts::from_record(&record)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hardy_weinberg_p_value() {
+        fn assert_approx_eq(actual: f64, expected: f64) {
+            assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+        }
+
+        assert_approx_eq(hardy_weinberg_p_value(0, 0, 0), 1.0);
+        assert_approx_eq(hardy_weinberg_p_value(25, 50, 25), 1.0);
+        assert_approx_eq(hardy_weinberg_p_value(1, 0, 1), 1.0 / 3.0);
+    }
+}
@@ -22,6 +22,8 @@ pub use self::{
 
 use std::{error, fmt, num, str::FromStr};
 
+use noodles_core::{self as core, region::Interval, Feature};
+
 use super::Header;
 
 pub(crate) const MISSING_FIELD: &str = ".";
@@ -704,6 +706,30 @@ impl Record {
     }
 }
 
+impl Feature for Record {
+    fn reference_sequence_name<'a>(&'a self, _: &'a ()) -> core::Result<&'a str> {
+        match self.chromosome() {
+            Chromosome::Name(name) | Chromosome::Symbol(name) => Ok(name),
+        }
+    }
+
+    fn interval(&self) -> core::Result<Interval> {
+        fn try_into_position(position: Position) -> core::Result<core::Position> {
+            core::Position::try_from(usize::from(position))
+                .map_err(|e| core::Error::new(core::error::Kind::TryFrom, e))
+        }
+
+        let start = try_into_position(self.position())?;
+
+        let end = self
+            .end()
+            .map_err(|e| core::Error::new(core::error::Kind::Other, e))
+            .and_then(try_into_position)?;
+
+        Ok(Interval::from(start..=end))
+    }
+}
+
 impl fmt::Display for Record {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -760,6 +786,27 @@ impl FromStr for Record {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_region() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(8))
+            .set_reference_bases("ACGTACG".parse()?)
+            .build()?;
+
+        assert_eq!(record.region(&())?, "sq0:8-14".parse()?);
+
+        let record = Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(0))
+            .set_reference_bases("A".parse()?)
+            .build()?;
+
+        assert!(record.interval().is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_end() -> Result<(), Box<dyn std::error::Error>> {
         use crate::header::info::key;
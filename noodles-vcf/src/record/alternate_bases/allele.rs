@@ -1,8 +1,9 @@
 //! VCF record alternate bases allele and symbol.
 
+pub mod breakend;
 pub mod symbol;
 
-pub use self::symbol::Symbol;
+pub use self::{breakend::Breakend, symbol::Symbol};
 
 use std::{
     error,
@@ -20,7 +21,7 @@ pub enum Allele {
     /// A symbolic allele (e.g., `<DEL>`, `<CN:0>`, etc.).
     Symbol(Symbol),
     /// A breakend (e.g., `]sq0:5]A`, `G.`, etc.).
-    Breakend(String),
+    Breakend(Breakend),
     /// An overlapping deletion, i.e., a missing allele (`*`).
     OverlappingDeletion,
 }
@@ -36,7 +37,7 @@ impl fmt::Display for Allele {
                 Ok(())
             }
             Self::Symbol(symbol) => write!(f, "<{symbol}>"),
-            Self::Breakend(breakend) => f.write_str(breakend),
+            Self::Breakend(breakend) => write!(f, "{breakend}"),
             Self::OverlappingDeletion => f.write_str("*"),
         }
     }
@@ -51,6 +52,8 @@ pub enum ParseError {
     InvalidSymbol(symbol::ParseError),
     /// A base is invalid.
     InvalidBase(base::TryFromCharError),
+    /// The breakend is invalid.
+    InvalidBreakend(breakend::ParseError),
 }
 
 impl error::Error for ParseError {
@@ -59,6 +62,7 @@ impl error::Error for ParseError {
             Self::Empty => None,
             Self::InvalidSymbol(e) => Some(e),
             Self::InvalidBase(e) => Some(e),
+            Self::InvalidBreakend(e) => Some(e),
         }
     }
 }
@@ -69,6 +73,7 @@ impl fmt::Display for ParseError {
             Self::Empty => f.write_str("empty input"),
             Self::InvalidSymbol(_) => f.write_str("invalid symbol"),
             Self::InvalidBase(_) => f.write_str("invalid base"),
+            Self::InvalidBreakend(_) => f.write_str("invalid breakend"),
         }
     }
 }
@@ -87,7 +92,9 @@ impl FromStr for Allele {
                         .map(Self::Symbol)
                         .map_err(ParseError::InvalidSymbol)
                 } else if is_breakend(s) {
-                    Ok(Self::Breakend(s.into()))
+                    s.parse()
+                        .map(Self::Breakend)
+                        .map_err(ParseError::InvalidBreakend)
                 } else {
                     s.chars()
                         .map(|c| c.to_ascii_uppercase())
@@ -108,6 +115,7 @@ fn is_breakend(s: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::record::{Chromosome, Position};
 
     #[test]
     fn test_fmt() {
@@ -128,22 +136,22 @@ mod tests {
         let allele = Allele::Symbol(Symbol::NonstructuralVariant(String::from("CN:0")));
         assert_eq!(allele.to_string(), "<CN:0>");
 
-        let allele = Allele::Breakend(String::from("]sq0:5]A"));
+        let allele = Allele::Breakend("]sq0:5]A".parse().unwrap());
         assert_eq!(allele.to_string(), "]sq0:5]A");
 
-        let allele = Allele::Breakend(String::from("C[sq1:13["));
+        let allele = Allele::Breakend("C[sq1:13[".parse().unwrap());
         assert_eq!(allele.to_string(), "C[sq1:13[");
 
-        let allele = Allele::Breakend(String::from("G."));
+        let allele = Allele::Breakend("G.".parse().unwrap());
         assert_eq!(allele.to_string(), "G.");
 
-        let allele = Allele::Breakend(String::from("CT."));
+        let allele = Allele::Breakend("CT.".parse().unwrap());
         assert_eq!(allele.to_string(), "CT.");
 
-        let allele = Allele::Breakend(String::from(".A"));
+        let allele = Allele::Breakend(".A".parse().unwrap());
         assert_eq!(allele.to_string(), ".A");
 
-        let allele = Allele::Breakend(String::from(".GC"));
+        let allele = Allele::Breakend(".GC".parse().unwrap());
         assert_eq!(allele.to_string(), ".GC");
     }
 
@@ -179,32 +187,64 @@ mod tests {
 
         assert_eq!(
             "]sq0:5]A".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from("]sq0:5]A")))
+            Ok(Allele::Breakend(Breakend::new(
+                String::from("A"),
+                breakend::Orientation::Right,
+                Some(breakend::Mate::new(
+                    Chromosome::Name(String::from("sq0")),
+                    Position::from(5),
+                    breakend::Orientation::Left,
+                )),
+            )))
         );
 
         assert_eq!(
             "C[sq1:13[".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from("C[sq1:13[")))
+            Ok(Allele::Breakend(Breakend::new(
+                String::from("C"),
+                breakend::Orientation::Left,
+                Some(breakend::Mate::new(
+                    Chromosome::Name(String::from("sq1")),
+                    Position::from(13),
+                    breakend::Orientation::Right,
+                )),
+            )))
         );
 
         assert_eq!(
             "G.".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from("G.")))
+            Ok(Allele::Breakend(Breakend::new(
+                String::from("G"),
+                breakend::Orientation::Left,
+                None,
+            )))
         );
 
         assert_eq!(
             "CT.".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from("CT.")))
+            Ok(Allele::Breakend(Breakend::new(
+                String::from("CT"),
+                breakend::Orientation::Left,
+                None,
+            )))
         );
 
         assert_eq!(
             ".A".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from(".A")))
+            Ok(Allele::Breakend(Breakend::new(
+                String::from("A"),
+                breakend::Orientation::Right,
+                None,
+            )))
         );
 
         assert_eq!(
             ".GC".parse::<Allele>(),
-            Ok(Allele::Breakend(String::from(".GC")))
+            Ok(Allele::Breakend(Breakend::new(
+                String::from("GC"),
+                breakend::Orientation::Right,
+                None,
+            )))
         );
 
         assert_eq!("".parse::<Allele>(), Err(ParseError::Empty));
@@ -216,5 +256,9 @@ mod tests {
             "Z".parse::<Allele>(),
             Err(ParseError::InvalidBase(_))
         ));
+        assert!(matches!(
+            "G[sq1:13]".parse::<Allele>(),
+            Err(ParseError::InvalidBreakend(_))
+        ));
     }
 }
@@ -0,0 +1,503 @@
+//! VCF record alternate bases allele breakend.
+
+use std::{error, fmt, num, str::FromStr};
+
+use crate::record::{chromosome, Chromosome, Position};
+
+const SINGLE_BREAKEND: char = '.';
+const MATE_POSITION_DELIMITER: char = ':';
+
+/// The side of a breakend position on which the retained sequence lies.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Orientation {
+    /// The sequence extends to the left of the position.
+    Left,
+    /// The sequence extends to the right of the position.
+    Right,
+}
+
+/// A breakend mate, i.e., the remote end of a novel adjacency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mate {
+    chromosome: Chromosome,
+    position: Position,
+    orientation: Orientation,
+}
+
+impl Mate {
+    /// Creates a breakend mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Mate, Orientation},
+    ///     Chromosome, Position,
+    /// };
+    ///
+    /// let mate = Mate::new(
+    ///     Chromosome::Name(String::from("sq1")),
+    ///     Position::from(13),
+    ///     Orientation::Right,
+    /// );
+    /// ```
+    pub fn new(chromosome: Chromosome, position: Position, orientation: Orientation) -> Self {
+        Self {
+            chromosome,
+            position,
+            orientation,
+        }
+    }
+
+    /// Returns the chromosome of the mate.
+    ///
+    /// This is a symbol if the mate is on an assembly contig (e.g., `<ctg0>`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{alternate_bases::allele::Breakend, Chromosome};
+    ///
+    /// let breakend: Breakend = "G[sq1:13[".parse()?;
+    /// let mate = breakend.mate().unwrap();
+    /// assert_eq!(mate.chromosome(), &Chromosome::Name(String::from("sq1")));
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    /// Returns the position of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{alternate_bases::allele::Breakend, Position};
+    ///
+    /// let breakend: Breakend = "G[sq1:13[".parse()?;
+    /// let mate = breakend.mate().unwrap();
+    /// assert_eq!(mate.position(), Position::from(13));
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the side of the mate position on which the joined sequence lies.
+    ///
+    /// An opening bracket (`[`) joins the sequence to the right of the mate position, and a
+    /// closing bracket (`]`), the sequence to the left of the mate position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::{breakend::Orientation, Breakend};
+    ///
+    /// let breakend: Breakend = "G[sq1:13[".parse()?;
+    /// let mate = breakend.mate().unwrap();
+    /// assert_eq!(mate.orientation(), Orientation::Right);
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+}
+
+/// A VCF alternate bases allele breakend (e.g., `G[sq1:13[`, `]sq0:5]A`, `G.`, etc.).
+///
+/// A breakend describes a novel adjacency between the record position and a mate position. A
+/// single breakend (e.g., `G.`) has no mate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Breakend {
+    bases: String,
+    orientation: Orientation,
+    mate: Option<Mate>,
+}
+
+impl Breakend {
+    /// Creates a breakend.
+    ///
+    /// `bases` are the replacement bases, i.e., the reference base and any inserted sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::{breakend::Orientation, Breakend};
+    ///
+    /// let breakend = Breakend::new(String::from("G"), Orientation::Left, None);
+    /// assert_eq!(breakend.to_string(), "G.");
+    /// ```
+    pub fn new(bases: String, orientation: Orientation, mate: Option<Mate>) -> Self {
+        Self {
+            bases,
+            orientation,
+            mate,
+        }
+    }
+
+    /// Returns the replacement bases.
+    ///
+    /// This includes the reference base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::Breakend;
+    ///
+    /// let breakend: Breakend = "GTC[sq1:13[".parse()?;
+    /// assert_eq!(breakend.bases(), "GTC");
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn bases(&self) -> &str {
+        &self.bases
+    }
+
+    /// Returns the sequence inserted between the record position and the mate.
+    ///
+    /// This is the replacement bases without the reference base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::Breakend;
+    ///
+    /// let breakend: Breakend = "GTC[sq1:13[".parse()?;
+    /// assert_eq!(breakend.inserted_sequence(), "TC");
+    ///
+    /// let breakend: Breakend = "]sq0:5]TCG".parse()?;
+    /// assert_eq!(breakend.inserted_sequence(), "TC");
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn inserted_sequence(&self) -> &str {
+        match self.orientation {
+            Orientation::Left => self.bases.get(1..).unwrap_or_default(),
+            Orientation::Right => self
+                .bases
+                .get(..self.bases.len().saturating_sub(1))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the side of the record position on which the retained sequence lies.
+    ///
+    /// The adjacency is joined after the replacement bases (e.g., `G[sq1:13[`) if the retained
+    /// sequence is on the left and before the replacement bases (e.g., `]sq0:5]A`) if it is on
+    /// the right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::{breakend::Orientation, Breakend};
+    ///
+    /// let breakend: Breakend = "G[sq1:13[".parse()?;
+    /// assert_eq!(breakend.orientation(), Orientation::Left);
+    ///
+    /// let breakend: Breakend = ".A".parse()?;
+    /// assert_eq!(breakend.orientation(), Orientation::Right);
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Returns the mate.
+    ///
+    /// This is `None` for a single breakend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::Breakend;
+    ///
+    /// let breakend: Breakend = "G[sq1:13[".parse()?;
+    /// assert!(breakend.mate().is_some());
+    ///
+    /// let breakend: Breakend = "G.".parse()?;
+    /// assert!(breakend.mate().is_none());
+    /// # Ok::<_, noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn mate(&self) -> Option<&Mate> {
+        self.mate.as_ref()
+    }
+}
+
+impl fmt::Display for Breakend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.mate, self.orientation) {
+            (None, Orientation::Left) => write!(f, "{}{SINGLE_BREAKEND}", self.bases),
+            (None, Orientation::Right) => write!(f, "{SINGLE_BREAKEND}{}", self.bases),
+            (Some(mate), orientation) => {
+                let bracket = match mate.orientation {
+                    Orientation::Left => ']',
+                    Orientation::Right => '[',
+                };
+
+                if orientation == Orientation::Left {
+                    f.write_str(&self.bases)?;
+                }
+
+                write!(
+                    f,
+                    "{bracket}{}{MATE_POSITION_DELIMITER}{}{bracket}",
+                    mate.chromosome, mate.position
+                )?;
+
+                if orientation == Orientation::Right {
+                    f.write_str(&self.bases)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An error returned when a raw VCF record alternate bases allele breakend fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+    /// The replacement bases are missing.
+    MissingBases,
+    /// The mate position is missing.
+    MissingMatePosition,
+    /// The mate chromosome is invalid.
+    InvalidMateChromosome(chromosome::ParseError),
+    /// The mate position is invalid.
+    InvalidMatePosition(num::ParseIntError),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidMateChromosome(e) => Some(e),
+            Self::InvalidMatePosition(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+            Self::MissingBases => f.write_str("missing bases"),
+            Self::MissingMatePosition => f.write_str("missing mate position"),
+            Self::InvalidMateChromosome(_) => f.write_str("invalid mate chromosome"),
+            Self::InvalidMatePosition(_) => f.write_str("invalid mate position"),
+        }
+    }
+}
+
+impl FromStr for Breakend {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let i = match s.find(['[', ']']) {
+            Some(i) => i,
+            None => return parse_single_breakend(s),
+        };
+
+        let bracket = s[i..].chars().next().ok_or(ParseError::Invalid)?;
+
+        let mate_orientation = if bracket == '[' {
+            Orientation::Right
+        } else {
+            Orientation::Left
+        };
+
+        let (bases, raw_mate, orientation) = if i == 0 {
+            // `]p]t` or `[p[t`
+            let t = &s[1..];
+            let j = t.find(bracket).ok_or(ParseError::Invalid)?;
+            (&t[j + 1..], &t[..j], Orientation::Right)
+        } else {
+            // `t[p[` or `t]p]`
+            let t = s[i + 1..]
+                .strip_suffix(bracket)
+                .ok_or(ParseError::Invalid)?;
+            (&s[..i], t, Orientation::Left)
+        };
+
+        if bases.is_empty() {
+            return Err(ParseError::MissingBases);
+        } else if !is_valid_bases(bases) {
+            return Err(ParseError::Invalid);
+        }
+
+        let mate = parse_mate(raw_mate, mate_orientation)?;
+
+        Ok(Self::new(bases.into(), orientation, Some(mate)))
+    }
+}
+
+fn parse_single_breakend(s: &str) -> Result<Breakend, ParseError> {
+    let (bases, orientation) = if let Some(t) = s.strip_suffix(SINGLE_BREAKEND) {
+        (t, Orientation::Left)
+    } else if let Some(t) = s.strip_prefix(SINGLE_BREAKEND) {
+        (t, Orientation::Right)
+    } else {
+        return Err(ParseError::Invalid);
+    };
+
+    if bases.is_empty() {
+        Err(ParseError::MissingBases)
+    } else if is_valid_bases(bases) {
+        Ok(Breakend::new(bases.into(), orientation, None))
+    } else {
+        Err(ParseError::Invalid)
+    }
+}
+
+fn parse_mate(s: &str, orientation: Orientation) -> Result<Mate, ParseError> {
+    let (raw_chromosome, raw_position) = s
+        .rsplit_once(MATE_POSITION_DELIMITER)
+        .ok_or(ParseError::MissingMatePosition)?;
+
+    let chromosome = raw_chromosome
+        .parse()
+        .map_err(ParseError::InvalidMateChromosome)?;
+
+    let position = raw_position
+        .parse::<usize>()
+        .map(Position::from)
+        .map_err(ParseError::InvalidMatePosition)?;
+
+    Ok(Mate::new(chromosome, position, orientation))
+}
+
+fn is_valid_bases(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let breakend = Breakend::new(
+            String::from("G"),
+            Orientation::Left,
+            Some(Mate::new(
+                Chromosome::Name(String::from("sq1")),
+                Position::from(13),
+                Orientation::Right,
+            )),
+        );
+        assert_eq!(breakend.to_string(), "G[sq1:13[");
+
+        let breakend = Breakend::new(
+            String::from("A"),
+            Orientation::Right,
+            Some(Mate::new(
+                Chromosome::Symbol(String::from("ctg0")),
+                Position::from(5),
+                Orientation::Left,
+            )),
+        );
+        assert_eq!(breakend.to_string(), "]<ctg0>:5]A");
+
+        let breakend = Breakend::new(String::from("CT"), Orientation::Left, None);
+        assert_eq!(breakend.to_string(), "CT.");
+
+        let breakend = Breakend::new(String::from("GC"), Orientation::Right, None);
+        assert_eq!(breakend.to_string(), ".GC");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let mate = |name: &str, position, orientation| {
+            Some(Mate::new(
+                Chromosome::Name(name.into()),
+                Position::from(position),
+                orientation,
+            ))
+        };
+
+        assert_eq!(
+            "G[sq1:13[".parse(),
+            Ok(Breakend::new(
+                String::from("G"),
+                Orientation::Left,
+                mate("sq1", 13, Orientation::Right)
+            ))
+        );
+
+        assert_eq!(
+            "GT]sq1:13]".parse(),
+            Ok(Breakend::new(
+                String::from("GT"),
+                Orientation::Left,
+                mate("sq1", 13, Orientation::Left)
+            ))
+        );
+
+        assert_eq!(
+            "]sq0:5]A".parse(),
+            Ok(Breakend::new(
+                String::from("A"),
+                Orientation::Right,
+                mate("sq0", 5, Orientation::Left)
+            ))
+        );
+
+        assert_eq!(
+            "[sq0:5[A".parse(),
+            Ok(Breakend::new(
+                String::from("A"),
+                Orientation::Right,
+                mate("sq0", 5, Orientation::Right)
+            ))
+        );
+
+        assert_eq!(
+            "C[<ctg0>:1[".parse(),
+            Ok(Breakend::new(
+                String::from("C"),
+                Orientation::Left,
+                Some(Mate::new(
+                    Chromosome::Symbol(String::from("ctg0")),
+                    Position::from(1),
+                    Orientation::Right
+                ))
+            ))
+        );
+
+        assert_eq!(
+            "G.".parse(),
+            Ok(Breakend::new(String::from("G"), Orientation::Left, None))
+        );
+
+        assert_eq!(
+            ".GC".parse(),
+            Ok(Breakend::new(String::from("GC"), Orientation::Right, None))
+        );
+
+        assert_eq!("".parse::<Breakend>(), Err(ParseError::Empty));
+        assert_eq!("G".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!(".".parse::<Breakend>(), Err(ParseError::MissingBases));
+        assert_eq!("[sq0:5[".parse::<Breakend>(), Err(ParseError::MissingBases));
+        assert_eq!("G[sq1:13]".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!("G[sq1:13[A".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!(
+            "G[sq1[".parse::<Breakend>(),
+            Err(ParseError::MissingMatePosition)
+        );
+        assert!(matches!(
+            "G[:13[".parse::<Breakend>(),
+            Err(ParseError::InvalidMateChromosome(_))
+        ));
+        assert!(matches!(
+            "G[sq1:x[".parse::<Breakend>(),
+            Err(ParseError::InvalidMatePosition(_))
+        ));
+    }
+}
@@ -29,6 +29,34 @@ impl DerefMut for Genotype {
     }
 }
 
+impl fmt::Display for Genotype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::allele::Phasing;
+
+        let (first_allele, alleles) = self.0.split_first().ok_or(fmt::Error)?;
+
+        // The phasing of the first allele is only written when it cannot be inferred from the
+        // phasing of the subsequent alleles.
+        let implicit_phasing = if alleles.iter().any(|a| a.phasing() == Phasing::Unphased) {
+            Phasing::Unphased
+        } else {
+            Phasing::Phased
+        };
+
+        if first_allele.phasing() != implicit_phasing {
+            write!(f, "{}", first_allele.phasing())?;
+        }
+
+        allele::fmt_position(f, first_allele.position())?;
+
+        for allele in alleles {
+            write!(f, "{allele}")?;
+        }
+
+        Ok(())
+    }
+}
+
 impl FromStr for Genotype {
     type Err = ParseError;
 
@@ -75,6 +103,14 @@ impl fmt::Display for TryFromAllelesError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fmt() {
+        for s in ["0/1", "0|1", "./.", "0", "0/1/2", "0/1|2", "|0/1/2", "/0"] {
+            let genotype: Genotype = s.parse().unwrap();
+            assert_eq!(genotype.to_string(), s);
+        }
+    }
+
     #[test]
     fn test_from_str() {
         use allele::Phasing;
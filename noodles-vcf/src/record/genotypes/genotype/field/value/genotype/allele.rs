@@ -114,6 +114,20 @@ impl fmt::Display for ParseError {
     }
 }
 
+impl fmt::Display for Allele {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.phasing)?;
+        fmt_position(f, self.position)
+    }
+}
+
+pub(super) fn fmt_position(f: &mut fmt::Formatter<'_>, position: Option<usize>) -> fmt::Result {
+    match position {
+        Some(n) => write!(f, "{n}"),
+        None => f.write_str(MISSING_POSITION),
+    }
+}
+
 impl FromStr for Allele {
     type Err = ParseError;
 
@@ -141,6 +155,13 @@ pub(super) fn parse_position(s: &str) -> Result<Option<usize>, ParseError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Allele::new(None, Phasing::Unphased).to_string(), "/.");
+        assert_eq!(Allele::new(Some(0), Phasing::Unphased).to_string(), "/0");
+        assert_eq!(Allele::new(Some(13), Phasing::Phased).to_string(), "|13");
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!("/.".parse(), Ok(Allele::new(None, Phasing::Unphased)));
@@ -0,0 +1,462 @@
+//! VCF structural variant events.
+//!
+//! [`events`] interprets the alternate alleles of a record as structural variants. A symbolic
+//! structural variant allele (e.g., `<DEL>`, `<DUP:TANDEM>`) is combined with the `END`, `SVLEN`,
+//! `CIPOS`, and `CIEND` info fields to form an [`Interval`], and a breakend allele (e.g.,
+//! `G[sq1:13[`) is combined with the record position and `CIPOS` to form an [`Adjacency`]. Both
+//! carry the claim of the call (`SVCLAIM`), if set.
+
+use std::{error, fmt, io, ops::RangeInclusive, str::FromStr};
+
+use crate::{
+    header::info::key,
+    record::{
+        alternate_bases::{
+            allele::{
+                symbol::{self, structural_variant::Type},
+                Breakend, Symbol,
+            },
+            Allele,
+        },
+        info::field::Value,
+        Chromosome, Position,
+    },
+    Record,
+};
+
+/// A structural variant event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// A variant spanning an interval of the reference sequence.
+    Interval(Interval),
+    /// A novel adjacency described by a breakend.
+    Adjacency(Adjacency),
+}
+
+/// A structural variant claim (`SVCLAIM`).
+///
+/// Added in VCF 4.4.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Claim {
+    /// A change in abundance, e.g., read depth (`D`).
+    Abundance,
+    /// A novel adjacency (`J`).
+    Adjacency,
+    /// Both a change in abundance and a novel adjacency (`DJ`).
+    AbundanceAndAdjacency,
+}
+
+impl AsRef<str> for Claim {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Abundance => "D",
+            Self::Adjacency => "J",
+            Self::AbundanceAndAdjacency => "DJ",
+        }
+    }
+}
+
+impl fmt::Display for Claim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+/// An error returned when a raw structural variant claim fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+        }
+    }
+}
+
+impl FromStr for Claim {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ParseError::Empty),
+            "D" => Ok(Self::Abundance),
+            "J" => Ok(Self::Adjacency),
+            "DJ" => Ok(Self::AbundanceAndAdjacency),
+            _ => Err(ParseError::Invalid),
+        }
+    }
+}
+
+/// A structural variant spanning an interval of the reference sequence.
+///
+/// As with symbolic alleles, the position is that of the base preceding the variant, i.e., the
+/// variant affects the reference bases after the position through the end position.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Interval {
+    chromosome: Chromosome,
+    position: Position,
+    end: Position,
+    symbol: symbol::StructuralVariant,
+    length: Option<i32>,
+    position_confidence_interval: Option<RangeInclusive<i32>>,
+    end_confidence_interval: Option<RangeInclusive<i32>>,
+    claim: Option<Claim>,
+}
+
+impl Interval {
+    /// Returns the chromosome.
+    pub fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    /// Returns the position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the end position.
+    ///
+    /// This is `END`, if set; otherwise, it is derived from `SVLEN` for deletions, duplications,
+    /// inversions, and copy number variations.
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Returns the structural variant symbol, e.g., `DEL`, `DUP:TANDEM`, etc.
+    pub fn symbol(&self) -> &symbol::StructuralVariant {
+        &self.symbol
+    }
+
+    /// Returns the structural variant length (`SVLEN`).
+    ///
+    /// The sign of the value is as written. VCF 4.3 uses negative lengths for deletions.
+    pub fn length(&self) -> Option<i32> {
+        self.length
+    }
+
+    /// Returns the confidence interval around the position (`CIPOS`).
+    pub fn position_confidence_interval(&self) -> Option<&RangeInclusive<i32>> {
+        self.position_confidence_interval.as_ref()
+    }
+
+    /// Returns the confidence interval around the end position (`CIEND`).
+    pub fn end_confidence_interval(&self) -> Option<&RangeInclusive<i32>> {
+        self.end_confidence_interval.as_ref()
+    }
+
+    /// Returns the structural variant claim (`SVCLAIM`).
+    pub fn claim(&self) -> Option<Claim> {
+        self.claim
+    }
+}
+
+/// A novel adjacency between the record position and, unless it is a single breakend, a mate
+/// position.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Adjacency {
+    chromosome: Chromosome,
+    position: Position,
+    breakend: Breakend,
+    confidence_interval: Option<RangeInclusive<i32>>,
+    claim: Option<Claim>,
+}
+
+impl Adjacency {
+    /// Returns the chromosome of the breakend.
+    pub fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    /// Returns the position of the breakend.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the breakend allele.
+    ///
+    /// This describes the orientation, mate, and inserted sequence of the adjacency.
+    pub fn breakend(&self) -> &Breakend {
+        &self.breakend
+    }
+
+    /// Returns the confidence interval around the position of the breakend (`CIPOS`).
+    pub fn confidence_interval(&self) -> Option<&RangeInclusive<i32>> {
+        self.confidence_interval.as_ref()
+    }
+
+    /// Returns the structural variant claim (`SVCLAIM`).
+    pub fn claim(&self) -> Option<Claim> {
+        self.claim
+    }
+}
+
+/// Returns the structural variant events of a record.
+///
+/// There is one entry per alternate allele, in order. It is `None` if the allele is not a
+/// structural variant, e.g., a list of bases or a nonstructural variant symbol (`<*>`).
+///
+/// For multiallelic records, `SVLEN` and `SVCLAIM` are taken per allele if they have a value for
+/// each alternate allele and `CIPOS` and `CIEND`, if they have a pair of values for each alternate allele.
+/// Otherwise, the first value or pair applies to all alleles.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     record::Position,
+///     structural_variant::{self, Event},
+/// };
+///
+/// let header = vcf::Header::default();
+/// let record = vcf::Record::try_from_str(
+///     "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tEND=13;CIPOS=-2,3",
+///     &header,
+/// )?;
+///
+/// let events = structural_variant::events(&record)?;
+///
+/// match &events[0] {
+///     Some(Event::Interval(interval)) => {
+///         assert_eq!(interval.end(), Position::from(13));
+///         assert_eq!(interval.position_confidence_interval(), Some(&(-2..=3)));
+///     }
+///     _ => panic!("expected an interval"),
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn events(record: &Record) -> io::Result<Vec<Option<Event>>> {
+    let alternate_bases = record.alternate_bases();
+    let allele_count = alternate_bases.len();
+
+    let lengths = get_integers(record, &key::SV_LENGTHS)?;
+    let position_confidence_intervals = get_integers(record, &key::POSITION_CONFIDENCE_INTERVALS)?;
+    let end_confidence_intervals = get_integers(record, &key::END_CONFIDENCE_INTERVALS)?;
+    let claims = get_claims(record)?;
+
+    alternate_bases
+        .iter()
+        .enumerate()
+        .map(|(i, allele)| match allele {
+            Allele::Symbol(Symbol::StructuralVariant(sv)) => {
+                let length = allele_value(&lengths, i, allele_count);
+                let claim = allele_value(&claims, i, allele_count);
+                let end = resolve_end(record, sv.ty(), length)?;
+
+                Ok(Some(Event::Interval(Interval {
+                    chromosome: record.chromosome().clone(),
+                    position: record.position(),
+                    end,
+                    symbol: sv.clone(),
+                    length,
+                    position_confidence_interval: allele_pair(
+                        &position_confidence_intervals,
+                        i,
+                        allele_count,
+                    ),
+                    end_confidence_interval: allele_pair(
+                        &end_confidence_intervals,
+                        i,
+                        allele_count,
+                    ),
+                    claim,
+                })))
+            }
+            Allele::Breakend(breakend) => Ok(Some(Event::Adjacency(Adjacency {
+                chromosome: record.chromosome().clone(),
+                position: record.position(),
+                breakend: breakend.clone(),
+                confidence_interval: allele_pair(&position_confidence_intervals, i, allele_count),
+                claim: allele_value(&claims, i, allele_count),
+            }))),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+fn get_integers(record: &Record, key: &key::Key) -> io::Result<Vec<Option<i32>>> {
+    match record.info().get(key) {
+        None | Some(None) => Ok(Vec::new()),
+        Some(Some(Value::Integer(n))) => Ok(vec![Some(*n)]),
+        Some(Some(Value::IntegerArray(values))) => Ok(values.clone()),
+        Some(Some(_)) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid {key} value type"),
+        )),
+    }
+}
+
+fn get_claims(record: &Record) -> io::Result<Vec<Option<Claim>>> {
+    fn parse_claim(s: &str) -> io::Result<Claim> {
+        s.parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    match record.info().get(&key::SV_CLAIM) {
+        None | Some(None) => Ok(Vec::new()),
+        Some(Some(Value::String(s))) => parse_claim(s).map(|claim| vec![Some(claim)]),
+        Some(Some(Value::StringArray(values))) => values
+            .iter()
+            .map(|value| value.as_deref().map(parse_claim).transpose())
+            .collect(),
+        Some(Some(_)) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid {} value type", key::SV_CLAIM),
+        )),
+    }
+}
+
+fn allele_value<T>(values: &[Option<T>], i: usize, allele_count: usize) -> Option<T>
+where
+    T: Copy,
+{
+    if values.len() == allele_count {
+        values[i]
+    } else {
+        values.first().copied().flatten()
+    }
+}
+
+fn allele_pair(
+    values: &[Option<i32>],
+    i: usize,
+    allele_count: usize,
+) -> Option<RangeInclusive<i32>> {
+    let j = if values.len() == 2 * allele_count {
+        2 * i
+    } else {
+        0
+    };
+
+    match (values.get(j), values.get(j + 1)) {
+        (Some(Some(start)), Some(Some(end))) => Some(*start..=*end),
+        _ => None,
+    }
+}
+
+fn resolve_end(record: &Record, ty: Type, length: Option<i32>) -> io::Result<Position> {
+    let has_end_position = matches!(record.info().get(&key::END_POSITION), Some(Some(_)));
+
+    if !has_end_position {
+        if let (
+            Type::Deletion | Type::Duplication | Type::Inversion | Type::CopyNumberVariation,
+            Some(len),
+        ) = (ty, length)
+        {
+            let start = usize::from(record.position());
+
+            let end = usize::try_from(len.unsigned_abs())
+                .ok()
+                .and_then(|len| start.checked_add(len))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid SV length"))?;
+
+            return Ok(Position::from(end));
+        }
+    }
+
+    record
+        .end()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{record::alternate_bases::allele::breakend::Orientation, Header};
+
+    fn interval(event: &Option<Event>) -> &Interval {
+        match event {
+            Some(Event::Interval(interval)) => interval,
+            _ => panic!("expected an interval"),
+        }
+    }
+
+    #[test]
+    fn test_events() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::default();
+
+        let record = Record::try_from_str(
+            "sq0\t8\t.\tA\t<DEL>,<DUP:TANDEM>,C\t.\tPASS\tSVLEN=-5,10,.;CIPOS=-2,3,0,0,.,.;CIEND=-1,1;SVCLAIM=D,DJ,.",
+            &header,
+        )?;
+        let events = super::events(&record)?;
+        assert_eq!(events.len(), 3);
+
+        let deletion = interval(&events[0]);
+        assert_eq!(
+            deletion.chromosome(),
+            &Chromosome::Name(String::from("sq0"))
+        );
+        assert_eq!(deletion.position(), Position::from(8));
+        assert_eq!(deletion.end(), Position::from(13));
+        assert_eq!(deletion.symbol().ty(), Type::Deletion);
+        assert_eq!(deletion.length(), Some(-5));
+        assert_eq!(deletion.position_confidence_interval(), Some(&(-2..=3)));
+        assert_eq!(deletion.end_confidence_interval(), Some(&(-1..=1)));
+        assert_eq!(deletion.claim(), Some(Claim::Abundance));
+
+        let duplication = interval(&events[1]);
+        assert_eq!(duplication.end(), Position::from(18));
+        assert_eq!(duplication.symbol().subtypes(), [String::from("TANDEM")]);
+        assert_eq!(duplication.length(), Some(10));
+        assert_eq!(duplication.position_confidence_interval(), Some(&(0..=0)));
+        assert_eq!(duplication.end_confidence_interval(), Some(&(-1..=1)));
+        assert_eq!(duplication.claim(), Some(Claim::AbundanceAndAdjacency));
+
+        assert!(events[2].is_none());
+
+        let record =
+            Record::try_from_str("sq0\t8\t.\tA\t<INS>\t.\tPASS\tEND=8;SVLEN=100", &header)?;
+        let events = super::events(&record)?;
+        let insertion = interval(&events[0]);
+        assert_eq!(insertion.end(), Position::from(8));
+        assert_eq!(insertion.length(), Some(100));
+        assert!(insertion.position_confidence_interval().is_none());
+
+        let record = Record::try_from_str(
+            "sq0\t8\t.\tA\tA[sq1:13[,<*>\t.\tPASS\tCIPOS=-4,4;SVCLAIM=J,.",
+            &header,
+        )?;
+        let events = super::events(&record)?;
+
+        match &events[0] {
+            Some(Event::Adjacency(adjacency)) => {
+                assert_eq!(adjacency.position(), Position::from(8));
+                assert_eq!(adjacency.breakend().orientation(), Orientation::Left);
+                assert_eq!(
+                    adjacency.breakend().mate().map(|mate| mate.position()),
+                    Some(Position::from(13))
+                );
+                assert_eq!(adjacency.confidence_interval(), Some(&(-4..=4)));
+                assert_eq!(adjacency.claim(), Some(Claim::Adjacency));
+            }
+            _ => panic!("expected an adjacency"),
+        }
+
+        assert!(events[1].is_none());
+
+        let record = Record::try_from_str("sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVCLAIM=X", &header)?;
+        assert!(super::events(&record).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_claim_from_str() {
+        assert_eq!("D".parse(), Ok(Claim::Abundance));
+        assert_eq!("J".parse(), Ok(Claim::Adjacency));
+        assert_eq!("DJ".parse(), Ok(Claim::AbundanceAndAdjacency));
+
+        assert_eq!("".parse::<Claim>(), Err(ParseError::Empty));
+        assert_eq!("JD".parse::<Claim>(), Err(ParseError::Invalid));
+    }
+}
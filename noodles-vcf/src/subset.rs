@@ -0,0 +1,258 @@
+//! VCF sample subsetting.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io;
+
+use crate::{recompute::Counts, record::Genotypes, Header, Record};
+
+/// A VCF sample subset.
+///
+/// This holds a header pruned to the selected samples and transforms records read with the
+/// original header to match it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subset {
+    header: Header,
+    sample_count: usize,
+    sample_indices: Vec<usize>,
+    recompute_allele_counts: bool,
+}
+
+impl Subset {
+    /// Returns a builder to create a sample subset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset::Subset;
+    /// let builder = Subset::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the pruned header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, header::SampleNames, subset::Subset};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let subset = Subset::builder().set_sample_names(["sample1"]).build(&header)?;
+    ///
+    /// let expected: SampleNames = [String::from("sample1")].into_iter().collect();
+    /// assert_eq!(subset.header().sample_names(), &expected);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the indices of the selected samples in the original header.
+    ///
+    /// The indices are in output order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, subset::Subset};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .add_sample_name("sample2")
+    ///     .build();
+    ///
+    /// let subset = Subset::builder()
+    ///     .set_sample_indices(vec![1])
+    ///     .set_exclude(true)
+    ///     .build(&header)?;
+    ///
+    /// assert_eq!(subset.sample_indices(), [0, 2]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn sample_indices(&self) -> &[usize] {
+        &self.sample_indices
+    }
+
+    /// Returns whether `AC` and `AN` are recomputed from the selected genotypes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, subset::Subset};
+    /// let header = vcf::Header::default();
+    /// let subset = Subset::builder().build(&header)?;
+    /// assert!(!subset.recomputes_allele_counts());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn recomputes_allele_counts(&self) -> bool {
+        self.recompute_allele_counts
+    }
+
+    /// Returns the number of samples in the original header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, subset::Subset};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let subset = Subset::builder().set_sample_names(["sample1"]).build(&header)?;
+    /// assert_eq!(subset.original_sample_count(), 2);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn original_sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    /// Drops the unselected samples from a record.
+    ///
+    /// The record is expected to have the samples of the original header. If allele count
+    /// recomputation is enabled, the `AC` and `AN` info fields are set from the selected `GT`
+    /// values when they are defined in the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, subset::Subset};
+    ///
+    /// let data = b"##fileformat=VCFv4.3\n##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">\n##INFO=<ID=AN,Number=1,Type=Integer,Description=\"Total number of alleles in called genotypes\">
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1\tsample2
+    /// sq0\t1\t.\tA\tC\t.\tPASS\tAC=3;AN=6\tGT\t0/1\t1/1\t0/0
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// let header = reader.read_header()?.parse()?;
+    ///
+    /// let subset = Subset::builder()
+    ///     .set_sample_names(["sample2", "sample0"])
+    ///     .set_recompute_allele_counts(true)
+    ///     .build(&header)?;
+    ///
+    /// let mut record = reader.records(&header).next().transpose()?.unwrap();
+    /// subset.apply(&mut record)?;
+    ///
+    /// assert_eq!(record.to_string(), "sq0\t1\t.\tA\tC\t.\tPASS\tAC=1;AN=4\tGT\t0/0\t0/1");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn apply(&self, record: &mut Record) -> io::Result<()> {
+        let genotypes = record.genotypes();
+
+        if genotypes.is_empty() {
+            return Ok(());
+        }
+
+        if genotypes.len() != self.sample_count {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "sample count mismatch: expected {}, got {}",
+                    self.sample_count,
+                    genotypes.len()
+                ),
+            ));
+        }
+
+        let selected_genotypes = self
+            .sample_indices
+            .iter()
+            .map(|&i| genotypes[i].clone())
+            .collect();
+
+        *record.genotypes_mut() = Genotypes::new(genotypes.keys().clone(), selected_genotypes);
+
+        if self.recompute_allele_counts {
+            recompute_allele_counts(&self.header, record)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn recompute_allele_counts(header: &Header, record: &mut Record) -> io::Result<()> {
+    match Counts::from_record(record)? {
+        Some(counts) => counts.update_allele_count_fields(header, record.info_mut()),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b"##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count in genotypes\">
+##INFO=<ID=AN,Number=1,Type=Integer,Description=\"Total number of alleles in called genotypes\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=GQ,Number=1,Type=Integer,Description=\"Conditional genotype quality\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1\tsample2
+sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=2,2;AN=6\tGT:GQ\t0/1:7\t2/2:13\t1|.:21
+sq0\t2\t.\tA\tC\t.\tPASS\t.\tGQ\t7\t13\t21
+sq0\t3\t.\tA\tC\t.\tPASS\t.
+";
+
+        let mut reader = crate::Reader::new(&data[..]);
+        let header = reader.read_header()?.parse()?;
+        let records: Vec<_> = reader.records(&header).collect::<io::Result<_>>()?;
+
+        let subset = Subset::builder()
+            .set_sample_names(["sample1"])
+            .set_exclude(true)
+            .set_recompute_allele_counts(true)
+            .build(&header)?;
+
+        let actual: Vec<_> = records
+            .into_iter()
+            .map(|mut record| subset.apply(&mut record).map(|_| record.to_string()))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=2,0;AN=3\tGT:GQ\t0/1:7\t1|.:21",
+            "sq0\t2\t.\tA\tC\t.\tPASS\t.\tGQ\t7\t21",
+            "sq0\t3\t.\tA\tC\t.\tPASS\t.",
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_with_sample_count_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build();
+
+        let subset = Subset::builder()
+            .set_sample_indices(vec![1])
+            .build(&header)?;
+
+        let mut record: Record = "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/1".parse()?;
+
+        assert!(matches!(
+            subset.apply(&mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use std::io;
+
+use super::Subset;
+use crate::Header;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Selection {
+    Names(Vec<String>),
+    Indices(Vec<usize>),
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self::Indices(Vec::new())
+    }
+}
+
+/// A VCF sample subset builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    selection: Selection,
+    exclude: bool,
+    recompute_allele_counts: bool,
+}
+
+impl Builder {
+    /// Selects samples by name.
+    ///
+    /// This replaces any previous selection. Unless excluding, samples are output in the given
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset::Subset;
+    /// let builder = Subset::builder().set_sample_names(["sample0", "sample2"]);
+    /// ```
+    pub fn set_sample_names<I, S>(mut self, sample_names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.selection = Selection::Names(sample_names.into_iter().map(|s| s.into()).collect());
+        self
+    }
+
+    /// Selects samples by index in the original header.
+    ///
+    /// This replaces any previous selection. Unless excluding, samples are output in the given
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset::Subset;
+    /// let builder = Subset::builder().set_sample_indices(vec![0, 2]);
+    /// ```
+    pub fn set_sample_indices(mut self, sample_indices: Vec<usize>) -> Self {
+        self.selection = Selection::Indices(sample_indices);
+        self
+    }
+
+    /// Sets whether the selected samples are dropped rather than kept.
+    ///
+    /// When excluding, the remaining samples keep their original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset::Subset;
+    /// let builder = Subset::builder().set_exclude(true);
+    /// ```
+    pub fn set_exclude(mut self, exclude: bool) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
+    /// Sets whether to recompute the `AC` and `AN` info fields from the selected genotypes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset::Subset;
+    /// let builder = Subset::builder().set_recompute_allele_counts(true);
+    /// ```
+    pub fn set_recompute_allele_counts(mut self, recompute_allele_counts: bool) -> Self {
+        self.recompute_allele_counts = recompute_allele_counts;
+        self
+    }
+
+    /// Builds a sample subset of the given header.
+    ///
+    /// This returns an error if a sample name is not in the header, a sample index is out of
+    /// range, or a sample is selected more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, subset::Subset};
+    ///
+    /// let header = vcf::Header::builder().add_sample_name("sample0").build();
+    /// let subset = Subset::builder().set_sample_names(["sample0"]).build(&header)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build(self, header: &Header) -> io::Result<Subset> {
+        let sample_names = header.sample_names();
+        let sample_count = sample_names.len();
+
+        let selected_indices = match self.selection {
+            Selection::Names(names) => names
+                .iter()
+                .map(|name| {
+                    sample_names.get_index_of(name).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("missing sample: {name}"),
+                        )
+                    })
+                })
+                .collect::<io::Result<Vec<_>>>()?,
+            Selection::Indices(indices) => {
+                if let Some(i) = indices.iter().find(|&&i| i >= sample_count) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid sample index: {i}"),
+                    ));
+                }
+
+                indices
+            }
+        };
+
+        let mut is_selected = vec![false; sample_count];
+
+        for &i in &selected_indices {
+            if is_selected[i] {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("duplicate sample: {}", sample_names[i]),
+                ));
+            }
+
+            is_selected[i] = true;
+        }
+
+        let sample_indices = if self.exclude {
+            (0..sample_count).filter(|&i| !is_selected[i]).collect()
+        } else {
+            selected_indices
+        };
+
+        let mut subset_header = header.clone();
+
+        *subset_header.sample_names_mut() = sample_indices
+            .iter()
+            .map(|&i| sample_names[i].clone())
+            .collect();
+
+        Ok(Subset {
+            header: subset_header,
+            sample_count,
+            sample_indices,
+            recompute_allele_counts: self.recompute_allele_counts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_header() -> Header {
+        Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .add_sample_name("sample2")
+            .build()
+    }
+
+    #[test]
+    fn test_build() -> io::Result<()> {
+        let header = build_header();
+
+        let subset = Builder::default()
+            .set_sample_names(["sample2", "sample0"])
+            .build(&header)?;
+        assert_eq!(subset.sample_indices(), [2, 0]);
+        assert!(subset
+            .header()
+            .sample_names()
+            .iter()
+            .eq(["sample2", "sample0"]));
+
+        let subset = Builder::default()
+            .set_sample_names(["sample2", "sample0"])
+            .set_exclude(true)
+            .build(&header)?;
+        assert_eq!(subset.sample_indices(), [1]);
+
+        let subset = Builder::default()
+            .set_sample_indices(vec![1])
+            .build(&header)?;
+        assert_eq!(subset.sample_indices(), [1]);
+
+        let subset = Builder::default().set_exclude(true).build(&header)?;
+        assert_eq!(subset.sample_indices(), [0, 1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_invalid_selection() {
+        let header = build_header();
+
+        assert!(matches!(
+            Builder::default().set_sample_names(["sample3"]).build(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            Builder::default().set_sample_indices(vec![3]).build(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            Builder::default().set_sample_indices(vec![0, 0]).build(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}